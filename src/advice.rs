@@ -0,0 +1,190 @@
+use std::fmt;
+
+/// What kind of failure occurred
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Transient,    // timeout, connection reset - may succeed if tried again
+    Permanent,    // dependency gone, resource missing - won't fix itself
+    InvalidInput, // caller sent something malformed
+    Bug,          // an invariant we believed in was violated
+}
+
+impl ErrorKind {
+    pub const ALL: [ErrorKind; 4] = [
+        ErrorKind::Transient,
+        ErrorKind::Permanent,
+        ErrorKind::InvalidInput,
+        ErrorKind::Bug,
+    ];
+    
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorKind::Transient => "Transient",
+            ErrorKind::Permanent => "Permanent",
+            ErrorKind::InvalidInput => "Invalid input",
+            ErrorKind::Bug => "Bug (broken invariant)",
+        }
+    }
+    
+    /// As `advise --kind` takes it
+    pub fn id(self) -> &'static str {
+        match self {
+            ErrorKind::Transient => "transient",
+            ErrorKind::Permanent => "permanent",
+            ErrorKind::InvalidInput => "invalid-input",
+            ErrorKind::Bug => "bug",
+        }
+    }
+    
+    pub fn from_id(id: &str) -> Option<ErrorKind> {
+        ErrorKind::ALL.into_iter().find(|kind| kind.id() == id)
+    }
+}
+
+/// Where the failing call sits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallContext {
+    RequestPath, // serving a user right now
+    BatchJob,    // processing many independent items
+    Startup,     // before the service accepts traffic
+    Drop,        // inside a Drop impl, possibly during unwinding
+}
+
+impl CallContext {
+    pub const ALL: [CallContext; 4] = [
+        CallContext::RequestPath,
+        CallContext::BatchJob,
+        CallContext::Startup,
+        CallContext::Drop,
+    ];
+    
+    pub fn name(self) -> &'static str {
+        match self {
+            CallContext::RequestPath => "In a request path",
+            CallContext::BatchJob => "In a batch job",
+            CallContext::Startup => "At startup",
+            CallContext::Drop => "Inside Drop",
+        }
+    }
+    
+    /// As `advise --context` takes it
+    pub fn id(self) -> &'static str {
+        match self {
+            CallContext::RequestPath => "request-path",
+            CallContext::BatchJob => "batch-job",
+            CallContext::Startup => "startup",
+            CallContext::Drop => "drop",
+        }
+    }
+    
+    pub fn from_id(id: &str) -> Option<CallContext> {
+        CallContext::ALL.into_iter().find(|context| context.id() == id)
+    }
+}
+
+/// A named error handling pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pattern {
+    RetryWithBudget,
+    DegradeWithFallback,
+    RejectWithError,
+    SkipAndRecord,
+    FailLoudly,
+    IsolateAndReport,
+    NeverPanicInDrop,
+}
+
+impl Pattern {
+    pub fn name(self) -> &'static str {
+        match self {
+            Pattern::RetryWithBudget => "retry with backoff and a budget",
+            Pattern::DegradeWithFallback => "degrade with a fallback",
+            Pattern::RejectWithError => "reject with a descriptive error",
+            Pattern::SkipAndRecord => "skip the item and record it",
+            Pattern::FailLoudly => "fail loudly - stop before doing damage",
+            Pattern::IsolateAndReport => "isolate at the boundary and report",
+            Pattern::NeverPanicInDrop => "log it - never panic in Drop",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Recommendation {
+    pub kind: ErrorKind,
+    pub context: CallContext,
+    pub pattern: Pattern,
+}
+
+impl fmt::Display for Recommendation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} error {}: {}",
+               self.kind.name(), self.context.name().to_lowercase(), self.pattern.name())
+    }
+}
+
+/// The decision tree. Every combination is spelled out - no wildcard arms,
+/// so a new ErrorKind or CallContext won't compile until it has advice.
+pub fn recommend(kind: ErrorKind, context: CallContext) -> Recommendation {
+    use CallContext::*;
+    use ErrorKind::*;
+    
+    let pattern = match (kind, context) {
+        (Transient, RequestPath) => Pattern::RetryWithBudget,
+        (Transient, BatchJob) => Pattern::RetryWithBudget,
+        (Transient, Startup) => Pattern::RetryWithBudget,
+        (Transient, Drop) => Pattern::NeverPanicInDrop,
+        
+        (Permanent, RequestPath) => Pattern::DegradeWithFallback,
+        (Permanent, BatchJob) => Pattern::SkipAndRecord,
+        (Permanent, Startup) => Pattern::FailLoudly,
+        (Permanent, Drop) => Pattern::NeverPanicInDrop,
+        
+        (InvalidInput, RequestPath) => Pattern::RejectWithError,
+        (InvalidInput, BatchJob) => Pattern::SkipAndRecord,
+        (InvalidInput, Startup) => Pattern::FailLoudly,
+        (InvalidInput, Drop) => Pattern::NeverPanicInDrop,
+        
+        (Bug, RequestPath) => Pattern::IsolateAndReport,
+        (Bug, BatchJob) => Pattern::FailLoudly,
+        (Bug, Startup) => Pattern::FailLoudly,
+        (Bug, Drop) => Pattern::NeverPanicInDrop,
+    };
+    
+    Recommendation { kind, context, pattern }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn every_kind_and_context_has_real_advice() {
+        let mut seen = Vec::new();
+        for kind in ErrorKind::ALL {
+            for context in CallContext::ALL {
+                let recommendation = recommend(kind, context);
+                assert_eq!((recommendation.kind, recommendation.context), (kind, context));
+                let text = recommendation.to_string();
+                assert!(!recommendation.pattern.name().is_empty(), "{}", text);
+                assert!(!text.to_lowercase().contains("todo") && !text.contains("unimplemented"), "{}", text);
+                if context == CallContext::Drop {
+                    assert_eq!(recommendation.pattern, Pattern::NeverPanicInDrop);
+                }
+                seen.push((kind.id(), context.id()));
+            }
+        }
+        seen.dedup();
+        assert_eq!(seen.len(), ErrorKind::ALL.len() * CallContext::ALL.len());
+    }
+    
+    #[test]
+    fn ids_round_trip() {
+        for kind in ErrorKind::ALL {
+            assert_eq!(ErrorKind::from_id(kind.id()), Some(kind));
+        }
+        for context in CallContext::ALL {
+            assert_eq!(CallContext::from_id(context.id()), Some(context));
+        }
+        assert_eq!(ErrorKind::from_id("Transient"), None);
+    }
+}
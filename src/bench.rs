@@ -0,0 +1,146 @@
+use std::hint::black_box;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use crate::panics;
+
+/// How many stack frames the failure crosses before it is handled
+pub const DEPTH: u32 = 10;
+
+/// The least time each operation is repeated for
+pub const BUDGET: Duration = Duration::from_millis(100);
+
+/// One operation, timed over many iterations
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub name: &'static str,
+    pub iterations: u64,
+    pub elapsed: Duration,
+}
+
+impl Measurement {
+    pub fn ns_per_op(&self) -> f64 {
+        self.elapsed.as_nanos() as f64 / self.iterations as f64
+    }
+}
+
+/// Two ways to do the same thing. The contender is None when this build
+/// can't run it - a panic = "abort" build has nothing to catch a panic with
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub baseline: Measurement,
+    pub contender: Option<Measurement>,
+}
+
+impl Comparison {
+    /// How many times slower the contender is than the baseline
+    pub fn factor(&self) -> Option<f64> {
+        self.contender.as_ref().map(|contender| contender.ns_per_op() / self.baseline.ns_per_op())
+    }
+}
+
+/// Everything `bench` measures
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchReport {
+    pub propagation: Comparison,  // Err returned vs panic caught, through DEPTH frames
+    pub success_path: Comparison, // match vs unwrap() on Some
+    pub catch_setup: Comparison,  // a plain call vs the same call inside catch_unwind
+}
+
+/// Runs `op` in doubling batches until one batch takes at least `budget`,
+/// so a 1 ns operation and a 10 µs one are both timed over a real span
+pub fn measure(name: &'static str, budget: Duration, mut op: impl FnMut()) -> Measurement {
+    op(); // warm up: page in the code, let the branch predictor see it once
+    let mut iterations: u64 = 1;
+    loop {
+        let start = Instant::now();
+        for _ in 0..iterations {
+            op();
+        }
+        let elapsed = start.elapsed();
+        if elapsed >= budget || iterations >= 1 << 32 {
+            return Measurement { name, iterations, elapsed };
+        }
+        iterations *= 2;
+    }
+}
+
+/// The error the deepest frame returns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DeepFailure(u32);
+
+#[inline(never)]
+fn err_through(depth: u32, input: u32) -> Result<u32, DeepFailure> {
+    if depth == 0 {
+        return if input == u32::MAX { Ok(input) } else { Err(DeepFailure(input)) };
+    }
+    Ok(err_through(depth - 1, black_box(input))?.wrapping_add(1))
+}
+
+#[inline(never)]
+fn panic_through(depth: u32, input: u32) -> u32 {
+    if depth == 0 {
+        if input != u32::MAX {
+            panic!("deep failure: {}", input);
+        }
+        return input;
+    }
+    panic_through(depth - 1, black_box(input)).wrapping_add(1)
+}
+
+#[inline(never)]
+fn match_some(value: Option<u64>) -> u64 {
+    match value {
+        Some(value) => value,
+        None => no_value(), // handled, and never taken here
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn no_value() -> u64 {
+    0
+}
+
+#[inline(never)]
+fn unwrap_some(value: Option<u64>) -> u64 {
+    value.unwrap() // unwrap-ok: measured on Some only
+}
+
+#[inline(never)]
+fn add_one(value: u64) -> u64 {
+    value.wrapping_add(1)
+}
+
+/// Times every comparison, each operation for at least `budget`
+pub fn run(budget: Duration) -> BenchReport {
+    let propagation = Comparison {
+        baseline: measure("Err returned through 10 frames", budget, || {
+            let _ = black_box(err_through(DEPTH, black_box(0)));
+        }),
+        contender: panics::UNWINDS.then(|| {
+            panics::without_panic_messages(|| {
+                measure("panic caught through 10 frames", budget, || {
+                    let _ = black_box(panic::catch_unwind(|| panic_through(DEPTH, black_box(0))));
+                })
+            })
+        }),
+    };
+    let success_path = Comparison {
+        baseline: measure("match on Some", budget, || {
+            black_box(match_some(black_box(Some(7))));
+        }),
+        contender: Some(measure("unwrap() on Some", budget, || {
+            black_box(unwrap_some(black_box(Some(7))));
+        })),
+    };
+    let catch_setup = Comparison {
+        baseline: measure("plain call", budget, || {
+            black_box(add_one(black_box(7)));
+        }),
+        contender: Some(measure("the call inside catch_unwind", budget, || {
+            let _ = black_box(panic::catch_unwind(AssertUnwindSafe(|| add_one(black_box(7)))));
+        })),
+    };
+    BenchReport { propagation, success_path, catch_setup }
+}
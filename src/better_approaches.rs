@@ -0,0 +1,1198 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fmt;
+use std::io;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, SystemTime};
+
+/// Both ways i32 division can fail
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DivisionError {
+    Zero,
+    Overflow, // i32::MIN / -1: the answer is i32::MAX + 1
+}
+
+impl fmt::Display for DivisionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DivisionError::Zero => write!(f, "Division by zero"),
+            DivisionError::Overflow => write!(f, "Division overflows i32"),
+        }
+    }
+}
+
+impl std::error::Error for DivisionError {}
+
+pub fn divide_safe(a: i32, b: i32) -> Result<i32, DivisionError> {
+    match a.checked_div(b) {
+        Some(quotient) => Ok(quotient),
+        None if b == 0 => Err(DivisionError::Zero),
+        None => Err(DivisionError::Overflow),
+    }
+}
+
+/// Each step of parse_and_double that can fail, as its own variant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseAndDoubleError {
+    Parse { input: String, source: std::num::ParseIntError },
+    DivisionByZero,
+    Overflow,
+}
+
+impl fmt::Display for ParseAndDoubleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseAndDoubleError::Parse { input, .. } => write!(f, "Could not parse {:?} as a number", input),
+            ParseAndDoubleError::DivisionByZero => write!(f, "Division by zero"),
+            ParseAndDoubleError::Overflow => write!(f, "Result does not fit in an i32"),
+        }
+    }
+}
+
+impl std::error::Error for ParseAndDoubleError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseAndDoubleError::Parse { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+pub fn parse_and_double_safe(s: &str) -> Result<i32, ParseAndDoubleError> {
+    let num: i32 = s.parse()
+        .map_err(|source| ParseAndDoubleError::Parse { input: s.to_string(), source })?;
+    let halved = divide_safe(num, 2)
+        .map_err(|e| match e {
+            DivisionError::Zero => ParseAndDoubleError::DivisionByZero,
+            DivisionError::Overflow => ParseAndDoubleError::Overflow,
+        })?;
+    halved.checked_mul(2).ok_or(ParseAndDoubleError::Overflow)
+}
+
+/// Why a config file couldn't be read - each variant has a different fix
+#[derive(Debug)]
+pub enum ConfigError {
+    NotFound(PathBuf),
+    PermissionDenied(PathBuf),
+    InvalidUtf8 { path: PathBuf, valid_up_to: usize },
+    Io(io::Error), // anything else; the io::Error says what
+}
+
+impl ConfigError {
+    /// Sorts an io::Error by kind, remembering which file it was about
+    pub fn at(path: impl Into<PathBuf>, error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::NotFound => ConfigError::NotFound(path.into()),
+            io::ErrorKind::PermissionDenied => ConfigError::PermissionDenied(path.into()),
+            _ => ConfigError::Io(error),
+        }
+    }
+}
+
+/// For `?` where the path isn't at hand; prefer `ConfigError::at`
+impl From<io::Error> for ConfigError {
+    fn from(error: io::Error) -> Self {
+        ConfigError::at(PathBuf::new(), error)
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::NotFound(path) => write!(f, "Config file not found: {}", path.display()),
+            ConfigError::PermissionDenied(path) => {
+                write!(f, "Permission denied reading config file: {}", path.display())
+            }
+            ConfigError::InvalidUtf8 { path, valid_up_to } => {
+                write!(f, "Config file {} is not valid UTF-8 after byte {}", path.display(), valid_up_to)
+            }
+            ConfigError::Io(_) => write!(f, "Could not read config file"), // the cause is source()
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+pub fn read_config_file_safe(path: impl AsRef<Path>) -> Result<String, ConfigError> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path).map_err(|e| ConfigError::at(path, e))?;
+    String::from_utf8(bytes).map_err(|e| ConfigError::InvalidUtf8 {
+        path: path.to_path_buf(),
+        valid_up_to: e.utf8_error().valid_up_to(),
+    })
+}
+
+/// Which layer of a nested Option was empty, counting from the outside
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NestingError {
+    pub layer: usize, // 1 is the outermost Option
+}
+
+impl fmt::Display for NestingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Layer {} of 3 is None", self.layer)
+    }
+}
+
+impl std::error::Error for NestingError {}
+
+pub fn get_nested_value_safe(data: Option<Option<Option<i32>>>) -> Result<i32, NestingError> {
+    nested_missing_at(data).map_err(|at| NestingError { layer: at.layer() })
+}
+
+// The same nesting through combinators: one expression each, no unwrap()
+
+pub fn nested_flatten(data: Option<Option<Option<i32>>>) -> Option<i32> {
+    data.flatten().flatten()
+}
+
+pub fn nested_and_then(data: Option<Option<Option<i32>>>) -> Option<i32> {
+    data.and_then(|middle| middle.and_then(|inner| inner))
+}
+
+/// `?` works in any function returning Option, too
+pub fn nested_question_mark(data: Option<Option<Option<i32>>>) -> Option<i32> {
+    let middle = data?;
+    middle? // the inner Option is already the answer
+}
+
+/// Which layer of `Option<Option<Option<_>>>` was None
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingAt {
+    Outer,
+    Middle,
+    Inner,
+}
+
+impl MissingAt {
+    /// 1 for the outermost layer
+    pub fn layer(self) -> usize {
+        match self {
+            MissingAt::Outer => 1,
+            MissingAt::Middle => 2,
+            MissingAt::Inner => 3,
+        }
+    }
+}
+
+/// The combinators can't say where the None was; ok_or can
+pub fn nested_missing_at(data: Option<Option<Option<i32>>>) -> Result<i32, MissingAt> {
+    data.ok_or(MissingAt::Outer)?
+        .ok_or(MissingAt::Middle)?
+        .ok_or(MissingAt::Inner)
+}
+
+/// An index past the end, with the length it was checked against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexError {
+    pub index: usize,
+    pub len: usize,
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Index {} is out of bounds for length {}", self.index, self.len)
+    }
+}
+
+impl std::error::Error for IndexError {}
+
+/// An owned copy of the element; see `get_element_ref` to avoid the clone
+pub fn get_element_safe<T: Clone>(slice: &[T], index: usize) -> Result<T, IndexError> {
+    get_element_ref(slice, index).cloned()
+}
+
+pub fn get_element_ref<T>(slice: &[T], index: usize) -> Result<&T, IndexError> {
+    slice.get(index).ok_or(IndexError { index, len: slice.len() })
+}
+
+/// A key that wasn't in the map, and the keys that were
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingKey {
+    pub key: String,
+    pub available: Vec<String>, // sorted, so the message is stable
+}
+
+impl fmt::Display for MissingKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "No entry for '{}' (known: {})", self.key, self.available.join(", "))
+    }
+}
+
+impl std::error::Error for MissingKey {}
+
+pub fn get_user_age_safe(map: &HashMap<String, u32>, name: &str) -> Result<u32, MissingKey> {
+    map.get(name).copied().ok_or_else(|| {
+        let mut available: Vec<String> = map.keys().cloned().collect();
+        available.sort();
+        MissingKey { key: name.to_string(), available }
+    })
+}
+
+/// When a missing key has a sensible default: the entry API inserts it
+pub fn get_user_age_or_insert(map: &mut HashMap<String, u32>, name: &str, default: u32) -> u32 {
+    *map.entry(name.to_string()).or_insert(default)
+}
+
+/// Reads through a poisoned lock. Poisoning only says a thread panicked
+/// while holding it; a counter is whole after every `+= 1`, so the value is
+/// still trustworthy. When an update spans several steps and the panic could
+/// have landed between them, recovering would serve half-written data -
+/// report the poisoning instead.
+pub fn read_counter_recovering(counter: &Mutex<u32>) -> u32 {
+    *counter.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Parses `key=value` lines. Keys and values are trimmed; blank lines,
+/// `#` comments and lines without `=` are skipped. A repeated key keeps
+/// its last value, so later lines override earlier ones.
+pub fn parse_config(text: &str) -> HashMap<String, String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+/// A parsed config that lacks a field, or has one that doesn't parse
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigFieldError {
+    MissingKey(String),
+    InvalidValue { key: String, value: String, source: std::num::ParseIntError },
+}
+
+impl fmt::Display for ConfigFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigFieldError::MissingKey(key) => write!(f, "Config has no '{}' key", key),
+            ConfigFieldError::InvalidValue { key, value, .. } => {
+                write!(f, "Config key '{}' has invalid value {:?}", key, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigFieldError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigFieldError::InvalidValue { source, .. } => Some(source),
+            ConfigFieldError::MissingKey(_) => None,
+        }
+    }
+}
+
+pub fn get_port_safe(config: &HashMap<String, String>) -> Result<u16, ConfigFieldError> {
+    let value = config.get("port").ok_or_else(|| ConfigFieldError::MissingKey(String::from("port")))?;
+    value.parse().map_err(|source| ConfigFieldError::InvalidValue {
+        key: String::from("port"),
+        value: value.clone(),
+        source,
+    })
+}
+
+/// Why an environment variable couldn't be used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvError {
+    Missing(String),    // the variable's name
+    NotUnicode(String), // set, but to bytes that aren't UTF-8
+}
+
+impl fmt::Display for EnvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EnvError::Missing(name) => write!(f, "Environment variable {} is not set", name),
+            EnvError::NotUnicode(name) => write!(f, "Environment variable {} is not valid Unicode", name),
+        }
+    }
+}
+
+impl std::error::Error for EnvError {}
+
+pub fn env_var_safe(name: &str) -> Result<String, EnvError> {
+    env_var_in(name, |name| std::env::var_os(name))
+}
+
+/// env_var_safe against `lookup` instead of the process environment, which
+/// other threads read: a map stands in for it without setting anything
+pub fn env_var_in(name: &str, lookup: impl Fn(&str) -> Option<OsString>) -> Result<String, EnvError> {
+    let value = lookup(name).ok_or_else(|| EnvError::Missing(name.to_string()))?;
+    value.into_string().map_err(|_| EnvError::NotUnicode(name.to_string()))
+}
+
+/// Which layer a setting came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingSource {
+    Environment,
+    ConfigFile,
+    Default,
+}
+
+/// Environment first, then a `NAME = value` line in the config file, then
+/// the compiled-in default. An unreadable config file counts as absent; a
+/// variable that is set but not Unicode is a mistake worth reporting.
+pub fn layered_setting(
+    name: &str,
+    config: &Path,
+    default: &str,
+) -> Result<(String, SettingSource), EnvError> {
+    layered_setting_in(name, config, default, |name| std::env::var_os(name))
+}
+
+/// layered_setting with `lookup` as the environment layer
+pub fn layered_setting_in(
+    name: &str,
+    config: &Path,
+    default: &str,
+    lookup: impl Fn(&str) -> Option<OsString>,
+) -> Result<(String, SettingSource), EnvError> {
+    match env_var_in(name, lookup) {
+        Ok(value) => return Ok((value, SettingSource::Environment)),
+        Err(EnvError::Missing(_)) => {}
+        Err(e) => return Err(e),
+    }
+    let from_file = read_config_file_safe(config).ok()
+        .and_then(|contents| parse_config(&contents).remove(name));
+    match from_file {
+        Some(value) => Ok((value, SettingSource::ConfigFile)),
+        None => Ok((default.to_string(), SettingSource::Default)),
+    }
+}
+
+/// The wall clock was set back (NTP, a VM resume, a person) and a
+/// timestamp we recorded now lies in the future
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockSkew {
+    pub ahead_by: Duration, // how far in the future the timestamp is
+}
+
+impl fmt::Display for ClockSkew {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Clock skew: timestamp is {:?} in the future", self.ahead_by)
+    }
+}
+
+impl std::error::Error for ClockSkew {}
+
+pub fn elapsed_checked(since: SystemTime) -> Result<Duration, ClockSkew> {
+    SystemTime::now().duration_since(since)
+        .map_err(|e| ClockSkew { ahead_by: e.duration() })
+}
+
+/// For timeouts and metrics, "no time has passed" is the safe reading of skew
+pub fn elapsed_or_zero(since: SystemTime) -> Duration {
+    elapsed_checked(since).unwrap_or(Duration::ZERO)
+}
+
+// Three ways to decode bytes that may not be UTF-8, each losing something different
+
+/// Propagate: nothing is lost - `FromUtf8Error::into_bytes` hands the buffer back
+pub fn decode_message_safe(bytes: Vec<u8>) -> Result<String, std::string::FromUtf8Error> {
+    String::from_utf8(bytes)
+}
+
+/// Replace: always a String, but invalid bytes become U+FFFD for good
+pub fn decode_message_lossy(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+/// Truncate: keep the valid prefix, drop everything from the first bad byte on
+pub fn decode_message_truncated(bytes: &[u8]) -> &str {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text,
+        // Everything before valid_up_to() was checked, so this can't fail
+        Err(e) => std::str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or_default(),
+    }
+}
+
+/// How a stream of results from a worker ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamEnd {
+    Complete,     // every expected result arrived
+    ProducerGone, // the sender was dropped first - the worker stopped or died
+    TimedOut,     // the worker is alive but silent for too long
+}
+
+/// Whatever arrived before the stream ended, and why it ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Collected {
+    pub results: Vec<u32>,
+    pub end: StreamEnd,
+}
+
+/// Drains up to `expected` results, never waiting more than `timeout` for one
+pub fn collect_results(results: &Receiver<u32>, expected: usize, timeout: Duration) -> Collected {
+    let mut received = Vec::with_capacity(expected);
+    while received.len() < expected {
+        match results.recv_timeout(timeout) {
+            Ok(result) => received.push(result),
+            Err(RecvTimeoutError::Disconnected) => {
+                return Collected { results: received, end: StreamEnd::ProducerGone }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                return Collected { results: received, end: StreamEnd::TimedOut }
+            }
+        }
+    }
+    Collected { results: received, end: StreamEnd::Complete }
+}
+
+/// An aggregate was asked of no data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmptyDataset;
+
+impl fmt::Display for EmptyDataset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "No rows to aggregate")
+    }
+}
+
+impl std::error::Error for EmptyDataset {}
+
+/// When 0 is an honest answer for "no prices"
+pub fn highest_price_or_default(prices: &[u32], floor: u32) -> u32 {
+    prices.iter().filter(|&&price| price > floor).max().copied().unwrap_or_default()
+}
+
+/// When it isn't: the caller decides what no rows means
+pub fn highest_price_checked(prices: &[u32], floor: u32) -> Result<u32, EmptyDataset> {
+    prices.iter().filter(|&&price| price > floor).max().copied().ok_or(EmptyDataset)
+}
+
+/// One pass over f64 data. f64 isn't Ord - NaN compares with nothing - so
+/// there is no max(); NaNs are counted and left out of every aggregate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub count: usize, // values that aren't NaN
+    pub nan_count: usize,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+}
+
+pub fn summarize(values: &[f64]) -> Summary {
+    let (count, nan_count, sum, min, max) = values.iter().fold(
+        (0, 0, 0.0, f64::INFINITY, f64::NEG_INFINITY),
+        |(count, nans, sum, min, max), &value| {
+            if value.is_nan() {
+                (count, nans + 1, sum, min, max)
+            } else {
+                (count + 1, nans, sum + value, f64::min(min, value), f64::max(max, value))
+            }
+        },
+    );
+    let present = |x| (count > 0).then_some(x);
+    Summary {
+        count,
+        nan_count,
+        min: present(min),
+        max: present(max),
+        mean: present(sum / count as f64),
+    }
+}
+
+/// A value that doesn't fit the type it was converted to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConversionError {
+    pub value: u64,
+    pub target_type: &'static str,
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} does not fit in {}", self.value, self.target_type)
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+/// Checked narrowing into any integer type, keeping the value that didn't fit
+pub fn convert<T: TryFrom<u64>>(value: u64) -> Result<T, ConversionError> {
+    T::try_from(value).map_err(|_| ConversionError { value, target_type: std::any::type_name::<T>() })
+}
+
+/// When clamping is the right answer - a progress counter, not a length
+pub fn to_u32_saturating(value: u64) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
+}
+
+/// Cleanup that runs on drop and may fail; see `defer_fallible`
+pub struct Deferred<F, E, R>
+where
+    F: FnOnce() -> Result<(), E>,
+    R: FnOnce(E),
+{
+    cleanup: Option<F>,
+    on_error: Option<R>,
+    error: PhantomData<fn() -> E>,
+}
+
+impl<F, E, R> Deferred<F, E, R>
+where
+    F: FnOnce() -> Result<(), E>,
+    R: FnOnce(E),
+{
+    /// Runs the cleanup now, where its error can still be returned
+    pub fn finish(mut self) -> Result<(), E> {
+        self.cleanup.take().map_or(Ok(()), |cleanup| cleanup())
+    }
+}
+
+impl<F, E, R> Drop for Deferred<F, E, R>
+where
+    F: FnOnce() -> Result<(), E>,
+    R: FnOnce(E),
+{
+    fn drop(&mut self) {
+        if let Some(cleanup) = self.cleanup.take() {
+            if let (Err(e), Some(on_error)) = (cleanup(), self.on_error.take()) {
+                on_error(e);
+            }
+        }
+    }
+}
+
+/// Drop can't return an error, and panicking there aborts if an unwind is
+/// already under way - so a failed cleanup is handed to `on_error` instead:
+/// the caller's log, metrics or channel
+pub fn defer_fallible<F, E, R>(cleanup: F, on_error: R) -> Deferred<F, E, R>
+where
+    F: FnOnce() -> Result<(), E>,
+    R: FnOnce(E),
+{
+    Deferred { cleanup: Some(cleanup), on_error: Some(on_error), error: PhantomData }
+}
+
+/// An error with a note about what was being attempted when it happened
+#[derive(Debug)]
+pub struct ContextError<E> {
+    message: Cow<'static, str>,
+    source: E,
+}
+
+impl<E> ContextError<E> {
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+    
+    /// The error the context was attached to
+    pub fn inner(&self) -> &E {
+        &self.source
+    }
+    
+    pub fn into_inner(self) -> E {
+        self.source
+    }
+}
+
+impl<E> fmt::Display for ContextError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message) // the cause is source()
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for ContextError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Adds context while propagating: `read(path).context("reading service config")?`
+pub trait Context<T, E>: Sized {
+    fn context(self, msg: &'static str) -> Result<T, ContextError<E>>;
+    
+    /// Builds the message only if there is an error to attach it to
+    fn with_context<F: FnOnce() -> String>(self, f: F) -> Result<T, ContextError<E>>;
+}
+
+impl<T, E: std::error::Error> Context<T, E> for Result<T, E> {
+    fn context(self, msg: &'static str) -> Result<T, ContextError<E>> {
+        self.map_err(|source| ContextError { message: Cow::Borrowed(msg), source })
+    }
+    
+    fn with_context<F: FnOnce() -> String>(self, f: F) -> Result<T, ContextError<E>> {
+        self.map_err(|source| ContextError { message: Cow::Owned(f()), source })
+    }
+}
+
+/// How many causes the chain reports print before eliding the rest
+pub const MAX_CHAIN_DEPTH: usize = 16;
+
+/// An error and every cause behind it, one per line:
+///
+/// ```text
+/// Could not read config file
+///   caused by: Could not parse "80x" as a number
+///   caused by: invalid digit found in string
+/// ```
+pub fn report_error(err: &dyn std::error::Error) -> String {
+    render_error_chain(err, "\n  caused by: ", MAX_CHAIN_DEPTH)
+}
+
+/// The same chain on one line, joined by ": "
+pub fn report_error_line(err: &dyn std::error::Error) -> String {
+    render_error_chain(err, ": ", MAX_CHAIN_DEPTH)
+}
+
+/// Walks `source()` from `err`, writing at most `max_depth` causes
+/// Everything goes straight into the one output string
+pub fn render_error_chain(err: &dyn std::error::Error, separator: &str, max_depth: usize) -> String {
+    use std::fmt::Write;
+    
+    let mut out = String::new();
+    let _ = write!(out, "{}", err);
+    let mut causes = std::iter::successors(err.source(), |cause| cause.source());
+    for cause in causes.by_ref().take(max_depth) {
+        let _ = write!(out, "{}{}", separator, cause);
+    }
+    let elided = causes.count();
+    if elided > 0 {
+        let _ = write!(out, "{}... {} more", separator, elided);
+    }
+    out
+}
+
+/// An unfinished feature is a normal, reportable outcome - not a panic
+#[derive(Debug)]
+pub enum ExportError {
+    Unsupported { feature: String },
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExportError::Unsupported { feature } => {
+                write!(f, "Unsupported feature: {}", feature)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+pub fn export_report_safe(format: &str) -> Result<String, ExportError> {
+    match format {
+        "text" => Ok(String::from("Report: 5 successful, 2 failed")),
+        other => Err(ExportError::Unsupported {
+            feature: format!("{} export", other),
+        }),
+    }
+}
+
+// Modeling absence vs failure: Option<User> → Result<Option<User>, E> → Lookup
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct User {
+    pub id: u32,
+    pub name: String,
+}
+
+/// The lookup itself went wrong - distinct from "the user doesn't exist"
+#[derive(Debug, Clone, PartialEq)]
+pub enum LookupError {
+    DirectoryUnavailable,
+}
+
+impl fmt::Display for LookupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LookupError::DirectoryUnavailable => write!(f, "User directory unavailable"),
+        }
+    }
+}
+
+impl std::error::Error for LookupError {}
+
+/// Final form: every outcome has a name.
+/// NotFound is a business outcome; Failed is an error.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Lookup {
+    Found(User),
+    NotFound,
+    Failed(LookupError),
+}
+
+impl From<Result<Option<User>, LookupError>> for Lookup {
+    fn from(result: Result<Option<User>, LookupError>) -> Self {
+        match result {
+            Ok(Some(user)) => Lookup::Found(user),
+            Ok(None) => Lookup::NotFound,
+            Err(e) => Lookup::Failed(e),
+        }
+    }
+}
+
+impl From<Lookup> for Result<Option<User>, LookupError> {
+    fn from(lookup: Lookup) -> Self {
+        match lookup {
+            Lookup::Found(user) => Ok(Some(user)),
+            Lookup::NotFound => Ok(None),
+            Lookup::Failed(e) => Err(e),
+        }
+    }
+}
+
+/// Lossy: what the original signature did - failures collapse into None
+impl From<Lookup> for Option<User> {
+    fn from(lookup: Lookup) -> Self {
+        match lookup {
+            Lookup::Found(user) => Some(user),
+            Lookup::NotFound | Lookup::Failed(_) => None,
+        }
+    }
+}
+
+/// Simulated user store that can go down
+pub struct UserDirectory {
+    users: Vec<User>,
+    available: bool,
+}
+
+impl UserDirectory {
+    pub fn new(users: Vec<User>) -> Self {
+        UserDirectory { users, available: true }
+    }
+    
+    pub fn set_available(&mut self, available: bool) {
+        self.available = available;
+    }
+    
+    /// Step 1 (original API): None means "not found"... or "lookup failed"
+    #[deprecated(note = "cannot distinguish not-found from lookup failure; use `lookup`")]
+    pub fn find(&self, id: u32) -> Option<User> {
+        self.lookup(id).into()
+    }
+    
+    /// Step 2: failure moves into the error channel, absence stays an Option
+    pub fn try_find(&self, id: u32) -> Result<Option<User>, LookupError> {
+        if !self.available {
+            return Err(LookupError::DirectoryUnavailable);
+        }
+        Ok(self.users.iter().find(|user| user.id == id).cloned())
+    }
+    
+    /// Step 3: one enum, three outcomes, no nesting
+    pub fn lookup(&self, id: u32) -> Lookup {
+        self.try_find(id).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// A file in the temp dir, removed when dropped
+    struct TempFile(PathBuf);
+    
+    impl TempFile {
+        fn new(name: &str, contents: &[u8]) -> Self {
+            let path = std::env::temp_dir().join(format!("unwrap-better-{}-{}", std::process::id(), name));
+            std::fs::write(&path, contents).unwrap();
+            TempFile(path)
+        }
+    }
+    
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+    
+    /// A variable no other test uses, put back as it was when dropped
+    struct EnvVar {
+        name: String,
+        previous: Option<std::ffi::OsString>,
+    }
+    
+    impl EnvVar {
+        fn new(test: &str) -> Self {
+            let name = format!("UNWRAP_TEST_{}_{}", test, std::process::id());
+            let previous = std::env::var_os(&name);
+            EnvVar { name, previous }
+        }
+        
+        fn set(&self, value: impl AsRef<std::ffi::OsStr>) {
+            std::env::set_var(&self.name, value);
+        }
+    }
+    
+    impl Drop for EnvVar {
+        fn drop(&mut self) {
+            match self.previous.take() {
+                Some(value) => std::env::set_var(&self.name, value),
+                None => std::env::remove_var(&self.name),
+            }
+        }
+    }
+    
+    #[test]
+    fn divide_safe_reports_both_failures() {
+        assert_eq!(divide_safe(10, 2), Ok(5));
+        assert_eq!(divide_safe(1, 0), Err(DivisionError::Zero));
+        assert_eq!(divide_safe(i32::MIN, -1), Err(DivisionError::Overflow));
+    }
+    
+    #[test]
+    fn safe_versions_return_errors_instead_of_panicking() {
+        assert_eq!(parse_and_double_safe("42"), Ok(42));
+        assert!(matches!(parse_and_double_safe("abc"), Err(ParseAndDoubleError::Parse { .. })));
+        assert!(matches!(read_config_file_safe("/nonexistent/unwrap/config.toml"), Err(ConfigError::NotFound(_))));
+        assert_eq!(get_nested_value_safe(Some(Some(Some(7)))), Ok(7));
+        assert_eq!(get_nested_value_safe(Some(None)), Err(NestingError { layer: 2 }));
+        assert_eq!(get_element_safe(&[1, 2, 3], 1), Ok(2));
+        assert_eq!(get_element_safe(&[1, 2, 3], 3), Err(IndexError { index: 3, len: 3 }));
+    }
+    
+    #[test]
+    fn config_errors_say_what_went_wrong() {
+        let file = TempFile::new("invalid-utf8", b"port = \xff\n");
+        match read_config_file_safe(&file.0) {
+            Err(ConfigError::InvalidUtf8 { path, valid_up_to }) => {
+                assert_eq!(path, file.0);
+                assert_eq!(valid_up_to, 7);
+            }
+            other => panic!("expected InvalidUtf8, got {:?}", other),
+        }
+        
+        let missing = std::env::temp_dir().join(format!("unwrap-better-{}-missing", std::process::id()));
+        match read_config_file_safe(&missing) {
+            Err(ConfigError::NotFound(path)) => assert_eq!(path, missing),
+            other => panic!("expected NotFound, got {:?}", other),
+        }
+        
+        let file = TempFile::new("valid", b"port = 8080\n");
+        assert_eq!(read_config_file_safe(&file.0).ok().as_deref(), Some("port = 8080\n"));
+        
+        // Kinds without a variant of their own keep the io::Error as the cause
+        let other = ConfigError::from(io::Error::other("disk on fire"));
+        assert!(matches!(other, ConfigError::Io(_)));
+        assert_eq!(std::error::Error::source(&other).map(ToString::to_string).as_deref(), Some("disk on fire"));
+        assert!(matches!(ConfigError::from(io::Error::from(io::ErrorKind::PermissionDenied)), ConfigError::PermissionDenied(_)));
+    }
+    
+    #[test]
+    fn parse_and_double_errors_name_the_failing_step() {
+        assert_eq!(parse_and_double_safe("42"), Ok(42));
+        
+        match parse_and_double_safe("abc") {
+            Err(e @ ParseAndDoubleError::Parse { .. }) => {
+                assert_eq!(e.to_string(), "Could not parse \"abc\" as a number");
+                let source = std::error::Error::source(&e).map(ToString::to_string);
+                assert_eq!(source.as_deref(), Some("invalid digit found in string"));
+            }
+            other => panic!("expected Parse, got {:?}", other),
+        }
+        
+        // Halving first keeps i32::MAX in range; one past it doesn't parse
+        assert_eq!(parse_and_double_safe(&i32::MAX.to_string()), Ok(i32::MAX - 1));
+        match parse_and_double_safe(&(i64::from(i32::MAX) + 1).to_string()) {
+            Err(ParseAndDoubleError::Parse { source, .. }) => {
+                assert_eq!(source.kind(), &std::num::IntErrorKind::PosOverflow);
+            }
+            other => panic!("expected Parse, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn error_reports_walk_the_whole_chain() {
+        let err: Result<(), _> = Err(ConfigError::Io(io::Error::other("disk on fire")));
+        let err = err.context("loading service config").unwrap_err();
+        
+        assert_eq!(report_error(&err),
+                   "loading service config\n  caused by: Could not read config file\n  caused by: disk on fire");
+        assert_eq!(report_error_line(&err), "loading service config: Could not read config file: disk on fire");
+        assert_eq!(render_error_chain(&err, ": ", 1), "loading service config: Could not read config file: ... 1 more");
+        assert_eq!(render_error_chain(&err, ": ", 0), "loading service config: ... 2 more");
+        assert_eq!(report_error(&DivisionError::Zero), "Division by zero");
+    }
+    
+    #[test]
+    fn with_context_builds_its_message_only_on_error() {
+        let calls = std::cell::Cell::new(0);
+        let message = || {
+            calls.set(calls.get() + 1);
+            format!("parsing port {:?}", "80x")
+        };
+        
+        let ok: Result<i32, ParseAndDoubleError> = parse_and_double_safe("80");
+        assert_eq!(ok.with_context(message).ok(), Some(80));
+        assert_eq!(calls.get(), 0);
+        
+        let err = parse_and_double_safe("80x").with_context(message).unwrap_err();
+        assert_eq!(calls.get(), 1);
+        assert_eq!(err.message(), "parsing port \"80x\"");
+        assert!(matches!(err.inner(), ParseAndDoubleError::Parse { .. }));
+        
+        let err = read_config_file_safe("/nonexistent/unwrap/config.toml").context("reading service config").unwrap_err();
+        assert_eq!(err.message(), "reading service config");
+        assert!(matches!(err.into_inner(), ConfigError::NotFound(_)));
+    }
+    
+    #[test]
+    fn nested_and_indexed_lookups_report_where_they_failed() {
+        assert_eq!(get_nested_value_safe(Some(Some(Some(3)))), Ok(3));
+        assert_eq!(get_nested_value_safe(Some(Some(None))), Err(NestingError { layer: 3 }));
+        assert_eq!(get_nested_value_safe(Some(None)), Err(NestingError { layer: 2 }));
+        assert_eq!(get_nested_value_safe(None), Err(NestingError { layer: 1 }));
+        
+        let values = [10, 20, 30];
+        assert_eq!(get_element_safe(&values, 2), Ok(30));
+        assert_eq!(get_element_safe(&values, 5), Err(IndexError { index: 5, len: 3 }));
+        assert_eq!(get_element_safe::<i32>(&[], 0), Err(IndexError { index: 0, len: 0 }));
+    }
+    
+    #[test]
+    fn element_lookups_work_for_strings_too() {
+        let names = vec![String::from("ada"), String::from("grace")];
+        assert_eq!(get_element_safe(&names, 1), Ok(String::from("grace")));
+        assert_eq!(get_element_safe(&names, 2), Err(IndexError { index: 2, len: 2 }));
+        // Borrowed, not cloned: the very String in the Vec
+        assert!(get_element_ref(&names, 0).is_ok_and(|name| std::ptr::eq(name, &names[0])));
+        assert_eq!(get_element_ref(&names, 9), Err(IndexError { index: 9, len: 2 }));
+        assert_eq!(crate::examples::get_element(&names, 0), "ada");
+    }
+    
+    #[test]
+    fn checked_division_catches_what_divide_misses() {
+        assert_eq!(divide_safe(-9, 3), Ok(-3));
+        assert_eq!(divide_safe(i32::MIN, 1), Ok(i32::MIN));
+        assert_eq!(divide_safe(7, 0), Err(DivisionError::Zero));
+        assert_eq!(divide_safe(i32::MIN, -1), Err(DivisionError::Overflow));
+        assert_eq!(DivisionError::Overflow.to_string(), "Division overflows i32");
+        
+        // The cautionary divide only guards the zero case
+        assert_eq!(crate::examples::divide(7, 0), None);
+        #[cfg(panic = "unwind")]
+        assert!(std::panic::catch_unwind(|| crate::examples::divide(i32::MIN, -1)).is_err());
+    }
+    
+    #[test]
+    fn map_lookups_name_the_missing_key() {
+        let mut ages = HashMap::from([(String::from("bob"), 41), (String::from("alice"), 36)]);
+        assert_eq!(get_user_age_safe(&ages, "alice"), Ok(36));
+        let missing = get_user_age_safe(&ages, "carol").unwrap_err();
+        assert_eq!(missing, MissingKey { key: String::from("carol"), available: vec![String::from("alice"), String::from("bob")] });
+        assert_eq!(missing.to_string(), "No entry for 'carol' (known: alice, bob)");
+        
+        assert_eq!(get_user_age_or_insert(&mut ages, "carol", 30), 30);
+        assert_eq!(get_user_age_or_insert(&mut ages, "bob", 30), 41);
+        assert_eq!(crate::examples::get_user_age(&ages, "carol"), 30);
+        #[cfg(panic = "unwind")]
+        assert!(std::panic::catch_unwind(|| crate::examples::get_user_age(&ages, "dave")).is_err());
+    }
+    
+    #[cfg(panic = "unwind")]
+    #[test]
+    fn poisoned_counter_is_still_readable() {
+        let counter = Mutex::new(41);
+        crate::examples::poison_while_holding(&counter);
+        assert!(counter.is_poisoned());
+        // The worker's += 1 finished before it panicked
+        assert_eq!(read_counter_recovering(&counter), 42);
+        assert!(std::panic::catch_unwind(|| crate::examples::read_counter(&counter)).is_err());
+        assert_eq!(read_counter_recovering(&Mutex::new(7)), 7);
+    }
+    
+    #[test]
+    fn env_lookups_fall_back_layer_by_layer() {
+        let var = EnvVar::new("LAYERED");
+        let missing_file = std::env::temp_dir().join(format!("unwrap-better-{}-no-config", std::process::id()));
+        assert_eq!(env_var_safe(&var.name), Err(EnvError::Missing(var.name.clone())));
+        assert_eq!(layered_setting(&var.name, &missing_file, "fallback"),
+                   Ok((String::from("fallback"), SettingSource::Default)));
+        
+        let config = TempFile::new("layered", format!("# settings\n{} = from-file\n", var.name).as_bytes());
+        assert_eq!(layered_setting(&var.name, &config.0, "fallback"),
+                   Ok((String::from("from-file"), SettingSource::ConfigFile)));
+        
+        var.set("from-env");
+        assert_eq!(env_var_safe(&var.name).as_deref(), Ok("from-env"));
+        assert_eq!(crate::examples::env_setting(&var.name), "from-env");
+        assert_eq!(layered_setting(&var.name, &config.0, "fallback"),
+                   Ok((String::from("from-env"), SettingSource::Environment)));
+    }
+    
+    #[test]
+    fn a_map_stands_in_for_the_environment() {
+        let config = TempFile::new("stand-in", b"DATABASE_URL = from-file\n");
+        let mut environment = HashMap::new();
+        let layered = |environment: &HashMap<&str, OsString>| {
+            layered_setting_in("DATABASE_URL", &config.0, "fallback", |name| environment.get(name).cloned())
+        };
+        assert_eq!(env_var_in("DATABASE_URL", |_| None), Err(EnvError::Missing(String::from("DATABASE_URL"))));
+        assert_eq!(layered(&environment), Ok((String::from("from-file"), SettingSource::ConfigFile)));
+        
+        environment.insert("DATABASE_URL", OsString::from("from-map"));
+        assert_eq!(layered(&environment), Ok((String::from("from-map"), SettingSource::Environment)));
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn non_unicode_env_value_is_reported_not_skipped() {
+        use std::os::unix::ffi::OsStrExt;
+        
+        let var = EnvVar::new("NOT_UNICODE");
+        var.set(std::ffi::OsStr::from_bytes(b"caf\xe9"));
+        assert_eq!(env_var_safe(&var.name), Err(EnvError::NotUnicode(var.name.clone())));
+        let config = TempFile::new("not-unicode", format!("{} = from-file\n", var.name).as_bytes());
+        assert_eq!(layered_setting(&var.name, &config.0, "fallback"), Err(EnvError::NotUnicode(var.name.clone())));
+    }
+    
+    #[test]
+    fn elapsed_time_handles_both_directions() {
+        let past = SystemTime::now() - Duration::from_secs(10);
+        assert!(elapsed_checked(past).is_ok_and(|elapsed| elapsed >= Duration::from_secs(10)));
+        assert!(elapsed_or_zero(past) >= Duration::from_secs(10));
+        assert!(crate::examples::elapsed_since(past) >= Duration::from_secs(10));
+        
+        let future = SystemTime::now() + Duration::from_secs(10);
+        let skew = elapsed_checked(future).unwrap_err();
+        assert!(skew.ahead_by > Duration::from_secs(9) && skew.ahead_by <= Duration::from_secs(10), "{}", skew);
+        assert_eq!(elapsed_or_zero(future), Duration::ZERO);
+        #[cfg(panic = "unwind")]
+        assert!(std::panic::catch_unwind(|| crate::examples::elapsed_since(future)).is_err());
+    }
+    
+    #[test]
+    fn lone_invalid_byte_decodes_three_ways() {
+        let bytes = b"temp=21\xffC".to_vec();
+        
+        let err = decode_message_safe(bytes.clone()).unwrap_err();
+        assert_eq!(err.utf8_error().valid_up_to(), 7);
+        assert_eq!(err.into_bytes(), bytes); // the buffer comes back intact
+        
+        assert_eq!(decode_message_lossy(&bytes), "temp=21\u{FFFD}C");
+        assert_eq!(decode_message_truncated(&bytes), "temp=21");
+        assert_eq!(decode_message_safe(b"temp=21C".to_vec()).as_deref(), Ok("temp=21C"));
+        assert_eq!(decode_message_truncated(b"temp=21C"), "temp=21C");
+        #[cfg(panic = "unwind")]
+        assert!(std::panic::catch_unwind(|| crate::examples::decode_message(bytes.clone())).is_err());
+    }
+    
+    #[test]
+    fn collecting_results_tells_how_the_stream_ended() {
+        let timeout = Duration::from_secs(5);
+        let done = collect_results(&crate::examples::start_worker(4, None), 4, timeout);
+        assert_eq!(done, Collected { results: vec![1, 4, 9, 16], end: StreamEnd::Complete });
+        
+        let died = collect_results(&crate::examples::start_worker(4, Some(2)), 4, timeout);
+        assert_eq!(died, Collected { results: vec![1, 4], end: StreamEnd::ProducerGone });
+        
+        // A sender that is alive but never sends
+        let (tx, rx) = std::sync::mpsc::channel();
+        tx.send(7).unwrap();
+        let silent = collect_results(&rx, 2, Duration::from_millis(20));
+        assert_eq!(silent, Collected { results: vec![7], end: StreamEnd::TimedOut });
+        drop(tx);
+        
+        assert_eq!(crate::examples::sum_results(&crate::examples::start_worker(3, None), 3), 14);
+        #[cfg(panic = "unwind")]
+        assert!(std::panic::catch_unwind(|| {
+            crate::examples::sum_results(&crate::examples::start_worker(4, Some(2)), 4)
+        }).is_err());
+    }
+    
+    #[test]
+    fn aggregates_handle_empty_single_and_nan_input() {
+        assert_eq!(highest_price_or_default(&[], 0), 0);
+        assert_eq!(highest_price_checked(&[5, 9], 10), Err(EmptyDataset));
+        assert_eq!(highest_price_checked(&[12], 10), Ok(12));
+        assert_eq!(highest_price_or_default(&[12, 30, 8], 10), 30);
+        #[cfg(panic = "unwind")]
+        assert!(std::panic::catch_unwind(|| crate::examples::highest_price_above(&[5, 9], 10)).is_err());
+        
+        let empty = summarize(&[]);
+        assert_eq!(empty, Summary { count: 0, nan_count: 0, min: None, max: None, mean: None });
+        let single = summarize(&[2.5]);
+        assert_eq!(single, Summary { count: 1, nan_count: 0, min: Some(2.5), max: Some(2.5), mean: Some(2.5) });
+        
+        // NaN neither wins max nor poisons the mean
+        let with_nan = summarize(&[f64::NAN, 3.0, -1.0, f64::NAN, 4.0]);
+        assert_eq!(with_nan, Summary { count: 3, nan_count: 2, min: Some(-1.0), max: Some(4.0), mean: Some(2.0) });
+        let only_nan = summarize(&[f64::NAN]);
+        assert_eq!((only_nan.count, only_nan.nan_count, only_nan.max, only_nan.mean), (0, 1, None, None));
+    }
+    
+    #[test]
+    fn conversions_at_the_u32_boundary() {
+        let max = u64::from(u32::MAX);
+        assert_eq!(convert::<u32>(max), Ok(u32::MAX));
+        let too_big = convert::<u32>(max + 1).unwrap_err();
+        assert_eq!(too_big, ConversionError { value: max + 1, target_type: "u32" });
+        assert_eq!(too_big.to_string(), "4294967296 does not fit in u32");
+        assert_eq!(to_u32_saturating(max), u32::MAX);
+        assert_eq!(to_u32_saturating(max + 1), u32::MAX);
+        assert_eq!(to_u32_saturating(7), 7);
+        
+        assert_eq!(crate::examples::frame_length(max), u32::MAX);
+        #[cfg(panic = "unwind")]
+        assert!(std::panic::catch_unwind(|| crate::examples::frame_length(max + 1)).is_err());
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(crate::examples::buffer_length(max + 1), 1 << 32);
+    }
+    
+    #[test]
+    fn config_parsing_and_field_errors() {
+        let config = parse_config("# service\n\n   \nport = 80\nhost=a=b\nno equals sign\n  # indented comment\nport=8080\n");
+        // A repeated key keeps its last value; only the first '=' splits
+        assert_eq!(config.get("port").map(String::as_str), Some("8080"));
+        assert_eq!(config.get("host").map(String::as_str), Some("a=b"));
+        assert_eq!(config.len(), 2, "{:?}", config);
+        assert_eq!(get_port_safe(&config), Ok(8080));
+        assert_eq!(crate::examples::get_port(&config), 8080);
+        
+        let missing = parse_config("host = example.com");
+        assert_eq!(get_port_safe(&missing), Err(ConfigFieldError::MissingKey(String::from("port"))));
+        
+        match get_port_safe(&parse_config("port=notanumber")) {
+            Err(e @ ConfigFieldError::InvalidValue { .. }) => {
+                assert_eq!(e.to_string(), "Config key 'port' has invalid value \"notanumber\"");
+                assert!(std::error::Error::source(&e).is_some());
+            }
+            other => panic!("expected InvalidValue, got {:?}", other),
+        }
+        assert!(matches!(get_port_safe(&parse_config("port=70000")), Err(ConfigFieldError::InvalidValue { .. })));
+    }
+    
+    #[test]
+    fn nesting_variants_agree_on_every_input() {
+        let cases = [
+            (Some(Some(Some(42))), Ok(42)),
+            (Some(Some(None)), Err(MissingAt::Inner)),
+            (Some(None), Err(MissingAt::Middle)),
+            (None, Err(MissingAt::Outer)),
+        ];
+        for (data, expected) in cases {
+            assert_eq!(nested_missing_at(data), expected, "{:?}", data);
+            let value = expected.ok();
+            assert_eq!(nested_flatten(data), value, "{:?}", data);
+            assert_eq!(nested_and_then(data), value, "{:?}", data);
+            assert_eq!(nested_question_mark(data), value, "{:?}", data);
+            assert_eq!(get_nested_value_safe(data), expected.map_err(|at| NestingError { layer: at.layer() }));
+        }
+    }
+    
+    #[test]
+    fn deferred_cleanup_hands_its_error_to_the_caller() {
+        let reported = std::cell::RefCell::new(Vec::new());
+        drop(defer_fallible(|| Err("disk full"), |e| reported.borrow_mut().push(e)));
+        drop(defer_fallible(|| Ok::<(), &str>(()), |e| reported.borrow_mut().push(e)));
+        assert_eq!(*reported.borrow(), ["disk full"]);
+        
+        // finish() returns the error instead, and on_error is never called
+        let finished = defer_fallible(|| Err("disk full"), |e| reported.borrow_mut().push(e)).finish();
+        assert_eq!(finished, Err("disk full"));
+        assert_eq!(reported.borrow().len(), 1);
+    }
+}
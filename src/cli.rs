@@ -0,0 +1,568 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::advice::{CallContext, ErrorKind};
+use crate::incidents::FailureCategory;
+use crate::lint::{Level, Policy};
+use crate::style::ColorChoice;
+use crate::system_design::{Design, PoissonLoad, RateLimit, Verbosity};
+
+/// What the binary was asked to do
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    All { paced: bool },
+    Examples { dangerous_demos: bool },
+    Simulate {
+        design: Option<Design>,
+        requests: Option<usize>,
+        failure_rate: Option<f64>,
+        seed: Option<u64>,
+        rate_limit: Option<RateLimit>,
+        arrivals: Option<PoissonLoad>,
+        monte_carlo: Option<usize>,
+        format: Format,
+        csv: Option<PathBuf>, // per-request results, written alongside the normal output
+        verbosity: Option<Verbosity>, // None: decided by the request count
+    },
+    Compare {
+        requests: Option<usize>,
+        failure_rate: Option<f64>,
+        seed: Option<u64>,
+        rate_limit: Option<RateLimit>,
+        arrivals: Option<PoissonLoad>,
+        format: Format,
+    },
+    Lessons {
+        name: Option<String>,
+        category: Option<FailureCategory>, // history only: one kind of incident
+        run_demos: bool,                    // history only: run each reproduction
+    },
+    Quiz,
+    Exercises,
+    Bench,
+    Advise { kind: Option<ErrorKind>, context: Option<CallContext> }, // None: every one
+    Export { out: PathBuf }, // one Markdown file per lesson in this directory
+    Lint {
+        path: PathBuf, // a directory of .rs files, or one file
+        format: LintFormat,
+        policy: Policy,
+        update_baseline: bool, // acknowledge every call site found, in .unwrapignore
+        mode: LintMode,
+        config: Option<PathBuf>, // keyword weights for the risk score
+    },
+    Help,
+}
+
+/// How simulation results are written to stdout
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text, // narrated, for people
+    Json, // one document, nothing else - see the json module for the schema
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+/// How lint findings are written: Format's two, and SARIF for code scanning
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LintFormat {
+    #[default]
+    Text,
+    Json,
+    Sarif, // a SARIF 2.1.0 log
+}
+
+impl LintFormat {
+    pub fn from_name(name: &str) -> Option<LintFormat> {
+        match name {
+            "text" => Some(LintFormat::Text),
+            "json" => Some(LintFormat::Json),
+            "sarif" => Some(LintFormat::Sarif),
+            _ => None,
+        }
+    }
+}
+
+/// What lint does with the call sites it finds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LintMode {
+    #[default]
+    Report,
+    Suggest, // a ? rewrite of each, as a diff
+    Fix,     // apply the rewrites that are safe, in place
+}
+
+/// Flags any command takes, wherever they appear
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GlobalFlags {
+    pub color: ColorChoice,
+    pub ascii: bool, // [OK] for ✓ and so on, whatever the locale says
+}
+
+/// Bad command lines are reported, never unwrapped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliError {
+    UnknownCommand(String),
+    UnknownFlag(String),
+    MissingValue(&'static str),
+    InvalidValue { flag: &'static str, value: String },
+    UnexpectedArgument(String),
+    Requires { flag: &'static str, requires: &'static str },
+    Conflict { flag: &'static str, with: &'static str },
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliError::UnknownCommand(cmd) => write!(f, "unknown command '{}'", cmd),
+            CliError::UnknownFlag(flag) => write!(f, "unknown flag '{}'", flag),
+            CliError::MissingValue(flag) => write!(f, "{} requires a value", flag),
+            CliError::InvalidValue { flag, value } => {
+                write!(f, "invalid value '{}' for {}", value, flag)
+            }
+            CliError::UnexpectedArgument(arg) => write!(f, "unexpected argument '{}'", arg),
+            CliError::Requires { flag, requires } => write!(f, "{} requires {}", flag, requires),
+            CliError::Conflict { flag, with } => write!(f, "{} cannot be combined with {}", flag, with),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+pub const USAGE: &str = "\
+Usage: unwrap [COMMAND]
+
+Commands:
+  all [--paced]              Run the complete demo (default); --paced waits
+                             for Enter before each lesson (s skips, q quits)
+  examples                   The unwrap() failure examples and their fixes
+  examples --dangerous-demos
+                             Then panic in Drop during an unwind: the process
+                             aborts, as Example 19 explains
+  simulate [OPTIONS]         Production load simulation
+  compare [OPTIONS]          Every design on the same load, side by side
+  lessons [NAME]             Run one lesson, or list them
+  lessons history --category C
+                             Only the famous failures of one category: Race,
+                             Overflow, Units, MemorySafety, Timing, Cascade,
+                             Deployment or Unwrap
+  lessons history --run-demos
+                             Run each incident's reproduction as it is told
+  export [OPTIONS]           Write every lesson to a Markdown file
+  exercises                  Check your fixes to the unwrap() exercises
+  lint [PATH] [OPTIONS]      Every unwrap() and expect() under PATH (default .),
+                             and how dense they are. Exits 1 if it finds any
+  bench                      Time panic and unwind against Result propagation
+                             on this machine (build with --release)
+  advise [OPTIONS]           What to do instead of unwrap(), for one kind of
+                             error and/or one place it happens
+  quiz                       Test yourself: answer each question with its number
+  help                       Show this message
+
+Global options:
+  --color WHEN               always, auto (default) or never; auto means on a
+                             terminal and only if NO_COLOR is unset
+  --ascii                    Plain ASCII: [OK], [FAIL], [WARN] and --- for the
+                             symbols (automatic when the locale isn't UTF-8)
+
+Simulate options (compare takes all but --design, --monte-carlo, --csv and -q/-v):
+  --design NAME              Only run one design: unsafe, safe, resilient, retry
+  --requests N               Generate N requests instead of the demo's seven
+  --failure-rate P           Probability that a request fails, 0.0 to 1.0
+  --seed N                   Seed the injected failures; same seed, same run
+  --rate-limit N             Token bucket: admit N requests per second
+                             (requests arrive every 10ms, i.e. 100 per second)
+  --burst N                  Token bucket capacity (default: the rate limit)
+  --arrival-rate R           Poisson arrivals, R requests per second on average
+                             (instead of --requests)
+  --duration S               How long Poisson arrivals run, in seconds (default 60)
+  --monte-carlo N            Repeat the run N times and summarize availability
+  --format FORMAT            text (default) or json: one JSON document on stdout
+  --csv PATH                 Also write one row per request to a CSV file
+  -q, -v, -vv                Nothing but --csv; a line per request; per-request
+                             detail and breaker changes (default: a line per
+                             request up to 50, then only the summary)
+
+Lint options:
+  --format FORMAT            text (default), json, or sarif for code scanning
+  --strict=false             Group call sites by context - library, main,
+                             const fn, test - and only fail on library and
+                             main; a local `fn unwrap` isn't counted either
+  --fail-level LEVEL         Fail only on call sites at LEVEL or above: note
+                             (default), warning or error. expect() is a note,
+                             unwrap() a warning, either on I/O an error;
+                             none fails on exempt ones too
+  --update-baseline          Write every call site found to PATH/.unwrapignore,
+                             so only new ones fail the run. Call sites are
+                             also acknowledged by `// unwrap-ok: reason` on
+                             their line or the line before
+  --config PATH              Risk weights: a [keywords] table of
+                             `keyword = weight` lines, e.g. lock = 3.0
+  --suggest                  Show each call site rewritten to propagate with ?,
+                             as a diff, and whether --fix would apply it
+  --fix                      Rewrite in place the call sites where ? is sure
+                             to compile: unwrap() ending a statement, on I/O
+                             or parse::<primitive>() in a fn that returns
+                             io::Result or Result<T, Box<dyn Error>>
+
+Advise options:
+  --kind KIND                transient, permanent, invalid-input or bug
+  --context CONTEXT          request-path, batch-job, startup or drop
+
+Export options:
+  --format markdown          The only export format, and the default
+  --out DIR                  Where the .md files go (default: lessons)";
+
+/// Takes the global flags out of the arguments; parse_args gets the rest
+pub fn parse_global_flags<I>(args: I) -> Result<(GlobalFlags, Vec<String>), CliError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut flags = GlobalFlags::default();
+    let mut rest = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--color" => {
+                let value = args.next().ok_or(CliError::MissingValue("--color"))?;
+                flags.color = ColorChoice::from_name(&value)
+                    .ok_or(CliError::InvalidValue { flag: "--color", value })?;
+            }
+            "--ascii" => flags.ascii = true,
+            _ => rest.push(arg),
+        }
+    }
+    Ok((flags, rest))
+}
+
+/// Parses the arguments after the program name
+pub fn parse_args<I>(args: I) -> Result<Command, CliError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    let command = match args.next() {
+        None => return Ok(Command::All { paced: false }),
+        Some(command) => command,
+    };
+    
+    let parsed = match command.as_str() {
+        "all" | "--paced" => {
+            let mut paced = command == "--paced";
+            for arg in args.by_ref() {
+                match arg.as_str() {
+                    "--paced" => paced = true,
+                    flag if flag.starts_with('-') => return Err(CliError::UnknownFlag(arg)),
+                    _ => return Err(CliError::UnexpectedArgument(arg)),
+                }
+            }
+            Command::All { paced }
+        }
+        "examples" => {
+            let mut dangerous_demos = false;
+            for arg in args.by_ref() {
+                match arg.as_str() {
+                    "--dangerous-demos" => dangerous_demos = true,
+                    flag if flag.starts_with('-') => return Err(CliError::UnknownFlag(arg)),
+                    _ => return Err(CliError::UnexpectedArgument(arg)),
+                }
+            }
+            Command::Examples { dangerous_demos }
+        }
+        "help" | "--help" | "-h" => Command::Help,
+        "simulate" | "compare" => {
+            let comparing = command == "compare";
+            let mut design = None;
+            let mut requests = None;
+            let mut failure_rate = None;
+            let mut seed = None;
+            let mut per_second = None;
+            let mut burst = None;
+            let mut arrival_rate = None;
+            let mut duration = None;
+            let mut monte_carlo = None;
+            let mut format = Format::Text;
+            let mut csv = None;
+            let mut verbosity = None;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--design" | "--monte-carlo" | "--csv" | "-q" | "-v" | "-vv" if comparing => {
+                        return Err(CliError::UnknownFlag(arg))
+                    }
+                    "--design" => {
+                        let value = args.next().ok_or(CliError::MissingValue("--design"))?;
+                        let parsed = Design::from_name(&value)
+                            .ok_or(CliError::InvalidValue { flag: "--design", value })?;
+                        design = Some(parsed);
+                    }
+                    "--requests" => {
+                        let value = args.next().ok_or(CliError::MissingValue("--requests"))?;
+                        let parsed = value.parse::<usize>()
+                            .map_err(|_| CliError::InvalidValue { flag: "--requests", value })?;
+                        requests = Some(parsed);
+                    }
+                    "--failure-rate" => {
+                        let value = args.next().ok_or(CliError::MissingValue("--failure-rate"))?;
+                        let parsed = value.parse::<f64>().ok()
+                            .filter(|rate| (0.0..=1.0).contains(rate))
+                            .ok_or(CliError::InvalidValue { flag: "--failure-rate", value })?;
+                        failure_rate = Some(parsed);
+                    }
+                    "--seed" => {
+                        let value = args.next().ok_or(CliError::MissingValue("--seed"))?;
+                        let parsed = value.parse::<u64>()
+                            .map_err(|_| CliError::InvalidValue { flag: "--seed", value })?;
+                        seed = Some(parsed);
+                    }
+                    "--rate-limit" => {
+                        let value = args.next().ok_or(CliError::MissingValue("--rate-limit"))?;
+                        let parsed = value.parse::<u64>()
+                            .map_err(|_| CliError::InvalidValue { flag: "--rate-limit", value })?;
+                        per_second = Some(parsed);
+                    }
+                    "--burst" => {
+                        let value = args.next().ok_or(CliError::MissingValue("--burst"))?;
+                        let parsed = value.parse::<u64>()
+                            .map_err(|_| CliError::InvalidValue { flag: "--burst", value })?;
+                        burst = Some(parsed);
+                    }
+                    "--arrival-rate" => {
+                        let value = args.next().ok_or(CliError::MissingValue("--arrival-rate"))?;
+                        let parsed = value.parse::<f64>().ok()
+                            .filter(|rate| *rate > 0.0 && rate.is_finite())
+                            .ok_or(CliError::InvalidValue { flag: "--arrival-rate", value })?;
+                        arrival_rate = Some(parsed);
+                    }
+                    "--duration" => {
+                        let value = args.next().ok_or(CliError::MissingValue("--duration"))?;
+                        let parsed = value.parse::<f64>().ok()
+                            .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                            .ok_or(CliError::InvalidValue { flag: "--duration", value })?;
+                        duration = Some(parsed);
+                    }
+                    "--monte-carlo" => {
+                        let value = args.next().ok_or(CliError::MissingValue("--monte-carlo"))?;
+                        let parsed = value.parse::<usize>()
+                            .map_err(|_| CliError::InvalidValue { flag: "--monte-carlo", value })?;
+                        monte_carlo = Some(parsed);
+                    }
+                    "--format" => {
+                        let value = args.next().ok_or(CliError::MissingValue("--format"))?;
+                        format = Format::from_name(&value)
+                            .ok_or(CliError::InvalidValue { flag: "--format", value })?;
+                    }
+                    "--csv" => {
+                        let value = args.next().ok_or(CliError::MissingValue("--csv"))?;
+                        csv = Some(PathBuf::from(value));
+                    }
+                    "-q" => verbosity = Some(Verbosity::Quiet),
+                    "-v" => verbosity = Some(Verbosity::PerRequest),
+                    "-vv" => verbosity = Some(Verbosity::Debug),
+                    flag if flag.starts_with('-') => return Err(CliError::UnknownFlag(arg)),
+                    _ => return Err(CliError::UnexpectedArgument(arg)),
+                }
+            }
+            let rate_limit = match (per_second, burst) {
+                (Some(per_second), burst) => {
+                    Some(RateLimit { burst: burst.unwrap_or(per_second), per_second })
+                }
+                (None, Some(_)) => {
+                    return Err(CliError::Requires { flag: "--burst", requires: "--rate-limit" })
+                }
+                (None, None) => None,
+            };
+            let arrivals = match (arrival_rate, duration) {
+                (Some(_), _) if requests.is_some() => {
+                    return Err(CliError::Conflict { flag: "--arrival-rate", with: "--requests" })
+                }
+                (Some(rate), duration) => Some(PoissonLoad {
+                    rate,
+                    duration: duration.unwrap_or(Duration::from_secs(60)),
+                }),
+                (None, Some(_)) => {
+                    return Err(CliError::Requires { flag: "--duration", requires: "--arrival-rate" })
+                }
+                (None, None) => None,
+            };
+            if monte_carlo.is_some() && csv.is_some() {
+                return Err(CliError::Conflict { flag: "--csv", with: "--monte-carlo" });
+            }
+            if comparing {
+                Command::Compare { requests, failure_rate, seed, rate_limit, arrivals, format }
+            } else {
+                Command::Simulate {
+                    design, requests, failure_rate, seed, rate_limit, arrivals, monte_carlo, format, csv, verbosity,
+                }
+            }
+        }
+        "lessons" => {
+            let mut name = None;
+            let mut category = None;
+            let mut run_demos = false;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--category" => {
+                        let value = args.next().ok_or(CliError::MissingValue("--category"))?;
+                        category = Some(
+                            FailureCategory::from_name(&value)
+                                .ok_or(CliError::InvalidValue { flag: "--category", value })?,
+                        );
+                    }
+                    "--run-demos" => run_demos = true,
+                    flag if flag.starts_with('-') => return Err(CliError::UnknownFlag(arg)),
+                    _ if name.is_none() => name = Some(arg),
+                    _ => return Err(CliError::UnexpectedArgument(arg)),
+                }
+            }
+            if name.as_deref() != Some("history") {
+                if category.is_some() {
+                    return Err(CliError::Requires { flag: "--category", requires: "the history lesson" });
+                }
+                if run_demos {
+                    return Err(CliError::Requires { flag: "--run-demos", requires: "the history lesson" });
+                }
+            }
+            Command::Lessons { name, category, run_demos }
+        }
+        "quiz" => Command::Quiz,
+        "exercises" => Command::Exercises,
+        "bench" => Command::Bench,
+        "advise" => {
+            let mut kind = None;
+            let mut context = None;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--kind" => {
+                        let value = args.next().ok_or(CliError::MissingValue("--kind"))?;
+                        kind = Some(
+                            ErrorKind::from_id(&value).ok_or(CliError::InvalidValue { flag: "--kind", value })?,
+                        );
+                    }
+                    "--context" => {
+                        let value = args.next().ok_or(CliError::MissingValue("--context"))?;
+                        context = Some(
+                            CallContext::from_id(&value).ok_or(CliError::InvalidValue { flag: "--context", value })?,
+                        );
+                    }
+                    flag if flag.starts_with('-') => return Err(CliError::UnknownFlag(arg)),
+                    _ => return Err(CliError::UnexpectedArgument(arg)),
+                }
+            }
+            Command::Advise { kind, context }
+        }
+        "export" => {
+            let mut out = PathBuf::from("lessons");
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--format" => {
+                        let value = args.next().ok_or(CliError::MissingValue("--format"))?;
+                        if value != "markdown" {
+                            return Err(CliError::InvalidValue { flag: "--format", value });
+                        }
+                    }
+                    "--out" => {
+                        let value = args.next().ok_or(CliError::MissingValue("--out"))?;
+                        out = PathBuf::from(value);
+                    }
+                    flag if flag.starts_with('-') => return Err(CliError::UnknownFlag(arg)),
+                    _ => return Err(CliError::UnexpectedArgument(arg)),
+                }
+            }
+            Command::Export { out }
+        }
+        "lint" => {
+            let mut path = None;
+            let mut format = LintFormat::Text;
+            let mut policy = Policy::default();
+            let mut update_baseline = false;
+            let mut mode = LintMode::Report;
+            let mut config = None;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--update-baseline" => update_baseline = true,
+                    "--suggest" | "--fix" if mode != LintMode::Report => {
+                        let flag = if arg == "--fix" { "--fix" } else { "--suggest" };
+                        let with = if mode == LintMode::Fix { "--fix" } else { "--suggest" }; // the one given first
+                        return Err(CliError::Conflict { flag, with });
+                    }
+                    "--suggest" => mode = LintMode::Suggest,
+                    "--fix" => mode = LintMode::Fix,
+                    "--strict" | "--strict=true" => policy.strict = true,
+                    "--strict=false" => policy.strict = false,
+                    flag if flag.starts_with("--strict=") => {
+                        let value = flag["--strict=".len()..].to_string();
+                        return Err(CliError::InvalidValue { flag: "--strict", value });
+                    }
+                    "--format" => {
+                        let value = args.next().ok_or(CliError::MissingValue("--format"))?;
+                        format = LintFormat::from_name(&value)
+                            .ok_or(CliError::InvalidValue { flag: "--format", value })?;
+                    }
+                    "--config" => {
+                        let value = args.next().ok_or(CliError::MissingValue("--config"))?;
+                        config = Some(PathBuf::from(value));
+                    }
+                    "--fail-level" => {
+                        let value = args.next().ok_or(CliError::MissingValue("--fail-level"))?;
+                        policy.fail_level = Level::from_name(&value)
+                            .ok_or(CliError::InvalidValue { flag: "--fail-level", value })?;
+                    }
+                    flag if flag.starts_with('-') => return Err(CliError::UnknownFlag(arg)),
+                    _ if path.is_none() => path = Some(PathBuf::from(arg)),
+                    _ => return Err(CliError::UnexpectedArgument(arg)),
+                }
+            }
+            if mode != LintMode::Report && format != LintFormat::Text {
+                let flag = if mode == LintMode::Fix { "--fix" } else { "--suggest" };
+                return Err(CliError::Conflict { flag, with: "--format" });
+            }
+            Command::Lint {
+                path: path.unwrap_or_else(|| PathBuf::from(".")),
+                format,
+                policy,
+                update_baseline,
+                mode,
+                config,
+            }
+        }
+        _ => return Err(CliError::UnknownCommand(command)),
+    };
+    
+    match args.next() {
+        Some(extra) => Err(CliError::UnexpectedArgument(extra)),
+        None => Ok(parsed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn parse(args: &[&str]) -> Result<Command, CliError> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+    
+    #[test]
+    fn lint_mode_conflicts_name_the_flags_given() {
+        let conflict = |args: &[&str]| parse(args).unwrap_err().to_string();
+        assert_eq!(conflict(&["lint", "--suggest", "--fix"]), "--fix cannot be combined with --suggest");
+        assert_eq!(conflict(&["lint", "--fix", "--suggest"]), "--suggest cannot be combined with --fix");
+        assert_eq!(conflict(&["lint", "--suggest", "--suggest"]), "--suggest cannot be combined with --suggest");
+        assert_eq!(conflict(&["lint", "--fix", "--fix"]), "--fix cannot be combined with --fix");
+        assert_eq!(conflict(&["lint", "--fix", "--format", "json"]), "--fix cannot be combined with --format");
+        
+        let Ok(Command::Lint { mode, update_baseline, .. }) = parse(&["lint", "src", "--fix", "--update-baseline"]) else {
+            panic!("lint --fix --update-baseline should parse");
+        };
+        assert_eq!((mode, update_baseline), (LintMode::Fix, true));
+    }
+}
@@ -0,0 +1,2217 @@
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crate::examples::{
+    buffer_length, decode_message, divide, elapsed_since, env_setting, export_report, frame_length,
+    get_element, get_nested_value, get_port, get_user_age, greet_user, highest_price_above,
+    parse_and_double, NoisyGuard, poison_while_holding, read_config_file, read_counter, run_job, spawn_worker,
+    start_worker, sum_results,
+};
+use crate::better_approaches::Context;
+use crate::cli::{Format, LintFormat};
+use crate::incidents::{self, FailureCategory, Incident};
+use crate::style::{paint, Color};
+use crate::{advice, bench, better_approaches, json, kv, lint, markdown, panics, stats, system_design};
+
+/// Writes three records, tears the last one as a crash mid-write would,
+/// then loads the file naively and with recovery
+fn demonstrate_torn_write(out: &mut dyn Write, path: &std::path::Path) -> Result<(), kv::KvError> {
+    {
+        let mut store = kv::Store::open(path)?;
+        store.put("config.version", b"41")?;
+        store.put("config.routes", b"/api,/health")?;
+        store.put("config.version", b"42")?;
+    }
+    let full_len = std::fs::metadata(path)?.len();
+    std::fs::OpenOptions::new().write(true).open(path)?.set_len(full_len - 3)?;
+    writeln!(out, "  Wrote 3 records, then lost the last 3 bytes (crash during write)")?;
+    
+    match panics::capture(|| kv::load_naive(path)) {
+        Ok(records) => writeln!(out, "Loaded {} records", records.len())?,
+        Err(e) => caught(out, &e, "Naive loader unwrapped a torn frame - every record lost")?,
+    }
+    
+    let mut store = kv::Store::open(path)?;
+    let recovery = store.recovery();
+    writeln!(out, "✓ Recovery scan: {} records recovered, {} lost ({} bytes truncated, {})",
+                  recovery.recovered,
+                  recovery.lost_records,
+                  recovery.lost_bytes,
+                  recovery.reason.map_or(String::from("clean"), |r| r.to_string()))?;
+    if let Some(version) = store.get("config.version")? {
+        writeln!(out, "✓ Last-known-good config.version = {}", String::from_utf8_lossy(&version))?;
+    }
+    store.put("config.version", b"43")?;
+    writeln!(out, "✓ Store still writable: {} keys, config.version now {}",
+                  store.len(),
+                  store.get("config.version")?.map_or(String::from("?"), |v| String::from_utf8_lossy(&v).into_owned()))?;
+    Ok(())
+}
+
+const DEMO_VAR: &str = "UNWRAP_PHILOSOPHY_DATABASE_URL";
+
+/// Walks one setting through every layer: unset, config file, default,
+/// environment, and (on Unix) set to bytes that aren't UTF-8.
+/// The environment layer is a map, so the process's own is never changed.
+fn demonstrate_env_layers(out: &mut dyn Write) -> io::Result<()> {
+    let config = std::env::temp_dir()
+        .join(format!("unwrap-philosophy-{}.env", std::process::id()));
+    let _ = std::fs::write(&config, format!("{} = postgres://from-config\n", DEMO_VAR));
+    
+    let result = walk_env_layers(out, &config);
+    
+    let _ = std::fs::remove_file(&config);
+    result
+}
+
+fn walk_env_layers(out: &mut dyn Write, config: &std::path::Path) -> io::Result<()> {
+    match panics::capture(|| env_setting(DEMO_VAR)) {
+        Ok(_) => writeln!(out, "Success")?,
+        Err(e) => caught(out, &e, format_args!("{} is unset - env::var().unwrap() panicked", DEMO_VAR))?,
+    }
+    let mut environment: HashMap<&str, OsString> = HashMap::new();
+    if let Err(e) = better_approaches::env_var_in(DEMO_VAR, |name| environment.get(name).cloned()) {
+        writeln!(out, "✓ Error handled gracefully: {}", e)?;
+    }
+    
+    let missing = std::path::Path::new("nonexistent.env");
+    let default = "postgres://localhost";
+    let show = |out: &mut dyn Write, label: &str, environment: &HashMap<&str, OsString>, path: &std::path::Path| {
+        let lookup = |name: &str| environment.get(name).cloned();
+        match better_approaches::layered_setting_in(DEMO_VAR, path, default, lookup) {
+            Ok((value, source)) => writeln!(out, "✓ {}: {} (from {:?})", label, value, source),
+            Err(e) => writeln!(out, "✓ {}: {}", label, e),
+        }
+    };
+    show(out, "No variable, config file", &environment, config)?;
+    show(out, "No variable, no config file", &environment, missing)?;
+    environment.insert(DEMO_VAR, OsString::from("postgres://from-env"));
+    show(out, "Variable set", &environment, config)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+        environment.insert(DEMO_VAR, OsString::from_vec(b"postgres://caf\xe9".to_vec()));
+        show(out, "Variable set to Latin-1 bytes", &environment, config)?;
+    }
+    Ok(())
+}
+
+/// A caught panic: what went wrong, then the panic's own words and where.
+/// A panic = "abort" build ran nothing, and says so instead.
+fn caught(out: &mut dyn Write, error: &panics::CaptureError, what: impl fmt::Display) -> io::Result<()> {
+    match error {
+        panics::CaptureError::Panicked(report) => {
+            writeln!(out, "✗ PANIC CAUGHT: {}", what)?;
+            writeln!(out, "  {}", report)
+        }
+        panics::CaptureError::Unsupported => writeln!(out, "⚠ Skipped - {}", error),
+    }
+}
+
+/// Runs the complete demo, start to finish
+pub fn run(out: &mut dyn Write) -> io::Result<()> {
+    run_paced(out, &mut NoPause)
+}
+
+/// The whole demo, asking `pacer` before each lesson whether to run it
+pub fn run_paced(out: &mut dyn Write, pacer: &mut dyn Pacer) -> io::Result<()> {
+    writeln!(out, "🔓 UNWRAP PROBLEM PROPAGATION DEMO 🔓\n")?;
+    writeln!(out, "This demo shows how unwrap() causes problems to 'unwrap' into panics.\n")?;
+    if !panics::UNWINDS {
+        writeln!(out, "⚠ This build has panic = \"abort\": a panic ends the process before catch_unwind")?;
+        writeln!(out, "  sees it, so every demonstration that catches one is skipped - running them would")?;
+        writeln!(out, "  make the demo its own outage. Everything that handles errors with Result still runs.\n")?;
+    }
+    
+    for lesson in all_lessons() {
+        match pacer.pause(out, lesson.as_ref())? {
+            Pace::Continue => lesson.run(out)?,
+            Pace::Skip => writeln!(out, "(skipped: {})\n", lesson.title())?,
+            Pace::Quit => break,
+        }
+    }
+    Ok(())
+}
+
+/// Examples 1-19: each unwrap() failure mode next to its better approach
+pub fn examples(out: &mut dyn Write) -> io::Result<()> {
+    // Demonstration 1: Basic unwrap success
+    writeln!(out, "=== Example 1: Basic Division ===")?;
+    match divide(10, 2) {
+        Some(result) => writeln!(out, "✓ 10 / 2 = {}", result)?,
+        None => writeln!(out, "✗ Division failed")?,
+    }
+    
+    // This would panic:
+    // println!("Result: {}", divide(10, 0).unwrap());
+    writeln!(out, "⚠ divide(10, 0).unwrap() would panic here!")?;
+    
+    // The second, hidden failure mode: divide() checks for zero but not overflow
+    match panics::capture(|| divide(i32::MIN, -1)) {
+        Ok(result) => writeln!(out, "Result: {:?}", result)?,
+        Err(e) => caught(out, &e, "divide(i32::MIN, -1) overflowed - the zero check didn't cover it")?,
+    }
+    for (a, b) in [(10, 2), (10, 0), (i32::MIN, -1)] {
+        match better_approaches::divide_safe(a, b) {
+            Ok(result) => writeln!(out, "✓ divide_safe({}, {}) = {}", a, b, result)?,
+            Err(e) => writeln!(out, "✓ divide_safe({}, {}): {}", a, b, e)?,
+        }
+    }
+    writeln!(out)?;
+    
+    // Demonstration 2: Chained unwraps
+    writeln!(out, "=== Example 2: Chained Operations ===")?;
+    match panics::capture(|| parse_and_double("not a number")) {
+        Ok(_) => writeln!(out, "Success")?,
+        Err(e) => caught(out, &e, "Invalid string caused parse().unwrap() to panic")?,
+    }
+    writeln!(out, "✓ parse_and_double(\"10\") = {}\n", parse_and_double("10"))?;
+    
+    // Demonstration 3: File operations
+    writeln!(out, "=== Example 3: File Operations ===")?;
+    match panics::capture(|| read_config_file("nonexistent.txt")) {
+        Ok(_) => writeln!(out, "Success")?,
+        Err(e) => caught(out, &e, "File doesn't exist, File::open().unwrap() panicked")?,
+    }
+    
+    // Better approach: a typed error, so each failure gets its own fix
+    let not_utf8 = std::env::temp_dir()
+        .join(format!("unwrap-philosophy-{}.conf", std::process::id()));
+    let _ = std::fs::write(&not_utf8, b"name = caf\xe9\n");
+    for path in [std::path::Path::new("nonexistent.txt"), not_utf8.as_path()] {
+        match better_approaches::read_config_file_safe(path).context("reading service config") {
+            Ok(contents) => writeln!(out, "✓ File contents: {}", contents)?,
+            Err(e) => {
+                writeln!(out, "✓ Error handled gracefully: {}", better_approaches::report_error(&e))?;
+                let fix = match e.inner() {
+                    better_approaches::ConfigError::NotFound(_) => "create it, or fix the path",
+                    better_approaches::ConfigError::PermissionDenied(_) => "check the file's owner and mode",
+                    better_approaches::ConfigError::InvalidUtf8 { .. } => "re-save the file as UTF-8",
+                    better_approaches::ConfigError::Io(_) => "retry, then check the disk",
+                };
+                writeln!(out, "  → Fix: {}", fix)?;
+            }
+        }
+    }
+    let _ = std::fs::remove_file(&not_utf8);
+    writeln!(out)?;
+    
+    // Demonstration 4: Nested unwraps
+    writeln!(out, "=== Example 4: Nested Option Unwrapping ===")?;
+    let nested_some = Some(Some(Some(42)));
+    writeln!(out, "✓ Nested Some: {}", get_nested_value(nested_some))?;
+    for nested in [Some(Some(None)), Some(None), None] {
+        match panics::capture(|| get_nested_value(nested)) {
+            Ok(_) => writeln!(out, "Success")?,
+            Err(e) => {
+                caught(out, &e, format_args!("{:?} - unwrap() can't say which layer was None", nested))?
+            }
+        }
+        match better_approaches::get_nested_value_safe(nested) {
+            Ok(value) => writeln!(out, "✓ Value: {}", value)?,
+            Err(e) => writeln!(out, "✓ Error handled gracefully: {}", e)?,
+        }
+    }
+    writeln!(out, "  The same lookup with combinators - flatten, and_then, ? - then ok_or per layer:")?;
+    for nested in [Some(Some(Some(42))), Some(Some(None)), Some(None), None] {
+        let (flattened, chained, question_mark) = (
+            better_approaches::nested_flatten(nested),
+            better_approaches::nested_and_then(nested),
+            better_approaches::nested_question_mark(nested),
+        );
+        let agree = if flattened == chained && chained == question_mark { "agree" } else { "DISAGREE" };
+        let input = format!("{:?}", nested);
+        match better_approaches::nested_missing_at(nested) {
+            Ok(value) => writeln!(out, "  {:<20} all three {}: {:?} → {}", input, agree, flattened, value)?,
+            Err(at) => writeln!(out, "  {:<20} all three {}: {:?} → missing at {:?}", input, agree, flattened, at)?,
+        }
+    }
+    writeln!(out)?;
+    
+    // Demonstration 5: Vector access
+    writeln!(out, "=== Example 5: Collection Access ===")?;
+    let numbers = vec![1, 2, 3, 4, 5];
+    for index in [2, 10] {
+        // A shared borrow is UnwindSafe, so the closure can use `numbers` directly
+        match panics::capture(|| get_element(&numbers, index)) {
+            Ok(value) => writeln!(out, "✓ get_element(&numbers, {}) = {}", index, value)?,
+            Err(e) => caught(out, &e, "Out of bounds access caused unwrap() to panic")?,
+        }
+        match better_approaches::get_element_safe(&numbers, index) {
+            Ok(value) => writeln!(out, "✓ get_element_safe(&numbers, {}) = {}", index, value)?,
+            Err(e) => writeln!(out, "✓ Error handled gracefully: {}", e)?,
+        }
+    }
+    let names = vec![String::from("ada"), String::from("grace")];
+    match better_approaches::get_element_ref(&names, 1) {
+        Ok(name) => writeln!(out, "✓ get_element_ref(&names, 1) = {:?} - borrowed, nothing cloned", name)?,
+        Err(e) => writeln!(out, "✓ Error handled gracefully: {}", e)?,
+    }
+    writeln!(out)?;
+    
+    // Demonstration 6: todo!() and unimplemented!()
+    writeln!(out, "=== Example 6: Unfinished Code Paths ===")?;
+    writeln!(out, "✓ export_report(\"text\") = {}", export_report("text"))?;
+    match panics::capture(|| export_report("json")) {
+        Ok(_) => writeln!(out, "Success")?,
+        Err(e) => match &e {
+            panics::CaptureError::Panicked(report) if report.is_unfinished() => {
+                caught(out, &e, format_args!("todo!() reached - \"{}\" is a panic too", report.message))?
+            }
+            _ => caught(out, &e, "export_report(\"json\") panicked")?,
+        },
+    }
+    match better_approaches::export_report_safe("json") {
+        Ok(report) => writeln!(out, "✓ {}", report)?,
+        Err(e) => writeln!(out, "✓ Caller can feature-detect: {}", e)?,
+    }
+    let mut service = system_design::Service::with_seed(0.0, 1);
+    let export = system_design::Request {
+        id: 1,
+        op: system_design::OpKind::Export,
+        payload: Some(String::from("report")),
+        deadline: None,
+        arrival: Duration::ZERO,
+    };
+    if let Err(e) = service.handle_request_safe(export) {
+        writeln!(out, "✓ The service says so too: {} ({} upstream calls)", e, service.attempts())?;
+    }
+    writeln!(out)?;
+    
+    // Demonstration 7: Option vs Result - absence is not failure
+    writeln!(out, "=== Example 7: Not Found vs Lookup Failed ===")?;
+    let mut directory = better_approaches::UserDirectory::new(vec![
+        better_approaches::User { id: 1, name: String::from("Ada") },
+    ]);
+    writeln!(out, "✓ {}", greet_user(&directory, 1))?;
+    directory.set_available(false);
+    match panics::capture(|| greet_user(&directory, 1)) {
+        Ok(_) => writeln!(out, "Success")?,
+        Err(e) => caught(out, &e, "Directory outage looked like a missing user - unwrap() can't tell")?,
+    }
+    directory.set_available(true);
+    
+    let (mut found, mut not_found, mut failed) = (0, 0, 0);
+    for (id, available) in [(1, true), (2, true), (1, false)] {
+        directory.set_available(available);
+        match directory.lookup(id) {
+            better_approaches::Lookup::Found(user) => {
+                found += 1;
+                writeln!(out, "✓ lookup({}): found {}", id, user.name)?;
+            }
+            better_approaches::Lookup::NotFound => {
+                not_found += 1;
+                writeln!(out, "✓ lookup({}): no such user (business outcome, not an error)", id)?;
+            }
+            better_approaches::Lookup::Failed(e) => {
+                failed += 1;
+                writeln!(out, "✓ lookup({}): failed - {} (retry or degrade)", id, e)?;
+            }
+        }
+    }
+    writeln!(out, "  Outcomes: {} found, {} not found, {} failed\n", found, not_found, failed)?;
+    
+    // Demonstration 8: Persistence after a torn write
+    writeln!(out, "=== Example 8: Persistence After a Crash ===")?;
+    let store_path = std::env::temp_dir()
+        .join(format!("unwrap-philosophy-{}.kv", std::process::id()));
+    if let Err(e) = demonstrate_torn_write(out, &store_path) {
+        writeln!(out, "⚠ Persistence demo skipped: {}", e)?;
+    }
+    let _ = std::fs::remove_file(&store_path);
+    writeln!(out)?;
+    
+    // Demonstration 9: Map lookups
+    writeln!(out, "=== Example 9: Map Lookup ===")?;
+    let mut ages = std::collections::HashMap::from([
+        (String::from("ada"), 36),
+        (String::from("grace"), 85),
+    ]);
+    writeln!(out, "✓ get_user_age(&ages, \"ada\") = {}", get_user_age(&ages, "ada"))?;
+    match panics::capture(|| get_user_age(&ages, "linus")) {
+        Ok(_) => writeln!(out, "Success")?,
+        Err(e) => caught(out, &e, "Missing key - map.get(name).unwrap() panicked")?,
+    }
+    match better_approaches::get_user_age_safe(&ages, "linus") {
+        Ok(age) => writeln!(out, "✓ Age: {}", age)?,
+        Err(e) => writeln!(out, "✓ Error handled gracefully: {}", e)?,
+    }
+    let age = better_approaches::get_user_age_or_insert(&mut ages, "linus", 0);
+    writeln!(out, "✓ With the entry API and a default: linus is {} ({} entries now)\n", age, ages.len())?;
+    
+    // Demonstration 10: Lock poisoning
+    writeln!(out, "=== Example 10: Mutex Poisoning ===")?;
+    let counter = std::sync::Mutex::new(41);
+    // A worker's panic ends the whole process when panics abort, caught or not
+    if panics::UNWINDS {
+        poison_while_holding(&counter);
+        writeln!(out, "✗ A worker thread panicked while holding the lock (poisoned: {})", counter.is_poisoned())?;
+    }
+    match panics::capture(|| read_counter(&counter)) {
+        Ok(_) => writeln!(out, "Success")?,
+        Err(e) => caught(out, &e, "lock().unwrap() on a poisoned mutex - the worker's failure spread")?,
+    }
+    writeln!(out, "✓ Recovered with PoisonError::into_inner: counter = {}",
+                  better_approaches::read_counter_recovering(&counter))?;
+    writeln!(out, "  Safe here: the counter is whole after every update. Half-done updates are not.\n")?;
+    
+    // Demonstration 11: Environment variables
+    writeln!(out, "=== Example 11: Configuration via Environment ===")?;
+    demonstrate_env_layers(out)?;
+    writeln!(out)?;
+    
+    // Demonstration 12: Wall-clock time
+    writeln!(out, "=== Example 12: Clock Skew ===")?;
+    let now = std::time::SystemTime::now();
+    let earlier = now - std::time::Duration::from_secs(10);
+    let future = now + std::time::Duration::from_secs(10); // as if the clock was set back 10s
+    writeln!(out, "✓ elapsed_since(10s ago) = {}s", elapsed_since(earlier).as_secs())?;
+    match panics::capture(|| elapsed_since(future)) {
+        Ok(_) => writeln!(out, "Success")?,
+        Err(e) => caught(out, &e, "duration_since().unwrap() - the timestamp is in the future")?,
+    }
+    match better_approaches::elapsed_checked(future) {
+        Ok(elapsed) => writeln!(out, "✓ Elapsed: {:?}", elapsed)?,
+        Err(e) => writeln!(out, "✓ Error handled gracefully: ~{}s in the future", e.ahead_by.as_secs_f64().round())?,
+    }
+    writeln!(out, "✓ elapsed_or_zero clamps it: {:?}\n", better_approaches::elapsed_or_zero(future))?;
+    
+    // Demonstration 13: Decoding bytes
+    writeln!(out, "=== Example 13: Decoding a Network Buffer ===")?;
+    let buffer = b"status=ok \xff retry=3".to_vec(); // one stray 0xFF in the middle
+    writeln!(out, "  Buffer: b\"{}\"", buffer.escape_ascii())?;
+    match panics::capture(|| decode_message(buffer.clone())) {
+        Ok(_) => writeln!(out, "Success")?,
+        Err(e) => caught(out, &e, "String::from_utf8().unwrap() - one bad byte, whole message lost")?,
+    }
+    match better_approaches::decode_message_safe(buffer.clone()) {
+        Ok(text) => writeln!(out, "✓ Decoded: {}", text)?,
+        Err(e) => {
+            writeln!(out, "✓ Propagated: {}", e)?;
+            let recovered = e.into_bytes();
+            writeln!(out, "  into_bytes() gives the buffer back: {} bytes, nothing lost", recovered.len())?;
+        }
+    }
+    writeln!(out, "✓ Lossy:     {:?} (the bad byte is now U+FFFD)", better_approaches::decode_message_lossy(&buffer))?;
+    writeln!(out, "✓ Truncated: {:?} (everything after it is gone)\n",
+                  better_approaches::decode_message_truncated(&buffer))?;
+    
+    // Demonstration 14: Channels
+    writeln!(out, "=== Example 14: A Worker Dies Mid-Stream ===")?;
+    writeln!(out, "✓ sum_results(all 5 jobs) = {}", sum_results(&start_worker(5, None), 5))?;
+    let results = start_worker(5, Some(2));
+    match panics::capture(|| sum_results(&results, 5)) {
+        Ok(_) => writeln!(out, "Success")?,
+        Err(e) => caught(out, &e, "Worker died after 2 jobs - recv().unwrap() crashed the consumer too")?,
+    }
+    let timeout = std::time::Duration::from_millis(100);
+    let collected = better_approaches::collect_results(&start_worker(5, Some(2)), 5, timeout);
+    writeln!(out, "✓ Drained gracefully: {:?} then {:?}", collected.results, collected.end)?;
+    let (_stalled, silent) = std::sync::mpsc::channel::<u32>(); // alive, never sends
+    let collected = better_approaches::collect_results(&silent, 5, timeout);
+    writeln!(out, "✓ recv_timeout({:?}) on a stalled worker: {:?} instead of blocking forever\n",
+                  timeout, collected.end)?;
+    
+    // Demonstration 15: Joining a panicked thread
+    writeln!(out, "=== Example 15: join().unwrap() ===")?;
+    writeln!(out, "✓ run_job(\"21\") = {}", run_job("21"))?;
+    match panics::capture(|| run_job("twenty-one")) {
+        Ok(_) => writeln!(out, "Success")?,
+        Err(e) => caught(out, &e, "The worker panicked, and join().unwrap() panicked the parent too")?,
+    }
+    if panics::UNWINDS {
+        match panics::join_report(spawn_worker("twenty-one")) {
+            Ok(result) => writeln!(out, "✓ Result: {}", result)?,
+            Err(e) => writeln!(out, "✓ Worker failure logged, parent carries on: {}", e)?,
+        }
+    }
+    writeln!(out)?;
+    
+    // Demonstration 16: Aggregates over empty data
+    writeln!(out, "=== Example 16: Aggregating Possibly-Empty Data ===")?;
+    let prices = [120, 95, 310];
+    writeln!(out, "✓ highest_price_above(&prices, 100) = {}", highest_price_above(&prices, 100))?;
+    match panics::capture(|| highest_price_above(&prices, 500)) {
+        Ok(_) => writeln!(out, "Success")?,
+        Err(e) => caught(out, &e, "No prices above 500 - max().unwrap() on an empty iterator")?,
+    }
+    writeln!(out, "✓ unwrap_or_default: {}", better_approaches::highest_price_or_default(&prices, 500))?;
+    match better_approaches::highest_price_checked(&prices, 500) {
+        Ok(price) => writeln!(out, "✓ Highest: {}", price)?,
+        Err(e) => writeln!(out, "✓ Error handled gracefully: {}", e)?,
+    }
+    for values in [&[][..], &[2.5, f64::NAN, 7.5][..]] {
+        let summary = better_approaches::summarize(values);
+        writeln!(out, "✓ summarize({:?}): max {:?}, mean {:?}, {} NaN skipped",
+                      values, summary.max, summary.mean, summary.nan_count)?;
+    }
+    writeln!(out)?;
+    
+    // Demonstration 17: Narrowing conversions
+    writeln!(out, "=== Example 17: Numeric Conversions ===")?;
+    let boundary = u64::from(u32::MAX);
+    writeln!(out, "✓ frame_length({}) = {}", boundary, frame_length(boundary))?;
+    writeln!(out, "✓ buffer_length({}) = {} (on this {}-bit target)",
+                  boundary + 1, buffer_length(boundary + 1), usize::BITS)?;
+    match panics::capture(|| frame_length(boundary + 1)) {
+        Ok(_) => writeln!(out, "Success")?,
+        Err(e) => {
+            caught(out, &e, format_args!("{} - one past u32::MAX, try_from().unwrap() panicked", boundary + 1))?
+        }
+    }
+    match better_approaches::convert::<u32>(boundary + 1) {
+        Ok(length) => writeln!(out, "✓ Length: {}", length)?,
+        Err(e) => writeln!(out, "✓ Checked: {}", e)?,
+    }
+    writeln!(out, "✓ Saturating: {} (clamped, no error - only if clamping is honest)\n",
+                  better_approaches::to_u32_saturating(boundary + 1))?;
+    
+    // Demonstration 18: Fields of a parsed config
+    writeln!(out, "=== Example 18: Parsed Config Fields ===")?;
+    for text in ["host = db.internal\nport = 5432", "# no port here\n\nhost = db.internal", "port = notanumber"] {
+        let config = better_approaches::parse_config(text);
+        match panics::capture(|| get_port(&config)) {
+            Ok(port) => writeln!(out, "✓ get_port = {}", port)?,
+            Err(e) => caught(out, &e, "get_port(): .get().unwrap().parse().unwrap() - which one failed?")?,
+        }
+        match better_approaches::get_port_safe(&config).context("loading database settings") {
+            Ok(port) => writeln!(out, "✓ get_port_safe = {}", port)?,
+            Err(e) => writeln!(out, "✓ Error handled gracefully: {}", better_approaches::report_error_line(&e))?,
+        }
+    }
+    writeln!(out)?;
+    
+    // Demonstration 19: Panicking in Drop
+    writeln!(out, "=== Example 19: A Panic in Drop ===")?;
+    match panics::capture(|| drop(NoisyGuard::new("log buffer", true))) {
+        Ok(()) => writeln!(out, "Success")?,
+        Err(e) => caught(out, &e, "flush().unwrap() in Drop - no unwind under way, so catch_unwind saw it")?,
+    }
+    writeln!(out, "⚠ Had the guard dropped during an unwind - another panic already in flight - the")?;
+    writeln!(out, "  second panic would abort the process: no catch_unwind, no destructors, no report.")?;
+    writeln!(out, "  (`unwrap examples --dangerous-demos` really does it, last.)")?;
+    let guard = NoisyGuard::new("audit log", false);
+    let failed_cleanup = std::cell::RefCell::new(None);
+    drop(better_approaches::defer_fallible(|| guard.flush(), |e| *failed_cleanup.borrow_mut() = Some(e)));
+    if let Some(e) = failed_cleanup.take() {
+        writeln!(out, "✓ defer_fallible(|| guard.flush(), on_error): the failure went to on_error, no panic: {}", e)?;
+    }
+    if let Err(e) = better_approaches::defer_fallible(|| guard.flush(), drop).finish() {
+        writeln!(out, "✓ finish() runs it where the error can be returned: {}", e)?;
+    }
+    writeln!(out)?;
+    
+    Ok(())
+}
+
+/// The abort Example 19 only describes: a NoisyGuard dropped while a panic
+/// is unwinding panics a second time, and the process aborts. Never returns
+/// in a build that unwinds
+pub fn double_panic(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "=== DANGEROUS: A Panic in Drop During an Unwind ===")?;
+    writeln!(out, "Panicking while a NoisyGuard is alive. Its Drop panics too, and Rust aborts:")?;
+    writeln!(out, "expect \"panic in a destructor during cleanup\" and an abort, not an exit code.\n")?;
+    out.flush()?;
+    let result = panics::capture(|| {
+        let _guard = NoisyGuard::new("log buffer", true);
+        panic!("the first panic");
+    });
+    match result {
+        Err(e @ panics::CaptureError::Unsupported) => writeln!(out, "⚠ Skipped - {}", e),
+        _ => writeln!(out, "✗ Still running - the double panic should have aborted"),
+    }
+}
+
+/// How a panic travels, and the better approaches that stop it
+pub fn cascade(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "=== THE CASCADE EFFECT ===")?;
+    writeln!(out, "When unwrap() panics, it:")?;
+    writeln!(out, "  1. Immediately terminates the current function")?;
+    writeln!(out, "  2. Unwinds the stack (unless caught)")?;
+    writeln!(out, "  3. Propagates up the call chain")?;
+    writeln!(out, "  4. Can crash the entire program")?;
+    writeln!(out, "  5. Crosses threads: a poisoned lock or a dead worker's channel crashes the caller")?;
+    writeln!(out, "\nThe 'problem' literally unwraps itself into a program crash!\n")?;
+    
+    // parse_and_double has two unwrap() sites, and both have to hold
+    writeln!(out, "=== THE ARITHMETIC OF A CHAIN ===")?;
+    writeln!(out, "parse_and_double() unwraps twice: parse() and divide(). Both must hold,")?;
+    writeln!(out, "so it is two components in series. At 99.9% each:")?;
+    let two_sites = stats::series_availability(&[0.999, 0.999]);
+    writeln!(out, "  0.999 × 0.999 = {:.4}% - a {:.2}% chance to panic, double one site's\n",
+                  two_sites * 100.0, (1.0 - two_sites) * 100.0)?;
+    writeln!(out, "  unwrap() sites   all hold   P(panic)   each with a fallback")?;
+    for sites in 1..=10 {
+        let chain = stats::series_availability(&vec![0.999; sites]);
+        let pair = stats::parallel_availability(&[0.999, 0.999]);
+        let backed = stats::series_availability(&vec![pair; sites]);
+        writeln!(out, "  {:>15} {:>9.3}% {:>9.3}% {:>21.5}%", sites, chain * 100.0, (1.0 - chain) * 100.0, backed * 100.0)?;
+    }
+    writeln!(out, "  Every unwrap() adds its failures to the chain; a fallback beside")?;
+    writeln!(out, "  each one (1 - 0.001²) keeps even ten of them at five nines.\n")?;
+    
+    // Better approach summary
+    writeln!(out, "=== BETTER APPROACHES ===")?;
+    match better_approaches::parse_and_double_safe("15") {
+        Ok(result) => writeln!(out, "✓ Safe parsing: 15 -> {}", result)?,
+        Err(e) => writeln!(out, "✗ Error: {}", e)?,
+    }
+    
+    let input = "invalid";
+    match better_approaches::parse_and_double_safe(input).with_context(|| format!("doubling {:?}", input)) {
+        Ok(result) => writeln!(out, "✓ Result: {}", result)?,
+        Err(e) => writeln!(out, "✓ Graceful error handling: {}", better_approaches::report_error_line(&e))?,
+    }
+    
+    // Errors wrap errors: a config loader that failed because a value didn't parse
+    if let Err(e) = better_approaches::parse_and_double_safe("80x") {
+        let io = std::io::Error::new(std::io::ErrorKind::InvalidData, e);
+        let err = better_approaches::ConfigError::Io(io);
+        writeln!(out, "\nThe whole chain, walked through source():")?;
+        writeln!(out, "{}", better_approaches::report_error(&err))?;
+    }
+    
+    writeln!(out, "\n🎯 KEY TAKEAWAY:")?;
+    writeln!(out, "Use ? operator, match, or if let instead of unwrap()")?;
+    writeln!(out, "Let errors propagate gracefully, not explosively!")?;
+    Ok(())
+}
+
+/// What one failure costs each design, assumed: a process restart after
+/// a crash, the one request after an error, nothing when a fallback answers
+fn recovery_time(design: system_design::Design) -> (Duration, &'static str) {
+    match design {
+        system_design::Design::Unsafe => (Duration::from_secs(30), "30s process restart"),
+        system_design::Design::Safe | system_design::Design::Retry => {
+            (system_design::ARRIVAL_INTERVAL, "10ms, one request")
+        }
+        system_design::Design::Resilient => (Duration::ZERO, "instant, fallback"),
+    }
+}
+
+/// Observed failure rates turned into MTBF, then availability and the
+/// downtime a year of it adds up to
+fn downtime_per_year(out: &mut dyn Write) -> io::Result<()> {
+    let config = system_design::SimConfig { requests: 10_000, seed: Some(42), ..Default::default() };
+    let reports: Vec<_> = panics::without_panic_messages(|| {
+        system_design::Design::ALL.iter().map(|&design| system_design::simulate_with_config(design, &config)).collect()
+    });
+    let failure_share = |report: &system_design::SimulationReport| {
+        report.failed as f64 / (report.successful + report.failed + report.deadline_exceeded).max(1) as f64
+    };
+    // A crash ends the fail-fast run at its first failure; the upstream it
+    // calls fails as often as the safe design's does - same seed, same rolls
+    let upstream = reports.iter()
+        .find(|report| report.design == system_design::Design::Safe)
+        .map_or(config.failure_rate, failure_share);
+    
+    writeln!(out, "\n=== The Cost of a Failure: Downtime per Year ===")?;
+    writeln!(out, "  {} requests, λ = {}, arriving every {:?}; MTBF from the observed failures",
+                  config.requests, config.failure_rate, system_design::ARRIVAL_INTERVAL)?;
+    writeln!(out, "  design       failed      MTBF  recovery (MTTR)      availability")?;
+    for report in &reports {
+        let share = match report.design {
+            system_design::Design::Unsafe => upstream,
+            _ => failure_share(report),
+        };
+        let mtbf = if share > 0.0 { system_design::ARRIVAL_INTERVAL.div_f64(share) } else { Duration::MAX };
+        let (mttr, recovery) = recovery_time(report.design);
+        let availability = stats::availability(mtbf, mttr);
+        writeln!(out, "  {:<10} {:>7.2}% {:>8.2}s  {:<20} {}", report.design.name(), share * 100.0,
+                      mtbf.as_secs_f64(), recovery, stats::nines(availability))?;
+    }
+    writeln!(out, "  Same upstream, same failures: what each one costs is the design decision.")?;
+    Ok(())
+}
+
+/// Is Rust to blame? The four designs under the same production load
+pub fn design_question(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "\n")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "IS RUST TO BLAME? The CloudFlare Question")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "As an assembly-level system, the language isn't to blame.")?;
+    writeln!(out, "Rust PROVIDES the tools for safe error handling.")?;
+    writeln!(out, "The choice to use unwrap() is a DESIGN DECISION.\n")?;
+    
+    writeln!(out, "All systems carry a Poisson distribution of potential failures.")?;
+    writeln!(out, "Runtime IS test copy - failures WILL occur in production.")?;
+    writeln!(out, "The question is: How does your system design respond?\n")?;
+    
+    // Demonstrate four system design approaches
+    simulate(out, &system_design::Design::ALL, None, Format::Text, None)?;
+    downtime_per_year(out)?;
+    
+    // A fallback still calls the broken upstream every time - a breaker stops that
+    writeln!(out, "\n=== Circuit Breaker: Closed → Open → Half-Open ===")?;
+    let clock = system_design::ManualClock::new();
+    let mut breaker = system_design::CircuitBreaker::new(3, Duration::from_secs(1), 2, &clock);
+    // (milliseconds since start, does the upstream answer?)
+    let script = [(0, false), (100, false), (200, false), (300, true), (600, true),
+                  (1300, true), (1400, true), (1500, true)];
+    let mut elapsed = 0;
+    for (ms, upstream_ok) in script {
+        clock.advance(Duration::from_millis(ms - elapsed));
+        elapsed = ms;
+        let line = if !breaker.allow_request() {
+            "short-circuited to fallback"
+        } else if upstream_ok {
+            breaker.record_success();
+            "upstream ok"
+        } else {
+            breaker.record_failure();
+            "upstream failed"
+        };
+        writeln!(out, "  t={:>4}ms  {:<28} breaker: {:?}", ms, line, breaker.state())?;
+    }
+    writeln!(out, "  Three failures open the breaker; after 1s, two good probes close it.")?;
+    
+    // Isolation: a flood of slow requests must not take every thread with it
+    writeln!(out, "\n=== Bulkhead: 10 Simultaneous Slow Requests, 2 Slots ===")?;
+    let bulkhead = system_design::Bulkhead::new(2);
+    let everyone_tried = std::sync::Barrier::new(10);
+    let admitted = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..10)
+            .map(|_| scope.spawn(|| {
+                let permit = bulkhead.try_acquire();
+                // Still "working": nobody releases a slot until all ten have asked
+                everyone_tried.wait();
+                permit.is_ok()
+            }))
+            .collect();
+        workers.into_iter().filter_map(|worker| worker.join().ok()).filter(|&ok| ok).count()
+    });
+    writeln!(out, "  Admitted: {}   Rejected immediately with Overloaded: {}", admitted, 10 - admitted)?;
+    writeln!(out, "  The other 8 get a fast \"try later\" instead of an unbounded queue.")?;
+    worker_pools(out)?;
+    
+    // Retrying is the next step beyond failing safely - but how long to wait?
+    writeln!(out, "\n=== Retry Delays: Backoff Strategies ===")?;
+    let strategies = [
+        ("Exponential", system_design::BackoffStrategy::Exponential),
+        ("Full jitter", system_design::BackoffStrategy::FullJitter),
+        ("Decorrelated", system_design::BackoffStrategy::DecorrelatedJitter),
+    ];
+    for (name, strategy) in strategies {
+        let delays: Vec<String> = system_design::BackoffIterator::new(
+            strategy,
+            std::time::Duration::from_millis(100),
+            std::time::Duration::from_secs(2),
+        )
+        .with_seed(42)
+        .take(6)
+        .map(|delay| format!("{}ms", delay.as_millis()))
+        .collect();
+        writeln!(out, "  {:<13} {}", name, delays.join(" → "))?;
+    }
+    let budgeted = system_design::BackoffIterator::new(
+        system_design::BackoffStrategy::Exponential,
+        std::time::Duration::from_millis(100),
+        std::time::Duration::from_secs(2),
+    )
+    .with_max_elapsed(std::time::Duration::from_secs(5))
+    .count();
+    writeln!(out, "  Delays are capped at 2s - even attempt #1000 cannot overflow into a panic.")?;
+    writeln!(out, "  With a 5s total retry budget the schedule ends after {} attempts.", budgeted)?;
+    Ok(())
+}
+
+/// One thread, a pool that lets its workers die, and a supervised pool,
+/// on the same 100 requests: every 10th has no payload, and the handler
+/// unwraps it
+fn worker_pools(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "\n=== Worker Pools: 100 Requests, Every 10th Panics ===")?;
+    if !panics::UNWINDS {
+        return writeln!(out, "  ⚠ Skipped - this build has panic = \"abort\": the first panic would end the process");
+    }
+    let requests: Vec<system_design::Request> = (1..=100)
+        .map(|id| system_design::Request {
+            id,
+            op: system_design::OpKind::Process,
+            payload: (id % 10 != 0).then(|| format!("req{}", id)),
+            deadline: None,
+            arrival: Duration::ZERO,
+        })
+        .collect();
+    let handler = |request: &system_design::Request| {
+        let payload = request.payload.as_deref().unwrap(); // The handler's own unwrap
+        system_design::Response { id: request.id, body: payload.to_uppercase(), latency: Duration::ZERO }
+    };
+    let architectures = [
+        ("one thread, unwrap()", 1, system_design::Supervision::None),
+        ("pool of 4, no restart", 4, system_design::Supervision::None),
+        ("pool of 4, supervised", 4, system_design::Supervision::Restart),
+    ];
+    writeln!(out, "  architecture            served  failed  dropped  workers left  restarts")?;
+    for (name, workers, supervision) in architectures {
+        let pool = system_design::WorkerPool::new(workers, supervision);
+        let report = panics::without_panic_messages(|| pool.run(&requests, handler));
+        writeln!(out, "  {:<22} {:>7} {:>7} {:>8} {:>13} {:>9}", name, report.served(), report.failed(),
+                      report.dropped(), report.workers_left, report.restarts)?;
+    }
+    writeln!(out, "  One thread dies with its first panic. Without restarts, each panic costs a worker")?;
+    writeln!(out, "  until none are left; supervised, only the request that panicked is lost.")?;
+    Ok(())
+}
+
+/// Writes `{"<key>": [...]}` and nothing else
+fn write_json_array<T: json::ToJson>(out: &mut dyn Write, key: &str, items: &[T]) -> io::Result<()> {
+    writeln!(out, "{}", json::Object::new().field(key, json::array(items)).finish())
+}
+
+/// Runs the production load simulation for each design and writes the reports
+/// Every design faces the same failures: one seed is shared across them
+/// `verbosity` None picks one from the request count
+pub fn simulate(
+    out: &mut dyn Write,
+    designs: &[system_design::Design],
+    seed: Option<u64>,
+    format: Format,
+    verbosity: Option<system_design::Verbosity>,
+) -> io::Result<Vec<system_design::SimulationReport>> {
+    let requests = system_design::default_requests();
+    let verbosity = verbosity.unwrap_or(system_design::Verbosity::default_for(requests.len()));
+    let seed = seed.unwrap_or_else(system_design::entropy_seed);
+    let run = |design| system_design::simulate_production_load_seeded(design, &requests, seed);
+    if format == Format::Json {
+        let reports: Vec<_> = panics::without_panic_messages(|| designs.iter().map(|&d| run(d)).collect());
+        write_json_array(out, "reports", &reports)?;
+        return Ok(reports);
+    }
+    
+    let mut reports = Vec::with_capacity(designs.len());
+    for &design in designs {
+        let report = run(design);
+        system_design::write_report(out, &report, verbosity)?;
+        reports.push(report);
+    }
+    Ok(reports)
+}
+
+/// Same as `simulate`, on a generated load of `config.requests` requests
+pub fn simulate_generated(
+    out: &mut dyn Write,
+    designs: &[system_design::Design],
+    config: &system_design::SimConfig,
+    format: Format,
+    verbosity: Option<system_design::Verbosity>,
+) -> io::Result<Vec<system_design::SimulationReport>> {
+    let config = system_design::SimConfig {
+        seed: Some(config.seed.unwrap_or_else(system_design::entropy_seed)),
+        ..*config
+    };
+    if format == Format::Json {
+        let reports: Vec<_> = panics::without_panic_messages(|| {
+            designs.iter().map(|&design| system_design::simulate_with_config(design, &config)).collect()
+        });
+        write_json_array(out, "reports", &reports)?;
+        return Ok(reports);
+    }
+    
+    let reports = designs.iter().map(|&design| system_design::simulate_with_config(design, &config));
+    if verbosity == Some(system_design::Verbosity::Quiet) {
+        return Ok(reports.collect());
+    }
+    
+    match config.arrivals {
+        Some(load) => writeln!(out, "\nPoisson arrivals: {} requests/s for {:?}, failure rate λ = {}",
+                               load.rate, load.duration, config.failure_rate)?,
+        None => writeln!(out, "\n{} requests, failure rate λ = {}", config.requests, config.failure_rate)?,
+    }
+    if let Some(limit) = config.rate_limit {
+        writeln!(out, "Rate limit: {} per second, bursts of {} (one request every {:?})",
+                 limit.per_second, limit.burst, system_design::ARRIVAL_INTERVAL)?;
+    }
+    let mut written = Vec::with_capacity(designs.len());
+    for report in reports {
+        // Poisson loads aren't sized until they are generated
+        let verbosity = verbosity.unwrap_or(system_design::Verbosity::default_for(report.outcomes.len()));
+        system_design::write_report(out, &report, verbosity)?;
+        if let Some(load) = config.arrivals {
+            system_design::write_failure_windows(out, &report, load.rate, config.failure_rate)?;
+        }
+        written.push(report);
+    }
+    Ok(written)
+}
+
+/// Every report's requests in one CSV file, under a single header row
+pub fn write_csv(path: &std::path::Path, reports: &[system_design::SimulationReport]) -> io::Result<()> {
+    let mut out = io::BufWriter::new(std::fs::File::create(path)?);
+    writeln!(out, "{}", system_design::CSV_HEADER)?;
+    for report in reports {
+        report.write_csv_rows(&mut out)?;
+    }
+    out.flush()
+}
+
+/// Writes every lesson to `<dir>/<id>.md`, creating `dir` if needed
+pub fn export_markdown(out: &mut dyn Write, dir: &std::path::Path) -> io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    for lesson in all_lessons() {
+        let path = dir.join(format!("{}.md", lesson.id()));
+        std::fs::write(&path, lesson.render_markdown())?;
+        writeln!(out, "✓ {}", path.display())?;
+    }
+    Ok(())
+}
+
+/// All designs on one seeded load, as a single table
+pub fn compare(out: &mut dyn Write, config: &system_design::SimConfig, format: Format) -> io::Result<()> {
+    // The fail-fast design's panic is already counted in the table
+    let table = panics::without_panic_messages(|| system_design::compare_designs(config));
+    if format == Format::Json {
+        return writeln!(out, "{}", json::ToJson::to_json(&table));
+    }
+    
+    match config.arrivals {
+        Some(load) => writeln!(out, "\nPoisson arrivals: {} requests/s for {:?}, failure rate λ = {}\n",
+                               load.rate, load.duration, config.failure_rate)?,
+        None => writeln!(out, "\n{} requests, failure rate λ = {}\n", config.requests, config.failure_rate)?,
+    }
+    writeln!(out, "{}", table)
+}
+
+fn write_comparison(out: &mut dyn Write, comparison: &bench::Comparison) -> io::Result<()> {
+    let baseline = &comparison.baseline;
+    writeln!(out, "  {:<34} {:>10.1} {:>9.1}×", baseline.name, baseline.ns_per_op(), 1.0)?;
+    match (&comparison.contender, comparison.factor()) {
+        (Some(contender), Some(factor)) => {
+            writeln!(out, "  {:<34} {:>10.1} {:>9.1}×", contender.name, contender.ns_per_op(), factor)
+        }
+        _ => writeln!(out, "  {:<34} {:>10} {:>10}", "(panic = \"abort\": can't run)", "-", "-"),
+    }
+}
+
+/// `advise`: the decision tree's recommendations, narrowed to one error
+/// kind and/or one call context
+pub fn advise(
+    out: &mut dyn Write,
+    kind: Option<advice::ErrorKind>,
+    context: Option<advice::CallContext>,
+) -> io::Result<()> {
+    let contexts = advice::CallContext::ALL.into_iter().filter(|c| context.is_none_or(|want| want == *c));
+    for context in contexts {
+        for kind in advice::ErrorKind::ALL.into_iter().filter(|k| kind.is_none_or(|want| want == *k)) {
+            writeln!(out, "{}", advice::recommend(kind, context))?;
+        }
+    }
+    Ok(())
+}
+
+/// Times panics against Result on this machine, then says what the
+/// numbers do and don't prove
+pub fn benchmarks(out: &mut dyn Write, budget: Duration) -> io::Result<()> {
+    writeln!(out, "=== BENCHMARK: WHAT A PANIC COSTS ===")?;
+    writeln!(out, "Each operation repeated for at least {:?} on this machine.", budget)?;
+    if cfg!(debug_assertions) {
+        writeln!(out, "⚠ Debug build: `cargo run --release -- bench` gives the numbers worth quoting.")?;
+    }
+    out.flush()?;
+    let report = bench::run(budget);
+    
+    writeln!(out, "\n  {:<34} {:>10} {:>10}", "operation", "ns/op", "factor")?;
+    write_comparison(out, &report.propagation)?;
+    writeln!(out)?;
+    write_comparison(out, &report.success_path)?;
+    writeln!(out)?;
+    write_comparison(out, &report.catch_setup)?;
+    
+    writeln!(out, "\n=== PERFORMANCE IS NOT THE REASON — CORRECTNESS IS ===")?;
+    writeln!(out, "• unwrap() on Some: {:.1} ns, match: {:.1} ns. The success path is free either way,",
+                  report.success_path.contender.as_ref().map_or(f64::NAN, bench::Measurement::ns_per_op),
+                  report.success_path.baseline.ns_per_op())?;
+    writeln!(out, "  so nobody should avoid unwrap() to go faster.")?;
+    writeln!(out, "• catch_unwind() with nothing to catch adds {:.1} ns: a safety net is cheap to hold.",
+                  (report.catch_setup.contender.as_ref().map_or(f64::NAN, bench::Measurement::ns_per_op)
+                      - report.catch_setup.baseline.ns_per_op()).max(0.0))?;
+    match report.propagation.factor() {
+        Some(factor) => {
+            writeln!(out, "• A panic through {} frames costs {:.0}× an Err - but only when something fails,", bench::DEPTH, factor)?;
+            writeln!(out, "  and failure should be rare. Speed isn't the argument against it either.")?;
+        }
+        None => writeln!(out, "• In this build a panic isn't slow - it is the end of the process.")?,
+    }
+    writeln!(out, "• unwrap() is wrong because it is a claim - \"this is never None\" - that the")?;
+    writeln!(out, "  compiler can't check and production will, eventually, prove false.\n")
+}
+
+/// The unwrap density of a scanned tree, as text, one JSON document or a SARIF log
+pub fn lint(out: &mut dyn Write, report: &lint::LintReport, format: LintFormat, policy: &lint::Policy) -> io::Result<()> {
+    match format {
+        LintFormat::Json => writeln!(out, "{}", json::ToJson::to_json(report)),
+        LintFormat::Sarif => writeln!(out, "{}", lint::to_sarif(report, policy)),
+        LintFormat::Text => lint::write_report(out, report, policy),
+    }
+}
+
+/// Many seeded runs per design, summarized - one run proves very little
+pub fn monte_carlo(
+    out: &mut dyn Write,
+    designs: &[system_design::Design],
+    config: &system_design::SimConfig,
+    iterations: usize,
+    format: Format,
+) -> io::Result<()> {
+    // Thousands of caught unwrap() panics would bury the summary in panic messages
+    let summaries: Vec<_> = panics::without_panic_messages(|| {
+        designs.iter()
+            .map(|&design| system_design::run_monte_carlo(design, config, iterations))
+            .collect()
+    });
+    if format == Format::Json {
+        return write_json_array(out, "monte_carlo", &summaries);
+    }
+    
+    writeln!(out, "\n{} runs of {} requests per design, failure rate λ = {}",
+             iterations, config.requests, config.failure_rate)?;
+    for summary in summaries {
+        writeln!(out, "\n{}", summary)?;
+        // How sure one run can be, and how much surer pooling the runs makes
+        // it, at the availability all of them averaged
+        writeln!(out, "  95% interval at {:.2}%, pooling 1, 10, 100... of the {} runs:",
+                      summary.mean_availability * 100.0, iterations)?;
+        let pooled = std::iter::successors(Some(1usize), |&runs| runs.checked_mul(10))
+            .take_while(|&runs| runs < iterations)
+            .chain(std::iter::once(iterations.max(1)));
+        for runs in pooled {
+            let requests = config.requests.saturating_mul(runs);
+            let successes = (summary.mean_availability * requests as f64).round() as u64;
+            let (low, high) = stats::wilson_interval(successes, requests as u64, 0.95);
+            writeln!(out, "    {:>9} requests: [{:.2}%, {:.2}%], ±{:.2} points",
+                          requests, low * 100.0, high * 100.0, (high - low) * 50.0)?;
+        }
+    }
+    Ok(())
+}
+
+/// Draws `samples` values from Poisson(λ) and sets their histogram beside
+/// the PMF, so the distribution the simulation relies on can be seen
+fn poisson_sampling(out: &mut dyn Write, lambda: f64, samples: usize) -> io::Result<()> {
+    let mut rng = system_design::XorShiftRng::seed_from_u64(42);
+    let draws: Vec<f64> = (0..samples).map(|_| stats::sample_poisson(&mut rng, lambda) as f64).collect();
+    let mean = draws.iter().sum::<f64>() / samples as f64;
+    let variance = draws.iter().map(|k| (k - mean).powi(2)).sum::<f64>() / samples as f64;
+    writeln!(out, "   → Sampled: {} draws with λ = {} - mean {:.3}, variance {:.3} (both should be λ)",
+                  samples, lambda, mean, variance)?;
+    // One bucket per k; anything past 3λ is rare enough to count as an outlier
+    let buckets = (3.0 * lambda).ceil() as usize;
+    let mut histogram = stats::Histogram::new(0.0, buckets as f64, buckets);
+    draws.iter().for_each(|&k| histogram.record(k));
+    let rendered = histogram.render(30);
+    for (k, line) in rendered.lines().enumerate() {
+        if k < buckets {
+            let expected = stats::poisson_pmf(lambda, k as u64) * samples as f64;
+            writeln!(out, "     {}  (Poisson: {:.0})", line, expected)?;
+        } else {
+            writeln!(out, "     {}", line)?;
+        }
+    }
+    writeln!(out)
+}
+
+/// Lessons from the CloudFlare incident, the verdict, and the decision tree
+pub fn cloudflare_lessons(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "LESSONS FROM THE CLOUDFLARE INCIDENT")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "1. LANGUAGE: Rust gave them Result<T,E> and Option<T>")?;
+    writeln!(out, "   → The tools for safety were available\n")?;
+    
+    writeln!(out, "2. DESIGN: They chose .unwrap() in critical paths")?;
+    writeln!(out, "   → Converted recoverable errors into unrecoverable panics\n")?;
+    
+    writeln!(out, "3. STATISTICS: Given λ (failure rate) and time, failures are inevitable")?;
+    writeln!(out, "   → Poisson distribution: P(k events) = (λ^k * e^-λ) / k!")?;
+    writeln!(out, "   → P(at least one failure) = 1 - P(0) = 1 - e^-λt:\n")?;
+    writeln!(out, "     λ per hour   hours      λt   P(≥1 failure)")?;
+    for (rate, hours) in [(0.001, 1.0), (0.001, 24.0), (0.001, 720.0), (0.01, 720.0), (0.01, 8760.0)] {
+        let expected = stats::expected_failures(rate, hours);
+        let at_least_one = 1.0 - stats::poisson_cdf(expected, 0);
+        writeln!(out, "   {:>12} {:>7} {:>7.3}   {:>12.1}%", rate, hours, expected, at_least_one * 100.0)?;
+    }
+    writeln!(out, "   → Run long enough and \"it will never fail\" stops being true\n")?;
+    poisson_sampling(out, 4.0, 100_000)?;
+    
+    writeln!(out, "4. TESTING: Runtime behavior differs from test environments")?;
+    writeln!(out, "   → Edge cases, load patterns, and timing create unique failure modes\n")?;
+    
+    writeln!(out, "5. RESPONSIBILITY: The bug was in the design, not the language")?;
+    writeln!(out, "   → unwrap() is like unsafe{{}} - use sparingly and with intention\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "{}", paint("THE VERDICT", Color::Bold))?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "{} It's like blaming:", paint("Rust is NOT to blame.", Color::Bold))?;
+    writeln!(out, "  • Assembly for allowing direct memory access")?;
+    writeln!(out, "  • C for having pointers")?;
+    writeln!(out, "  • SQL for allowing DROP TABLE\n")?;
+    
+    writeln!(out, "These are TOOLS. Power comes with responsibility.")?;
+    writeln!(out, "unwrap() says: 'I know this will never fail.'")?;
+    writeln!(out, "But in distributed systems with Poisson-distributed failures,")?;
+    writeln!(out, "'never' is a dangerous assumption.\n")?;
+    
+    writeln!(out, "{}", paint("✓ Use Result<T,E> and propagate errors with ?", Color::Green))?;
+    writeln!(out, "{}", paint("✓ Design for graceful degradation", Color::Green))?;
+    writeln!(out, "{}", paint("✓ Remember: Runtime IS test copy - plan for the unexpected", Color::Green))?;
+    writeln!(out, "{}\n", paint("✓ Respect the statistics: λt failures will occur over time t", Color::Green))?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "THE DECISION TREE: What To Do Instead of unwrap()")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    for context in advice::CallContext::ALL {
+        writeln!(out, "{}:", context.name())?;
+        for kind in advice::ErrorKind::ALL {
+            let recommendation = advice::recommend(kind, context);
+            writeln!(out, "  {:<24} → {}", kind.name(), recommendation.pattern.name())?;
+        }
+        writeln!(out)?;
+    }
+    
+    let cloudflare = advice::recommend(advice::ErrorKind::InvalidInput, advice::CallContext::RequestPath);
+    writeln!(out, "The CloudFlare case - a malformed input in the request path:")?;
+    writeln!(out, "  {}", cloudflare)?;
+    writeln!(out, "  Not: unwrap() and take the process down with it.\n")?;
+    Ok(())
+}
+
+/// Numbered incident entries: what happened, the lesson, and today's answer
+/// With `run_demos`, each incident that has a reproduction runs it
+fn write_incidents(out: &mut dyn Write, incidents: &[&Incident], run_demos: bool) -> io::Result<()> {
+    for (n, incident) in incidents.iter().enumerate() {
+        writeln!(out, "{}. {} ({}): {}", n + 1, incident.name, incident.years(), incident.root_cause)?;
+        writeln!(out, "   Lesson: {}", incident.lesson)?;
+        writeln!(out, "   → Today: {}", incident.mitigation)?;
+        match incident.demo {
+            Some(demo) if run_demos => {
+                writeln!(out, "   → Run it:")?;
+                let outcome = demo(out)?;
+                writeln!(out, "   {}", paint(format!("✓ {}", outcome), Color::Green))?;
+            }
+            Some(_) => writeln!(out, "   → Run it: unwrap lessons history --run-demos")?,
+            None => {}
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// The history lesson, optionally narrowed to one category and with the
+/// incidents' reproductions run
+pub fn history(out: &mut dyn Write, category: Option<FailureCategory>, run_demos: bool) -> io::Result<()> {
+    match category {
+        Some(category) => {
+            writeln!(out, "=== Famous failures: {} ===\n", category)?;
+            write_incidents(out, &incidents::by_category(category).collect::<Vec<_>>(), run_demos)
+        }
+        None => write_history(out, run_demos),
+    }
+}
+
+/// Learning from failure: historical incidents and the defensive mindset
+pub fn learning_from_failure(out: &mut dyn Write) -> io::Result<()> {
+    write_history(out, false)
+}
+
+fn write_history(out: &mut dyn Write, run_demos: bool) -> io::Result<()> {
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "LEARNING FROM FAILURE: The Low-Level Developer's Mindset")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "History teaches us: We learn more from failure than success.")?;
+    writeln!(out, "Success is passive - things work, we move on.")?;
+    writeln!(out, "Failure FORCES attention - we must understand why.\n")?;
+    
+    writeln!(out, "LOW-LEVEL DEVELOPERS: Never Have a Positive Bias\n")?;
+    
+    writeln!(out, "The Optimistic Developer:")?;
+    writeln!(out, "  • \"This network call will succeed\"")?;
+    writeln!(out, "  • \"This pointer is valid\"")?;
+    writeln!(out, "  • \"This allocation won't fail\"")?;
+    writeln!(out, "  • \"This parse will work\"")?;
+    writeln!(out, "  Result: .unwrap() everywhere → Production crashes\n")?;
+    
+    writeln!(out, "The Defensive Developer:")?;
+    writeln!(out, "  • \"What if the network is down?\"")?;
+    writeln!(out, "  • \"What if this pointer is null?\"")?;
+    writeln!(out, "  • \"What if we're out of memory?\"")?;
+    writeln!(out, "  • \"What if this input is malformed?\"")?;
+    writeln!(out, "  Result: Result<T,E> everywhere → Graceful degradation\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "HISTORICAL LESSONS: Famous Failures That Taught Us")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    write_incidents(out, &incidents::timeline(), run_demos)?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "THE DEFENSIVE PROGRAMMING MINDSET")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "Every .unwrap() is an assertion: 'This CANNOT fail.'")?;
+    writeln!(out, "But at the bit level, EVERYTHING can fail:\n")?;
+    
+    writeln!(out, "  • Cosmic rays can flip bits (soft errors)")?;
+    writeln!(out, "  • Hardware can malfunction")?;
+    writeln!(out, "  • Networks partition")?;
+    writeln!(out, "  • Disks fill up")?;
+    writeln!(out, "  • Memory exhausts")?;
+    writeln!(out, "  • Race conditions emerge")?;
+    writeln!(out, "  • Edge cases appear in production\n")?;
+    
+    writeln!(out, "The low-level developer EXPECTS failure.")?;
+    writeln!(out, "This isn't pessimism - it's realism based on history.\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "WHY FAILURE TEACHES MORE THAN SUCCESS")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "Success: Code works → Move to next task")?;
+    writeln!(out, "  Learning: Minimal (confirmation bias)\n")?;
+    
+    writeln!(out, "Failure: Code crashes → MUST investigate")?;
+    writeln!(out, "  Learning: Maximum (forced attention)")?;
+    writeln!(out, "  • What assumptions were wrong?")?;
+    writeln!(out, "  • What edge cases exist?")?;
+    writeln!(out, "  • What invariants were violated?")?;
+    writeln!(out, "  • How do we prevent this class of errors?\n")?;
+    
+    writeln!(out, "Every production failure is a gift:")?;
+    writeln!(out, "  It reveals the gap between our mental model")?;
+    writeln!(out, "  and the actual behavior of the system.\n")?;
+    
+    writeln!(out, "The unwrap() that works 99.9% of the time?")?;
+    writeln!(out, "That's DANGEROUS. It teaches you nothing.")?;
+    writeln!(out, "The 0.1% failure? That's your teacher.\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "CONCLUSION: The Path Forward")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "1. NEVER trust the happy path")?;
+    writeln!(out, "2. ALWAYS model failure explicitly (Result<T,E>)")?;
+    writeln!(out, "3. LEARN from each failure in production")?;
+    writeln!(out, "4. RESPECT the lessons history has taught us")?;
+    writeln!(out, "5. MAINTAIN a healthy skepticism about success\n")?;
+    
+    writeln!(out, "As Dijkstra said: 'Testing shows the presence of bugs,")?;
+    writeln!(out, "not their absence.' Production IS the ultimate test.\n")?;
+    
+    writeln!(out, "The best developers aren't those who write code that works.")?;
+    writeln!(out, "They're those who write code that fails gracefully")?;
+    writeln!(out, "when the inevitable happens.\n")?;
+    Ok(())
+}
+
+/// Trivial engines (S3, matrices) vs real systems that can fail
+pub fn trivial_vs_real(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "TRIVIAL ENGINES vs REAL SYSTEMS")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "ENGINES THAT DON'T FAIL ARE TRIVIAL\n")?;
+    
+    writeln!(out, "Mathematical/Deterministic Systems (TRIVIAL):")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "  1. Ruler and Compass Construction")?;
+    writeln!(out, "     • Euclidean geometry - perfect, deterministic")?;
+    writeln!(out, "     • Cannot fail given valid inputs")?;
+    writeln!(out, "     • Closed mathematical system\n")?;
+    
+    writeln!(out, "  2. Matrix Multiplication")?;
+    writeln!(out, "     • A × B always defined if dimensions match")?;
+    writeln!(out, "     • Pure mathematics, no external dependencies")?;
+    writeln!(out, "     • Result is deterministic and exact\n")?;
+    
+    writeln!(out, "  3. Slide Rules / log base n")?;
+    writeln!(out, "     • Mechanical computation via logarithms")?;
+    writeln!(out, "     • Deterministic transformation")?;
+    writeln!(out, "     • No failure modes (in pure form)\n")?;
+    
+    writeln!(out, "  4. S3 (Symmetric Group on 3 elements)")?;
+    writeln!(out, "     • Only 6 permutations under composition")?;
+    writeln!(out, "     • Closed group: e, (12), (13), (23), (123), (132)")?;
+    writeln!(out, "     • Every composition yields one of these 6")?;
+    writeln!(out, "     • Perfectly deterministic, no failure states\n")?;
+    
+    writeln!(out, "These systems are CLOSED and PURE:")?;
+    writeln!(out, "  → No I/O")?;
+    writeln!(out, "  → No resource constraints")?;
+    writeln!(out, "  → No timing dependencies")?;
+    writeln!(out, "  → No external state")?;
+    writeln!(out, "  → Perfect mathematical abstraction\n")?;
+    
+    writeln!(out, "Real Computational Systems (NON-TRIVIAL):")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "  1. Network I/O")?;
+    writeln!(out, "     • Can timeout, drop packets, partition")?;
+    writeln!(out, "     • Must handle: Result<Response, NetworkError>\n")?;
+    
+    writeln!(out, "  2. File System")?;
+    writeln!(out, "     • Disk can be full, file can be locked")?;
+    writeln!(out, "     • Must handle: Result<File, IoError>\n")?;
+    
+    writeln!(out, "  3. Memory Allocation")?;
+    writeln!(out, "     • System can be out of memory")?;
+    writeln!(out, "     • Must handle: Option<*mut T> or Result\n")?;
+    
+    writeln!(out, "  4. Parsing User Input")?;
+    writeln!(out, "     • Input can be malformed, truncated, malicious")?;
+    writeln!(out, "     • Must handle: Result<T, ParseError>\n")?;
+    
+    writeln!(out, "  5. Concurrent Operations")?;
+    writeln!(out, "     • Race conditions, deadlocks, livelocks")?;
+    writeln!(out, "     • Must handle: locks, channels, atomics\n")?;
+    
+    writeln!(out, "These systems are OPEN and IMPURE:")?;
+    writeln!(out, "  → Interact with external world")?;
+    writeln!(out, "  → Limited resources (memory, disk, network)")?;
+    writeln!(out, "  → Timing-dependent behavior")?;
+    writeln!(out, "  → Shared mutable state")?;
+    writeln!(out, "  → Failure is INEVITABLE\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "THE FUNDAMENTAL DIFFERENCE")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "TRIVIAL ENGINES:")?;
+    writeln!(out, "  Matrix A × Matrix B → Matrix C")?;
+    writeln!(out, "  (Always succeeds if dimensions match)\n")?;
+    
+    writeln!(out, "  fn multiply(a: Matrix, b: Matrix) -> Matrix {{")?;
+    writeln!(out, "      // Pure function, cannot fail")?;
+    writeln!(out, "      // No need for Result<T,E>")?;
+    writeln!(out, "  }}\n")?;
+    
+    writeln!(out, "REAL SYSTEMS:")?;
+    writeln!(out, "  HTTP Request → ??? (success, timeout, 404, 500, ...)")?;
+    writeln!(out, "  (Many failure modes, timing-dependent)\n")?;
+    
+    writeln!(out, "  fn fetch(url: &str) -> Result<Response, Error> {{")?;
+    writeln!(out, "      // Impure function, MUST handle failure")?;
+    writeln!(out, "      // Using .unwrap() here is negligent")?;
+    writeln!(out, "  }}\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "WHY unwrap() IS DANGEROUS")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "Using .unwrap() pretends a REAL system is TRIVIAL:")?;
+    writeln!(out, "  • You're asserting: 'This cannot fail'")?;
+    writeln!(out, "  • Reality: It's interacting with the messy world")?;
+    writeln!(out, "  • Result: System crashes when reality intrudes\n")?;
+    
+    writeln!(out, "It's like pretending:")?;
+    writeln!(out, "  • Your network is S3 (always one of 6 perfect states)")?;
+    writeln!(out, "  • Your disk is a slide rule (deterministic, no failures)")?;
+    writeln!(out, "  • Your parser is matrix multiplication (always succeeds)\n")?;
+    
+    writeln!(out, "But they're NOT. They're complex, open systems with:")?;
+    writeln!(out, "  • Unbounded state spaces")?;
+    writeln!(out, "  • External dependencies")?;
+    writeln!(out, "  • Resource constraints")?;
+    writeln!(out, "  • Probabilistic behavior\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "THE LESSON")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "If your system interacts with:")?;
+    writeln!(out, "  ✓ I/O (files, network, hardware)")?;
+    writeln!(out, "  ✓ User input")?;
+    writeln!(out, "  ✓ Shared resources")?;
+    writeln!(out, "  ✓ Time-dependent behavior")?;
+    writeln!(out, "  ✓ External services\n")?;
+    
+    writeln!(out, "Then it's NOT TRIVIAL. It can and will fail.")?;
+    writeln!(out, "Don't use .unwrap(). Use Result<T,E>.\n")?;
+    
+    writeln!(out, "The mathematical abstraction is beautiful:")?;
+    writeln!(out, "  S3 has exactly 6 elements under composition.")?;
+    writeln!(out, "  Matrix multiplication is deterministic.\n")?;
+    
+    writeln!(out, "But production systems aren't mathematical abstractions.")?;
+    writeln!(out, "They're NON-TRIVIAL ENGINES operating in a failure-rich environment.\n")?;
+    
+    writeln!(out, "Respect the difference. Handle the failures.")?;
+    writeln!(out, "That's what separates toy code from production systems.\n")?;
+    Ok(())
+}
+
+/// Gödel's incompleteness and the limits of proving software correct
+pub fn godel(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "GÖDEL'S INCOMPLETENESS: The Impossibility of Perfect Systems")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "HILBERT'S DREAM (Early 1900s):")?;
+    writeln!(out, "  Sought a perfect synthesis of Mathematics and Logic")?;
+    writeln!(out, "  Goals:")?;
+    writeln!(out, "    1. Completeness: Every true statement is provable")?;
+    writeln!(out, "    2. Consistency: No contradictions can be derived")?;
+    writeln!(out, "    3. Decidability: Mechanical procedure to prove/disprove any statement\n")?;
+    
+    writeln!(out, "  Hilbert believed: Mathematics could be perfectly formalized")?;
+    writeln!(out, "  A complete, consistent, decidable system for all of math\n")?;
+    
+    writeln!(out, "GÖDEL'S ANSWER (1931):")?;
+    writeln!(out, "  First Incompleteness Theorem:")?;
+    writeln!(out, "    'Any consistent formal system F sufficient for arithmetic")?;
+    writeln!(out, "     contains statements that are TRUE but UNPROVABLE in F.'\n")?;
+    
+    writeln!(out, "  Second Incompleteness Theorem:")?;
+    writeln!(out, "    'No consistent system can prove its own consistency.'\n")?;
+    
+    writeln!(out, "  Translation: Systems of sufficient complexity")?;
+    writeln!(out, "  DON'T HAVE THE MACHINERY TO PROVE THEMSELVES.\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "THE PARALLEL TO SOFTWARE SYSTEMS")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "Hilbert's Dream          →  The Optimistic Developer")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "  Perfect formalization  →  'My code is correct'")?;
+    writeln!(out, "  Complete proofs        →  'Tests prove correctness'")?;
+    writeln!(out, "  No contradictions      →  'No bugs possible'")?;
+    writeln!(out, "  Decidable              →  'Static analysis finds all issues'\n")?;
+    
+    writeln!(out, "Gödel's Reality          →  The Defensive Developer")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "  Incompleteness         →  'Some failures are unpredictable'")?;
+    writeln!(out, "  Unprovable truths      →  'Cannot test all paths'")?;
+    writeln!(out, "  Can't self-prove       →  'System can't validate itself'")?;
+    writeln!(out, "  Inherent limits        →  'Must handle unknown failures'\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "IMPLICATIONS FOR SOFTWARE")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "1. TESTING IS INSUFFICIENT")?;
+    writeln!(out, "   Just as Gödel showed true statements exist that can't be proven,")?;
+    writeln!(out, "   bug-free execution paths exist that can't be tested.\n")?;
+    
+    writeln!(out, "   • Test coverage: 100% → Still bugs in production")?;
+    writeln!(out, "   • Formal verification: Proves properties → Can't prove ALL properties")?;
+    writeln!(out, "   • Static analysis: Finds issues → Halting problem limits completeness\n")?;
+    
+    writeln!(out, "2. SELF-VALIDATION IS IMPOSSIBLE")?;
+    writeln!(out, "   A complex system cannot prove its own correctness.")?;
+    writeln!(out, "   You need external validation, monitoring, and graceful degradation.\n")?;
+    
+    writeln!(out, "   • .unwrap() assumes self-validation: 'This WILL work'")?;
+    writeln!(out, "   • Result<T,E> admits limits: 'This MIGHT fail'\n")?;
+    
+    writeln!(out, "3. COMPLEXITY BREEDS UNPROVABILITY")?;
+    writeln!(out, "   Simple systems (S3, matrix multiplication): Provably correct")?;
+    writeln!(out, "   Complex systems (distributed services): Inherently unprovable\n")?;
+    
+    writeln!(out, "   As Gödel showed: Sufficient complexity → Incompleteness")?;
+    writeln!(out, "   In software: Sufficient complexity → Inevitable bugs\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "THE FUNDAMENTAL LIMITS")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "Gödel (1931): 'Formal systems can't prove their consistency'")?;
+    writeln!(out, "Turing (1936): 'Halting problem is undecidable'")?;
+    writeln!(out, "Dijkstra (1970s): 'Testing shows presence, not absence of bugs'")?;
+    writeln!(out, "Rice (1953): 'Non-trivial program properties are undecidable'\n")?;
+    
+    writeln!(out, "These aren't engineering limitations - they're MATHEMATICAL IMPOSSIBILITIES.\n")?;
+    
+    writeln!(out, "You cannot:")?;
+    writeln!(out, "  ✗ Prove a complex system has no bugs")?;
+    writeln!(out, "  ✗ Test all possible execution paths")?;
+    writeln!(out, "  ✗ Guarantee a program will terminate")?;
+    writeln!(out, "  ✗ Decide if two programs are equivalent\n")?;
+    
+    writeln!(out, "Therefore, you MUST:")?;
+    writeln!(out, "  ✓ Design for failure")?;
+    writeln!(out, "  ✓ Use Result<T,E> to make failures explicit")?;
+    writeln!(out, "  ✓ Implement graceful degradation")?;
+    writeln!(out, "  ✓ Monitor and adapt at runtime\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "WHY .unwrap() VIOLATES GÖDEL'S LESSON")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "Using .unwrap() is claiming:")?;
+    writeln!(out, "  'I have proven this cannot fail.'\n")?;
+    
+    writeln!(out, "But Gödel proved:")?;
+    writeln!(out, "  Complex systems cannot prove themselves.\n")?;
+    
+    writeln!(out, "You're asserting completeness and consistency")?;
+    writeln!(out, "in a system that MATHEMATICALLY cannot have both.\n")?;
+    
+    writeln!(out, "This is why production systems fail:")?;
+    writeln!(out, "  • The developer assumes provable correctness")?;
+    writeln!(out, "  • Gödel guarantees unprovable cases exist")?;
+    writeln!(out, "  • .unwrap() hits an unprovable case")?;
+    writeln!(out, "  • System crashes\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "THE HUMBLE PATH FORWARD")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "Hilbert sought perfection. Gödel showed its impossibility.")?;
+    writeln!(out, "Similarly:")?;
+    writeln!(out, "  Developers seek bug-free code.")?;
+    writeln!(out, "  Reality shows it's mathematically impossible.\n")?;
+    
+    writeln!(out, "The solution isn't to give up - it's to be HUMBLE:")?;
+    writeln!(out, "  • Acknowledge limits of provability")?;
+    writeln!(out, "  • Design systems that tolerate unknown failures")?;
+    writeln!(out, "  • Use Result<T,E> to admit fallibility")?;
+    writeln!(out, "  • Accept that runtime will reveal what testing cannot\n")?;
+    
+    writeln!(out, "Gödel didn't end mathematics - he made it more honest.")?;
+    writeln!(out, "We shouldn't end software development - make it more honest.\n")?;
+    
+    writeln!(out, "Stop pretending you can prove correctness with .unwrap().")?;
+    writeln!(out, "Start admitting fallibility with Result<T,E>.\n")?;
+    
+    writeln!(out, "That's the lesson of incompleteness:")?;
+    writeln!(out, "  Perfection is impossible.")?;
+    writeln!(out, "  Graceful handling of imperfection is mandatory.\n")?;
+    Ok(())
+}
+
+/// The biological analogue: DNA replication and protein synthesis
+pub fn biology(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "THE BIOLOGICAL ANALOGUE: DNA → Protein Synthesis")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "The pattern repeats in nature:\n")?;
+    
+    writeln!(out, "DNA REPLICATION")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "  Purpose: Copy genetic information perfectly")?;
+    writeln!(out, "  Reality: ~1 error per 10^9 base pairs (even with proofreading)")?;
+    writeln!(out, "  Result: Mutations propagate to daughter cells\n")?;
+    
+    writeln!(out, "PROTEIN SYNTHESIS")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "  DNA → mRNA (transcription)")?;
+    writeln!(out, "    • RNA polymerase can misread DNA template")?;
+    writeln!(out, "    • ~1 error per 10^4 to 10^5 nucleotides")?;
+    writeln!(out, "  mRNA → Protein (translation)")?;
+    writeln!(out, "    • Ribosome can incorporate wrong amino acid")?;
+    writeln!(out, "    • ~1 error per 10^4 amino acids\n")?;
+    
+    writeln!(out, "THE PARADOX:")?;
+    writeln!(out, "  The system CODES FOR error detection and correction")?;
+    writeln!(out, "  • DNA polymerase has 3' → 5' exonuclease (proofreading)")?;
+    writeln!(out, "  • Mismatch repair systems (MMR)")?;
+    writeln!(out, "  • Base excision repair (BER)")?;
+    writeln!(out, "  • Nucleotide excision repair (NER)\n")?;
+    
+    writeln!(out, "  Yet the system ITSELF can carry errors in those very mechanisms:")?;
+    writeln!(out, "  • Mutation in DNA polymerase → worse proofreading")?;
+    writeln!(out, "  • Mutation in MMR genes → cancer (Lynch syndrome)")?;
+    writeln!(out, "  • Error in error-correction creates compound errors\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "THE PARALLEL TO SOFTWARE")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "Biology               →  Software")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "DNA template          →  Source code")?;
+    writeln!(out, "Replication           →  Compilation/deployment")?;
+    writeln!(out, "Transcription errors  →  Build errors")?;
+    writeln!(out, "Translation errors    →  Runtime errors")?;
+    writeln!(out, "Proofreading enzymes  →  Type checkers, linters")?;
+    writeln!(out, "Repair systems        →  Error handling (Result<T,E>)")?;
+    writeln!(out, "Mutations             →  Bugs\n")?;
+    
+    writeln!(out, "THE SELF-REFERENTIAL PROBLEM:")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "In Biology:")?;
+    writeln!(out, "  The DNA that codes for error-correction enzymes")?;
+    writeln!(out, "  can itself contain errors in those very genes.")?;
+    writeln!(out, "  → The system meant to prevent errors propagates them\n")?;
+    
+    writeln!(out, "In Software:")?;
+    writeln!(out, "  fn validate_input(s: &str) -> Result<Data, Error> {{")?;
+    writeln!(out, "      s.parse().unwrap() // BUG in validation code!")?;
+    writeln!(out, "  }}\n")?;
+    
+    writeln!(out, "  The error-handling code ITSELF contains .unwrap()")?;
+    writeln!(out, "  → The system meant to prevent crashes causes them\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "WHY PERFECT FIDELITY IS IMPOSSIBLE")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "1. THERMODYNAMICS")?;
+    writeln!(out, "   At molecular level: thermal fluctuations cause errors")?;
+    writeln!(out, "   At computational level: cosmic rays flip bits, hardware fails\n")?;
+    
+    writeln!(out, "2. COMPLEXITY COST")?;
+    writeln!(out, "   Biology: More proofreading = slower replication")?;
+    writeln!(out, "   Software: More validation = slower execution")?;
+    writeln!(out, "   Trade-off between speed and accuracy\n")?;
+    
+    writeln!(out, "3. SELF-REFERENCE")?;
+    writeln!(out, "   Biology: Error-correction genes can themselves mutate")?;
+    writeln!(out, "   Software: Error-handling code can itself have bugs")?;
+    writeln!(out, "   The corrector needs correction (infinite regress)\n")?;
+    
+    writeln!(out, "4. EVOLUTION REQUIRES ERRORS")?;
+    writeln!(out, "   Biology: Zero mutations = no adaptation = extinction")?;
+    writeln!(out, "   Software: Zero changes = no features = obsolescence")?;
+    writeln!(out, "   Some error rate is NECESSARY for survival\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "THE CASCADE: From Molecule to System")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "DNA error → Wrong amino acid → Misfolded protein → Non-functional enzyme")?;
+    writeln!(out, "  → Metabolic failure → Cell death → Tissue damage → Organ failure\n")?;
+    
+    writeln!(out, "Code error → Wrong type → .unwrap() panic → Thread crash")?;
+    writeln!(out, "  → Service unavailable → Request timeout → System cascade → Outage\n")?;
+    
+    writeln!(out, "Both systems exhibit:")?;
+    writeln!(out, "  • Local errors → Global failures")?;
+    writeln!(out, "  • Self-referential vulnerability")?;
+    writeln!(out, "  • Impossible perfect fidelity")?;
+    writeln!(out, "  • Trade-offs between speed and accuracy\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "THE BIOLOGICAL LESSON FOR SOFTWARE")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "Life doesn't prevent errors - it MANAGES them:")?;
+    writeln!(out, "  • Multiple repair pathways (redundancy)")?;
+    writeln!(out, "  • Apoptosis (graceful cell death instead of cancer)")?;
+    writeln!(out, "  • Immune system (runtime monitoring)")?;
+    writeln!(out, "  • Genetic diversity (fault tolerance through variation)\n")?;
+    
+    writeln!(out, "Software should do the same:")?;
+    writeln!(out, "  • Multiple error paths (Result<T,E>, not unwrap())")?;
+    writeln!(out, "  • Graceful degradation (fallback, not crash)")?;
+    writeln!(out, "  • Monitoring & observability (detect failures)")?;
+    writeln!(out, "  • Redundancy (replicas, not single points of failure)\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "THE UNIVERSAL PRINCIPLE")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "From molecules to mathematics to machines:")?;
+    writeln!(out, "  SYSTEMS THAT REPLICATE INFORMATION CARRY FORWARD ERRORS\n")?;
+    
+    writeln!(out, "  • DNA → Mutations propagate")?;
+    writeln!(out, "  • Gödel → Unprovable statements exist")?;
+    writeln!(out, "  • Software → Bugs persist\n")?;
+    
+    writeln!(out, "The common thread:")?;
+    writeln!(out, "  Any system complex enough to be interesting")?;
+    writeln!(out, "  is complex enough to fail in unpredictable ways.\n")?;
+    
+    writeln!(out, "The solution isn't perfection (impossible).")?;
+    writeln!(out, "The solution is ROBUSTNESS in the face of imperfection:\n")?;
+    
+    writeln!(out, "  Biology: Repair, redundancy, apoptosis, evolution")?;
+    writeln!(out, "  Mathematics: Acknowledge incompleteness, external validation")?;
+    writeln!(out, "  Software: Result<T,E>, monitoring, graceful degradation\n")?;
+    
+    writeln!(out, "Life has been solving this problem for 3.5 billion years.")?;
+    writeln!(out, "The lesson: Don't try to prevent all errors.")?;
+    writeln!(out, "Instead: Build systems that SURVIVE errors.\n")?;
+    
+    writeln!(out, "That's what .unwrap() misses and Result<T,E> embraces.")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    Ok(())
+}
+
+/// Why 'solving cancer' is more Gödel than Hilbert
+pub fn cancer(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "WHY 'SOLVING CANCER' IS MORE GÖDEL THAN HILBERT")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "HILBERT'S APPROACH: Complete, Perfect Solution")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "  'Solve cancer' implies:")?;
+    writeln!(out, "    • One universal cure")?;
+    writeln!(out, "    • Complete understanding")?;
+    writeln!(out, "    • Provably effective treatment")?;
+    writeln!(out, "    • No edge cases")?;
+    writeln!(out, "    • Problem eliminated forever\n")?;
+    
+    writeln!(out, "GÖDEL'S REALITY: Incompleteness Is Inevitable")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "  Cancer is fundamentally GÖDELIAN because:\n")?;
+    
+    writeln!(out, "  1. SELF-REFERENTIAL SYSTEM")?;
+    writeln!(out, "     • Cancer cells are YOUR cells")?;
+    writeln!(out, "     • They use the same machinery as healthy cells")?;
+    writeln!(out, "     • Immune system must distinguish self from self-gone-wrong")?;
+    writeln!(out, "     • Like Gödel's statement about itself: unprovable within system\n")?;
+    
+    writeln!(out, "  2. ERROR IN ERROR-CORRECTION")?;
+    writeln!(out, "     • Cancer often starts with mutations in DNA repair genes")?;
+    writeln!(out, "     • p53 (guardian of genome) itself gets mutated")?;
+    writeln!(out, "     • Mismatch repair (MMR) defects → Lynch syndrome")?;
+    writeln!(out, "     • The corrector is corrupted → compound errors\n")?;
+    
+    writeln!(out, "  3. EVOLUTIONARY ARMS RACE")?;
+    writeln!(out, "     • Give drug → cancer evolves resistance")?;
+    writeln!(out, "     • Target pathway → cancer finds alternate route")?;
+    writeln!(out, "     • Kill 99.9% of cells → 0.1% with resistance survive")?;
+    writeln!(out, "     • System adapts faster than we can intervene\n")?;
+    
+    writeln!(out, "  4. MOVING TARGET")?;
+    writeln!(out, "     • Not one disease, but ~200+ different cancers")?;
+    writeln!(out, "     • Each tumor is genetically unique")?;
+    writeln!(out, "     • Heterogeneity WITHIN a single tumor")?;
+    writeln!(out, "     • Trying to 'solve' all cancers = trying to prove all theorems\n")?;
+    
+    writeln!(out, "  5. ESSENTIAL PROCESSES HIJACKED")?;
+    writeln!(out, "     • Cell division (necessary for life)")?;
+    writeln!(out, "     • Growth signaling (necessary for healing)")?;
+    writeln!(out, "     • Angiogenesis (necessary for tissue)")?;
+    writeln!(out, "     • Can't eliminate without eliminating life itself\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "THE PARALLEL TO GÖDEL'S THEOREMS")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "Gödel's First Theorem:")?;
+    writeln!(out, "  'Any consistent system has true unprovable statements'\n")?;
+    
+    writeln!(out, "Cancer Translation:")?;
+    writeln!(out, "  'Any living system can develop cancers that evade treatment'")?;
+    writeln!(out, "  • New mutation pathways emerge")?;
+    writeln!(out, "  • Resistance mechanisms we haven't discovered")?;
+    writeln!(out, "  • Edge cases beyond current medical understanding\n")?;
+    
+    writeln!(out, "Gödel's Second Theorem:")?;
+    writeln!(out, "  'No system can prove its own consistency'\n")?;
+    
+    writeln!(out, "Cancer Translation:")?;
+    writeln!(out, "  'No organism can guarantee its cells won't become cancerous'")?;
+    writeln!(out, "  • You can't prove your DNA repair is perfect")?;
+    writeln!(out, "  • Repair mechanisms themselves can fail")?;
+    writeln!(out, "  • Self-validation is impossible\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "WHY 'CURE' MAY BE THE WRONG FRAMING")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "Hilbert wanted: Complete solution (impossible)")?;
+    writeln!(out, "Gödel showed: Manage incompleteness (realistic)\n")?;
+    
+    writeln!(out, "Similarly:")?;
+    writeln!(out, "  WRONG GOAL: 'Cure cancer' (eliminate forever)")?;
+    writeln!(out, "  • Assumes static target")?;
+    writeln!(out, "  • Assumes complete knowledge")?;
+    writeln!(out, "  • Assumes one solution works for all cases")?;
+    writeln!(out, "  • Like claiming .unwrap() will never fail\n")?;
+    
+    writeln!(out, "  RIGHT GOAL: 'Manage cancer' (survive with it)")?;
+    writeln!(out, "  • Treat as chronic disease")?;
+    writeln!(out, "  • Adaptive therapy (evolve with the cancer)")?;
+    writeln!(out, "  • Personalized medicine (handle each case)")?;
+    writeln!(out, "  • Monitor and adapt (runtime validation)")?;
+    writeln!(out, "  • Like using Result<T,E> instead of unwrap()\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "EXAMPLES OF THE GÖDELIAN NATURE")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "1. GLEEVEC (Imatinib) for CML")?;
+    writeln!(out, "   • Miracle drug, targeted therapy")?;
+    writeln!(out, "   • Initially: ~90% remission")?;
+    writeln!(out, "   • Over time: resistance mutations emerge")?;
+    writeln!(out, "   • T315I mutation makes it ineffective")?;
+    writeln!(out, "   • Unprovable statement: 'This will always work'\n")?;
+    
+    writeln!(out, "2. IMMUNE CHECKPOINT INHIBITORS")?;
+    writeln!(out, "   • Unleash immune system against cancer")?;
+    writeln!(out, "   • Works brilliantly for ~20-40% of patients")?;
+    writeln!(out, "   • Others: no response or autoimmune damage")?;
+    writeln!(out, "   • Can't prove who will respond")?;
+    writeln!(out, "   • Edge cases remain unpredictable\n")?;
+    
+    writeln!(out, "3. TUMOR HETEROGENEITY")?;
+    writeln!(out, "   • Single tumor has multiple clones")?;
+    writeln!(out, "   • Kill clone A → clone B takes over")?;
+    writeln!(out, "   • Like whack-a-mole with evolution")?;
+    writeln!(out, "   • No single 'proof' kills all variants\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "THE ADAPTIVE STRATEGY (Gödel's Way)")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "Since perfect cure is impossible, we adapt:\n")?;
+    
+    writeln!(out, "  • Liquid biopsies (runtime monitoring)")?;
+    writeln!(out, "  • Adaptive therapy (adjust based on feedback)")?;
+    writeln!(out, "  • Combination treatments (multiple error paths)")?;
+    writeln!(out, "  • Evolutionary pressure management (don't eradicate fully)")?;
+    writeln!(out, "  • Synthetic lethality (exploit specific weaknesses)\n")?;
+    
+    writeln!(out, "This is like software engineering:")?;
+    writeln!(out, "  • Not trying to prove code is bug-free")?;
+    writeln!(out, "  • Instead: monitor, adapt, gracefully degrade")?;
+    writeln!(out, "  • Result<T,E> acknowledges failure modes")?;
+    writeln!(out, "  • Runtime validation over static proof\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "THE LESSON")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "Some problems are FUNDAMENTALLY GÖDELIAN:")?;
+    writeln!(out, "  • Systems that reference themselves")?;
+    writeln!(out, "  • Error-correction that can itself error")?;
+    writeln!(out, "  • Moving targets that evolve")?;
+    writeln!(out, "  • Complexity beyond complete formalization\n")?;
+    
+    writeln!(out, "For these problems:")?;
+    writeln!(out, "  ✗ Don't seek Hilbert's perfect solution")?;
+    writeln!(out, "  ✓ Accept Gödel's incompleteness")?;
+    writeln!(out, "  ✓ Build adaptive, resilient responses")?;
+    writeln!(out, "  ✓ Manage, don't eliminate\n")?;
+    
+    writeln!(out, "Cancer is more Gödel than Hilbert.")?;
+    writeln!(out, "So is software reliability.")?;
+    writeln!(out, "So is any complex system with self-reference.\n")?;
+    
+    writeln!(out, "The humility to accept incompleteness")?;
+    writeln!(out, "is the wisdom to build systems that survive it.\n")?;
+    Ok(())
+}
+
+/// Epilogue: the strange loop of this program itself
+pub fn epilogue(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "EPILOGUE: The Philosophical Protein We Just Built")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "You're reading the output of a program that demonstrates")?;
+    writeln!(out, "error propagation in code, DNA, mathematics, and medicine.\n")?;
+    
+    writeln!(out, "But there's a deeper loop:\n")?;
+    
+    writeln!(out, "THIS PROGRAM ITSELF IS THE DEMONSTRATION")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "⏰ THE CLOCK → IDEA → REPO CONNECTION\n")?;
+    
+    writeln!(out, "  [Human Thought]")?;
+    writeln!(out, "      ↓")?;
+    writeln!(out, "  'unwrap() causes problems to unwrap themselves'")?;
+    writeln!(out, "      ↓")?;
+    writeln!(out, "  [AI Processing] ← Man + Machine collaboration")?;
+    writeln!(out, "      ↓")?;
+    writeln!(out, "  Simple demo → CloudFlare → Poisson → History")?;
+    writeln!(out, "      ↓")?;
+    writeln!(out, "  Gödel → DNA → Cancer → Strange loops")?;
+    writeln!(out, "      ↓")?;
+    writeln!(out, "  [Code Synthesis]")?;
+    writeln!(out, "      ↓")?;
+    writeln!(out, "  Git commits (information replication)")?;
+    writeln!(out, "      ↓")?;
+    writeln!(out, "  GitHub repository (error propagation across network)")?;
+    writeln!(out, "      ↓")?;
+    writeln!(out, "  [You, reading this]")?;
+    writeln!(out, "      ↓")?;
+    writeln!(out, "  Ideas replicate in YOUR mind")?;
+    writeln!(out, "      ↓")?;
+    writeln!(out, "  The cycle continues...\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "WE BUILT A PHILOSOPHICAL PROTEIN")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "In 30 minutes, human and AI collaborated to:")?;
+    writeln!(out, "  • Start with amino acids (basic ideas)")?;
+    writeln!(out, "  • Form peptide chains (connected concepts)")?;
+    writeln!(out, "  • Build secondary structure (individual sections)")?;
+    writeln!(out, "  • Fold into tertiary structure (coherent philosophy)")?;
+    writeln!(out, "  • Create functional protein (working demonstration)\n")?;
+    
+    writeln!(out, "Primary Structure:")?;
+    writeln!(out, "  Raw code: unwrap() examples, error handling patterns\n")?;
+    
+    writeln!(out, "Secondary Structure:")?;
+    writeln!(out, "  CloudFlare analysis, Poisson distributions,")?;
+    writeln!(out, "  Historical failures, Trivial vs Non-trivial systems\n")?;
+    
+    writeln!(out, "Tertiary Structure:")?;
+    writeln!(out, "  Gödel's incompleteness → DNA replication → Cancer")?;
+    writeln!(out, "  All folded together by self-reference\n")?;
+    
+    writeln!(out, "Quaternary Structure:")?;
+    writeln!(out, "  This running program + GitHub repo + your reading")?;
+    writeln!(out, "  = Complete functional system\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "THE IRONY IS PERFECT")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "This repository demonstrates:")?;
+    writeln!(out, "  • Information replication carries errors forward")?;
+    writeln!(out, "  • Systems that code for error-correction can err")?;
+    writeln!(out, "  • Complex systems cannot prove themselves\n")?;
+    
+    writeln!(out, "This repository IS:")?;
+    writeln!(out, "  • Information replicating (git commits)")?;
+    writeln!(out, "  • Built by systems that make errors (human + AI)")?;
+    writeln!(out, "  • Cannot prove its own completeness (could always add more)\n")?;
+    
+    writeln!(out, "The program ABOUT strange loops")?;
+    writeln!(out, "is ITSELF a strange loop:\n")?;
+    
+    writeln!(out, "  ┌─ Code describes error propagation")?;
+    writeln!(out, "  │")?;
+    writeln!(out, "  ├─ Code was created through idea propagation")?;
+    writeln!(out, "  │")?;
+    writeln!(out, "  ├─ Code replicates via git (with potential errors)")?;
+    writeln!(out, "  │")?;
+    writeln!(out, "  ├─ Code teaches: 'Systems can't prove themselves'")?;
+    writeln!(out, "  │")?;
+    writeln!(out, "  └─ Code cannot prove IT itself is complete or correct ───┐")?;
+    writeln!(out, "                                                            │")?;
+    writeln!(out, "  ┌─────────────────────────────────────────────────────────┘")?;
+    writeln!(out, "  └─ Yet this incompleteness IS the point it makes!\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "MAN + MACHINE: The Collaborative Synthesis")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "Human brought:")?;
+    writeln!(out, "  • Initial insight ('unwrap() is ironic')")?;
+    writeln!(out, "  • Philosophical connections (Gödel, DNA, Cancer)")?;
+    writeln!(out, "  • 'No positive bias' principle")?;
+    writeln!(out, "  • 'Runtime is test copy' wisdom")?;
+    writeln!(out, "  • Recognition of S3 as trivial system\n")?;
+    
+    writeln!(out, "AI brought:")?;
+    writeln!(out, "  • Code synthesis")?;
+    writeln!(out, "  • Pattern matching across domains")?;
+    writeln!(out, "  • Structured explanation")?;
+    writeln!(out, "  • Historical examples")?;
+    writeln!(out, "  • Formal implementation\n")?;
+    
+    writeln!(out, "Together, we folded ideas into actionable code.")?;
+    writeln!(out, "A biological process (thought) became digital (program).\n")?;
+    
+    writeln!(out, "This is EXACTLY like DNA → Protein:")?;
+    writeln!(out, "  • Information (ideas) encoded")?;
+    writeln!(out, "  • Transcription (human → AI understanding)")?;
+    writeln!(out, "  • Translation (concepts → code)")?;
+    writeln!(out, "  • Folding (structure emerges)")?;
+    writeln!(out, "  • Function (program runs, teaches)")?;
+    writeln!(out, "  • Replication (git, GitHub, your mind)\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "THE TIMESTAMP")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "From first unwrap() to this epilogue: ~30 minutes")?;
+    writeln!(out, "Three git commits, each building on the last")?;
+    writeln!(out, "Ideas evolved, mutated, complexified\n")?;
+    
+    writeln!(out, "Commit 1: 'Initial commit: unwrap-philosophy'")?;
+    writeln!(out, "  └─ Basic structure, CloudFlare, Gödel\n")?;
+    
+    writeln!(out, "Commit 2: 'Add biological analogue'")?;
+    writeln!(out, "  └─ DNA replication, error propagation\n")?;
+    
+    writeln!(out, "Commit 3: 'Why solving cancer is more Gödel than Hilbert'")?;
+    writeln!(out, "  └─ Medical application, adaptive therapy\n")?;
+    
+    writeln!(out, "Commit 4: (You're in it now)")?;
+    writeln!(out, "  └─ Meta-awareness, the strange loop closes\n")?;
+    
+    writeln!(out, "Each commit: Information replication")?;
+    writeln!(out, "Each build: Potential for compiler errors")?;
+    writeln!(out, "Each run: Runtime behavior we can't fully predict")?;
+    writeln!(out, "Each reader: New interpretation, new mutations\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+    writeln!(out, "THE FINAL LOOP")?;
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    
+    writeln!(out, "This program teaches:")?;
+    writeln!(out, "  'Don't use .unwrap() - acknowledge fallibility with Result<T,E>'\n")?;
+    
+    writeln!(out, "This program embodies:")?;
+    writeln!(out, "  'We didn't use .unwrap() in our thinking'")?;
+    writeln!(out, "  'We acknowledged: ideas might be incomplete'")?;
+    writeln!(out, "  'We built: adaptive structure that evolved'")?;
+    writeln!(out, "  'We accepted: this explanation itself is incomplete'\n")?;
+    
+    writeln!(out, "And now, YOU:")?;
+    writeln!(out, "  • Are reading output from a system that can't prove itself")?;
+    writeln!(out, "  • Written by collaboration that can't guarantee correctness")?;
+    writeln!(out, "  • About problems that are fundamentally unprovable")?;
+    writeln!(out, "  • Which demonstrates the very limits it describes\n")?;
+    
+    writeln!(out, "The strange loop is complete:")?;
+    writeln!(out, "  The function trying to unwrap uncertainty")?;
+    writeln!(out, "  Created an uncertain program")?;
+    writeln!(out, "  That explains uncertainty")?;
+    writeln!(out, "  Which proves you can't eliminate uncertainty")?;
+    writeln!(out, "  Which is exactly what .unwrap() fails to accept.\n")?;
+    
+    writeln!(out, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")?;
+    writeln!(out, "Thank you for running this program.")?;
+    writeln!(out, "May your errors be explicit, your systems resilient,")?;
+    writeln!(out, "and your appreciation for incompleteness complete.\n")?;
+    
+    writeln!(out, "                    — Human & AI")?;
+    writeln!(out, "                      November 20, 2025")?;
+    writeln!(out, "                      30 minutes of collaborative folding")?;
+    writeln!(out, "                      ∞ strange loops\n")?;
+    Ok(())
+}
+
+/// A self-contained part of the demo that can be listed and run on its own
+pub trait Lesson {
+    /// Short name for `unwrap lessons <id>`
+    fn id(&self) -> &'static str;
+    fn title(&self) -> &'static str;
+    fn run(&self, out: &mut dyn Write) -> io::Result<()>;
+    
+    /// The lesson as a Markdown document, converted from what `run` writes
+    fn render_markdown(&self) -> String {
+        let mut text = Vec::new();
+        // Writing to a Vec<u8> can't fail; a lesson's own failures are in its text
+        let _ = self.run(&mut text);
+        markdown::from_text(self.title(), &String::from_utf8_lossy(&text))
+    }
+}
+
+/// A lesson backed by one of this module's functions
+#[derive(Debug, Clone, Copy)]
+pub struct Section {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub run: fn(&mut dyn Write) -> io::Result<()>,
+}
+
+impl Lesson for Section {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+    
+    fn title(&self) -> &'static str {
+        self.title
+    }
+    
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        (self.run)(out)
+    }
+}
+
+/// Every section of the demo, in the order the full demo runs them
+pub const SECTIONS: [Section; 10] = [
+    Section { id: "examples", title: "The unwrap() failure examples and their fixes", run: examples },
+    Section { id: "cascade", title: "The cascade effect and better approaches", run: cascade },
+    Section { id: "design", title: "Is Rust to blame? Four designs under load", run: design_question },
+    Section { id: "cloudflare", title: "Lessons from the CloudFlare incident", run: cloudflare_lessons },
+    Section { id: "history", title: "Famous failures that taught us", run: learning_from_failure },
+    Section { id: "trivial", title: "Trivial engines vs real systems that can fail", run: trivial_vs_real },
+    Section { id: "godel", title: "Gödel and the limits of proving software correct", run: godel },
+    Section { id: "biology", title: "The biological analogue: DNA replication", run: biology },
+    Section { id: "cancer", title: "Why 'solving cancer' is more Gödel than Hilbert", run: cancer },
+    Section { id: "epilogue", title: "The strange loop of this program itself", run: epilogue },
+];
+
+/// The registry: every lesson, in demo order
+pub fn all_lessons() -> Vec<Box<dyn Lesson>> {
+    SECTIONS.iter().map(|&section| Box::new(section) as Box<dyn Lesson>).collect()
+}
+
+/// Looks up a lesson by id
+pub fn lesson(id: &str) -> Option<Box<dyn Lesson>> {
+    all_lessons().into_iter().find(|lesson| lesson.id() == id)
+}
+
+/// What the audience wants at a pause
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pace {
+    Continue,
+    Skip, // the lesson about to start
+    Quit,
+}
+
+/// Decides, before each lesson, whether to run it
+pub trait Pacer {
+    fn pause(&mut self, out: &mut dyn Write, next: &dyn Lesson) -> io::Result<Pace>;
+}
+
+/// Never stops: the whole demo in one go
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoPause;
+
+impl Pacer for NoPause {
+    fn pause(&mut self, _out: &mut dyn Write, _next: &dyn Lesson) -> io::Result<Pace> {
+        Ok(Pace::Continue)
+    }
+}
+
+/// Waits for a line of input at every pause: Enter continues, `s` skips
+/// the next lesson, `q` quits. End of input (Ctrl-D) quits too.
+#[derive(Debug)]
+pub struct LinePacer<R> {
+    input: R,
+}
+
+impl<R: io::BufRead> LinePacer<R> {
+    pub fn new(input: R) -> Self {
+        LinePacer { input }
+    }
+}
+
+impl<R: io::BufRead> Pacer for LinePacer<R> {
+    fn pause(&mut self, out: &mut dyn Write, next: &dyn Lesson) -> io::Result<Pace> {
+        loop {
+            write!(out, "── Next: {} [Enter to continue, s to skip, q to quit] ", next.title())?;
+            out.flush()?;
+            let mut line = Vec::new();
+            if self.input.read_until(b'\n', &mut line)? == 0 {
+                writeln!(out)?;
+                return Ok(Pace::Quit);
+            }
+            writeln!(out)?;
+            match String::from_utf8_lossy(&line).trim() {
+                "" => return Ok(Pace::Continue),
+                "s" | "S" => return Ok(Pace::Skip),
+                "q" | "Q" => return Ok(Pace::Quit),
+                _ => {} // anything else: ask again
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn every_lesson_writes_utf8_text() {
+        let lessons = all_lessons();
+        assert_eq!(lessons.len(), SECTIONS.len());
+        for lesson in &lessons {
+            let mut out = Vec::new();
+            lesson.run(&mut out).unwrap();
+            let text = String::from_utf8(out).unwrap_or_else(|e| panic!("{}: {}", lesson.id(), e));
+            assert!(!text.trim().is_empty(), "{} wrote nothing", lesson.id());
+            assert_eq!(self::lesson(lesson.id()).map(|found| found.title()), Some(lesson.title()));
+        }
+        let mut ids: Vec<_> = lessons.iter().map(|lesson| lesson.id()).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), lessons.len(), "lesson ids must be unique");
+        assert!(self::lesson("no-such-lesson").is_none());
+    }
+    
+    #[test]
+    fn lesson_output_is_captured_whole() {
+        let capture = |id| {
+            let mut out = Vec::new();
+            self::lesson(id).map(|lesson| lesson.run(&mut out)).transpose().unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        let godel = capture("godel");
+        assert!(godel.starts_with("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n\
+                                   GÖDEL'S INCOMPLETENESS: The Impossibility of Perfect Systems\n"), "{}", godel);
+        // Same text every run, so a golden file can pin it down
+        assert_eq!(capture("godel"), godel);
+        assert_eq!(capture("epilogue"), capture("epilogue"));
+        
+        let mut examples = Vec::new();
+        super::examples(&mut examples).unwrap();
+        let examples = String::from_utf8(examples).unwrap();
+        assert!(examples.contains("the failure went to on_error, no panic: audit log"), "{}", examples);
+    }
+    
+    #[test]
+    fn line_pacer_reads_keypresses() {
+        let next = &SECTIONS[0];
+        let mut pacer = LinePacer::new("\n  s \nwhat?\nQ\n".as_bytes());
+        let mut out = Vec::new();
+        assert_eq!(pacer.pause(&mut out, next).unwrap(), Pace::Continue);
+        assert_eq!(pacer.pause(&mut out, next).unwrap(), Pace::Skip);
+        assert_eq!(pacer.pause(&mut out, next).unwrap(), Pace::Quit); // asks again after "what?"
+        assert_eq!(pacer.pause(&mut out, next).unwrap(), Pace::Quit); // Ctrl-D: end of input
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches("── Next: The unwrap() failure examples").count(), 5);
+    }
+    
+    #[test]
+    fn paced_run_skips_and_quits_between_lessons() {
+        let mut pacer = LinePacer::new("s\ns\nq\n".as_bytes());
+        let mut out = Vec::new();
+        run_paced(&mut out, &mut pacer).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(&format!("(skipped: {})", SECTIONS[0].title)), "{}", out);
+        assert!(out.contains(&format!("(skipped: {})", SECTIONS[1].title)), "{}", out);
+        assert!(out.contains(&format!("── Next: {}", SECTIONS[2].title)), "{}", out);
+        assert!(!out.contains(SECTIONS[3].title), "quit should stop before the next lesson");
+        
+        // EOF at the first pause quits cleanly, with nothing run
+        let mut out = Vec::new();
+        run_paced(&mut out, &mut LinePacer::new(io::empty())).unwrap();
+        assert!(!String::from_utf8(out).unwrap().contains("skipped"));
+    }
+    
+    #[test]
+    fn quiet_simulation_writes_nothing_but_reports_everything() {
+        use system_design::{Design, SimConfig, Verbosity};
+        
+        let mut out = Vec::new();
+        let reports = simulate(&mut out, &Design::ALL, Some(299), Format::Text, Some(Verbosity::Quiet)).unwrap();
+        assert!(out.is_empty(), "{}", String::from_utf8_lossy(&out));
+        let requests = system_design::default_requests();
+        for (report, design) in reports.iter().zip(Design::ALL) {
+            let expected = system_design::simulate_production_load_seeded(design, &requests, 299);
+            assert_eq!(report.outcomes, expected.outcomes);
+            assert_eq!(report.availability, expected.availability);
+        }
+        
+        let config = SimConfig { requests: 500, failure_rate: 0.1, seed: Some(299), ..SimConfig::default() };
+        let reports = simulate_generated(&mut out, &[Design::Safe], &config, Format::Text, Some(Verbosity::Quiet)).unwrap();
+        assert!(out.is_empty(), "{}", String::from_utf8_lossy(&out));
+        assert_eq!(reports[0].outcomes.len(), 500);
+        assert_eq!(reports[0].outcomes, system_design::simulate_with_config(Design::Safe, &config).outcomes);
+        
+        // Past the per-request limit the default is the summary
+        assert_eq!(Verbosity::default_for(50), Verbosity::PerRequest);
+        assert_eq!(Verbosity::default_for(51), Verbosity::Summary);
+    }
+    
+    #[test]
+    fn monte_carlo_intervals_pool_up_to_every_run() {
+        use system_design::{Design, SimConfig};
+        
+        let config = SimConfig { requests: 20, failure_rate: 0.1, seed: Some(321), ..SimConfig::default() };
+        let sizes = |iterations| {
+            let mut out = Vec::new();
+            monte_carlo(&mut out, &[Design::Safe], &config, iterations, Format::Text).unwrap();
+            String::from_utf8(out).unwrap().lines()
+                .filter_map(|line| line.trim().strip_suffix(" points")?.split(" requests:").next()?.parse().ok())
+                .collect::<Vec<usize>>()
+        };
+        assert_eq!(sizes(25), [20, 200, 500]);
+        assert_eq!(sizes(100), [20, 200, 2000]);
+        assert_eq!(sizes(1), [20]);
+    }
+}
@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use crate::better_approaches::UserDirectory;
+
+/// Example 1: Simple unwrap that panics
+pub fn divide(a: i32, b: i32) -> Option<i32> {
+    if b == 0 {
+        None
+    } else {
+        Some(a / b) // Still panics for i32::MIN / -1 - the overflow case
+    }
+}
+
+/// Example 2: Chained unwraps - the cascade effect
+pub fn parse_and_double(s: &str) -> i32 {
+    let num: i32 = s.parse().unwrap(); // First unwrap - can panic on invalid string
+    let doubled = divide(num, 2).unwrap(); // Second unwrap - can panic on logic error
+    doubled * 2
+}
+
+/// Example 3: File operations with unwrap
+pub fn read_config_file(path: &str) -> String {
+    let mut file = File::open(path).unwrap(); // Panics if file doesn't exist
+    let mut contents = String::new();
+    file.read_to_string(&mut contents).unwrap(); // Panics if read fails
+    contents
+}
+
+/// Example 4: Nested structure access with unwrap
+pub fn get_nested_value(data: Option<Option<Option<i32>>>) -> i32 {
+    data.unwrap()      // First layer
+        .unwrap()      // Second layer
+        .unwrap()      // Third layer - any None causes panic
+}
+
+/// Example 5: Array indexing equivalent
+pub fn get_element<T: Clone>(slice: &[T], index: usize) -> T {
+    slice.get(index).unwrap().clone() // Panics on out-of-bounds
+}
+
+/// Example 6: Unfinished code paths with todo!()
+pub fn export_report(format: &str) -> String {
+    match format {
+        "text" => String::from("Report: 5 successful, 2 failed"),
+        _ => todo!(), // "We'll get to it" - still a panic in production
+    }
+}
+
+/// Example 7: Option can't say WHY a value is missing
+#[allow(deprecated)]
+pub fn greet_user(directory: &UserDirectory, id: u32) -> String {
+    // None could mean "no such user" or "directory is down" - unwrap treats both as impossible
+    let user = directory.find(id).unwrap();
+    format!("Hello, {}!", user.name)
+}
+
+/// Example 9: Map lookup - the most common unwrap() in code review
+pub fn get_user_age(map: &HashMap<String, u32>, name: &str) -> u32 {
+    *map.get(name).unwrap() // Panics if the name isn't a key
+}
+
+/// Example 10: A worker thread panics while holding the lock, poisoning it
+pub fn poison_while_holding(counter: &Mutex<u32>) {
+    std::thread::scope(|scope| {
+        let worker = scope.spawn(|| {
+            let mut count = counter.lock().unwrap();
+            *count += 1;
+            panic!("worker failed mid-update"); // the guard drops during unwinding
+        });
+        let _ = worker.join(); // the worker's panic stays in the worker
+    });
+}
+
+/// ...and every later lock().unwrap() panics too: the cascade crosses threads
+pub fn read_counter(counter: &Mutex<u32>) -> u32 {
+    *counter.lock().unwrap()
+}
+
+/// Example 11: Configuration via environment - unset in every fresh shell
+pub fn env_setting(name: &str) -> String {
+    std::env::var(name).unwrap() // Panics if unset or not valid Unicode
+}
+
+/// Example 12: "Time only moves forward" - not for the wall clock
+pub fn elapsed_since(earlier: SystemTime) -> Duration {
+    SystemTime::now().duration_since(earlier).unwrap() // Panics if `earlier` is in the future
+}
+
+/// Example 13: Bytes off the network are not a String until checked
+pub fn decode_message(bytes: Vec<u8>) -> String {
+    String::from_utf8(bytes).unwrap() // Panics on the first invalid byte
+}
+
+/// Example 14: A worker sends one result per job; with `dies_after` it
+/// gives up early and its Sender is dropped with it
+pub fn start_worker(jobs: u32, dies_after: Option<u32>) -> Receiver<u32> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for job in 1..=jobs {
+            if dies_after.is_some_and(|limit| job > limit) {
+                return; // the worker died; tx goes with it
+            }
+            if tx.send(job * job).is_err() {
+                return; // nobody is listening any more
+            }
+        }
+    });
+    rx
+}
+
+/// The consumer trusts the worker to deliver every result
+pub fn sum_results(results: &Receiver<u32>, expected: u32) -> u32 {
+    (0..expected).map(|_| results.recv().unwrap()).sum() // Panics once the worker is gone
+}
+
+/// Example 15: A worker thread that crashes on a bad job
+pub fn spawn_worker(job: &'static str) -> std::thread::JoinHandle<usize> {
+    std::thread::spawn(move || {
+        let parsed: usize = job.parse().unwrap(); // The worker's own unwrap
+        parsed * 2
+    })
+}
+
+/// join().unwrap() re-raises the worker's panic in the parent
+pub fn run_job(job: &'static str) -> usize {
+    spawn_worker(job).join().unwrap()
+}
+
+/// Example 16: Aggregating a query result that came back empty
+pub fn highest_price_above(prices: &[u32], floor: u32) -> u32 {
+    *prices.iter().filter(|&&price| price > floor).max().unwrap() // Panics on no rows
+}
+
+/// Example 17: A length from a parsed header, narrowed to fit the frame format
+pub fn frame_length(header_length: u64) -> u32 {
+    u32::try_from(header_length).unwrap() // Panics above u32::MAX (4 GiB)
+}
+
+/// ...and sized for a buffer: fine on 64-bit targets, a panic on 32-bit ones
+pub fn buffer_length(header_length: u64) -> usize {
+    usize::try_from(header_length).unwrap()
+}
+
+/// Example 18: The file parsed, so surely the field is there and valid
+pub fn get_port(config: &HashMap<String, String>) -> u16 {
+    config.get("port").unwrap().parse().unwrap() // Missing key, or not a number
+}
+
+/// Example 19: A guard that flushes its buffer when dropped - and unwraps
+/// the flush. With `panic_on_drop`, a failed flush panics inside Drop
+pub struct NoisyGuard {
+    name: &'static str,
+    panic_on_drop: bool,
+}
+
+impl NoisyGuard {
+    pub fn new(name: &'static str, panic_on_drop: bool) -> Self {
+        NoisyGuard { name, panic_on_drop }
+    }
+    
+    /// Always fails: the disk filled up while the guard was held
+    pub fn flush(&self) -> io::Result<()> {
+        Err(io::Error::other(format!("{}: no space left to flush", self.name)))
+    }
+}
+
+impl Drop for NoisyGuard {
+    fn drop(&mut self) {
+        if self.panic_on_drop {
+            self.flush().unwrap(); // During an unwind this is a second panic: abort
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// A file of its own under the temp dir, removed when dropped
+    struct TempFile(std::path::PathBuf);
+    
+    impl TempFile {
+        fn new(name: &str, contents: &[u8]) -> Self {
+            let path = std::env::temp_dir().join(format!("unwrap-examples-{}-{}", std::process::id(), name));
+            std::fs::write(&path, contents).unwrap();
+            TempFile(path)
+        }
+        
+        fn path(&self) -> &str {
+            self.0.to_str().unwrap()
+        }
+    }
+    
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+    
+    #[test]
+    fn divide_returns_none_for_zero() {
+        assert_eq!(divide(10, 2), Some(5));
+        assert_eq!(divide(-7, 2), Some(-3));
+        assert_eq!(divide(1, 0), None);
+    }
+    
+    #[test]
+    fn happy_paths_return_the_value() {
+        assert_eq!(parse_and_double("42"), 42);
+        assert_eq!(parse_and_double("21"), 20); // halved, then doubled: odd numbers round down
+        assert_eq!(get_nested_value(Some(Some(Some(7)))), 7);
+        assert_eq!(get_element(&[1, 2, 3], 2), 3);
+        let config = TempFile::new("config", b"port = 8080\n");
+        assert_eq!(read_config_file(config.path()), "port = 8080\n");
+    }
+    
+    #[cfg(panic = "unwind")]
+    #[test]
+    #[should_panic(expected = "ParseIntError")]
+    fn parse_and_double_panics_on_a_non_number() {
+        parse_and_double("abc");
+    }
+    
+    #[cfg(panic = "unwind")]
+    #[test]
+    #[should_panic(expected = "NotFound")]
+    fn read_config_file_panics_on_a_missing_file() {
+        read_config_file("/nonexistent/unwrap-examples/config.toml");
+    }
+    
+    #[cfg(panic = "unwind")]
+    #[test]
+    #[should_panic(expected = "InvalidData")]
+    fn read_config_file_panics_on_invalid_utf8() {
+        let config = TempFile::new("binary", &[0xFF, 0xFE]);
+        read_config_file(config.path());
+    }
+    
+    #[cfg(panic = "unwind")]
+    #[test]
+    fn get_nested_value_panics_on_any_missing_layer() {
+        for data in [None, Some(None), Some(Some(None))] {
+            assert!(std::panic::catch_unwind(|| get_nested_value(data)).is_err(), "{:?}", data);
+        }
+    }
+    
+    #[cfg(panic = "unwind")]
+    #[test]
+    #[should_panic(expected = "on a `None` value")]
+    fn get_element_panics_out_of_bounds() {
+        get_element(&[1, 2, 3], 3);
+    }
+    
+    #[cfg(panic = "unwind")]
+    #[test]
+    fn drop_panic_is_caught_when_nothing_else_is_unwinding() {
+        use crate::better_approaches::defer_fallible;
+        use crate::panics::{capture, CaptureError};
+        
+        // The only panic in flight, so catch_unwind gets it like any other
+        match capture(|| drop(NoisyGuard::new("log buffer", true))) {
+            Err(CaptureError::Panicked(report)) => {
+                assert!(report.message.starts_with("called `Result::unwrap()` on an `Err` value"), "{}", report);
+                assert!(report.message.contains("log buffer: no space left to flush"), "{}", report);
+            }
+            other => panic!("expected the drop to panic, got {:?}", other),
+        }
+        assert_eq!(capture(|| drop(NoisyGuard::new("audit log", false))), Ok(()));
+        
+        // The same flush deferred: its error goes to the log, and nothing panics
+        let guard = NoisyGuard::new("audit log", false);
+        let log = std::cell::RefCell::new(Vec::new());
+        let deferred = std::panic::AssertUnwindSafe(|| {
+            drop(defer_fallible(|| guard.flush(), |e| log.borrow_mut().push(e.to_string())));
+        });
+        assert_eq!(capture(deferred), Ok(()));
+        assert_eq!(*log.borrow(), ["audit log: no space left to flush"]);
+    }
+}
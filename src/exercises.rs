@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::num::ParseIntError;
+use std::panic::UnwindSafe;
+use std::path::Path;
+
+use crate::panics::{self, CaptureError, PanicReport};
+
+// Fix-the-unwrap challenges. Each exercise_* function below ships broken:
+// it unwraps, and the harness feeds it hostile input. Edit the body so it
+// returns the error (or None) instead, then run `cargo run -- exercises`.
+// The signatures stay as they are - the harness calls them directly.
+
+/// Settings by section, then by key, as in an INI file
+pub type Sections = HashMap<String, HashMap<String, String>>;
+
+/// Exercise 1: a port number typed by a user
+pub fn exercise_1_parse_port(input: &str) -> Result<u16, ParseIntError> {
+    Ok(input.trim().parse().unwrap())
+}
+
+/// Exercise 2: a file that may not be there
+pub fn exercise_2_read_file(path: &Path) -> io::Result<String> {
+    Ok(fs::read_to_string(path).unwrap())
+}
+
+/// Exercise 3: a setting two maps deep, either of which may lack the key
+pub fn exercise_3_nested_lookup(
+    config: &Sections,
+    section: &str,
+    key: &str,
+) -> Option<String> {
+    Some(config.get(section).unwrap().get(key).unwrap().clone())
+}
+
+// Reference solutions - the harness passes these. Try first, then compare.
+
+pub fn solution_1_parse_port(input: &str) -> Result<u16, ParseIntError> {
+    input.trim().parse()
+}
+
+pub fn solution_2_read_file(path: &Path) -> io::Result<String> {
+    fs::read_to_string(path)
+}
+
+pub fn solution_3_nested_lookup(
+    config: &Sections,
+    section: &str,
+    key: &str,
+) -> Option<String> {
+    config.get(section)?.get(key).cloned()
+}
+
+/// What the harness found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExerciseResult {
+    Passed,
+    Panicked { input: String, report: PanicReport },
+    Unchecked, // a panic = "abort" build can't feed it hostile input and live
+    WrongAnswer { input: String, expected: String, got: String },
+}
+
+impl ExerciseResult {
+    pub fn passed(&self) -> bool {
+        *self == ExerciseResult::Passed
+    }
+}
+
+impl fmt::Display for ExerciseResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExerciseResult::Passed => write!(f, "passed"),
+            ExerciseResult::Panicked { input, report } => write!(f, "on {}, {}", input, report),
+            ExerciseResult::Unchecked => write!(f, "not checked: this build aborts on panic"),
+            ExerciseResult::WrongAnswer { input, expected, got } => {
+                write!(f, "on {} expected {}, got {}", input, expected, got)
+            }
+        }
+    }
+}
+
+/// One challenge: the function to fix and how to check it
+#[derive(Debug, Clone, Copy)]
+pub struct Exercise {
+    pub id: usize,
+    pub name: &'static str,
+    pub task: &'static str,
+    check: fn() -> ExerciseResult,
+}
+
+impl Exercise {
+    pub fn check(&self) -> ExerciseResult {
+        (self.check)()
+    }
+}
+
+pub const EXERCISES: [Exercise; 3] = [
+    Exercise {
+        id: 1,
+        name: "exercise_1_parse_port",
+        task: "return the parse error instead of unwrapping it",
+        check: || check_parse_port(exercise_1_parse_port),
+    },
+    Exercise {
+        id: 2,
+        name: "exercise_2_read_file",
+        task: "return the io::Error for a missing file",
+        check: || check_read_file(exercise_2_read_file),
+    },
+    Exercise {
+        id: 3,
+        name: "exercise_3_nested_lookup",
+        task: "return None when the section or the key is missing",
+        check: || check_nested_lookup(exercise_3_nested_lookup),
+    },
+];
+
+/// Checks one exercise by its number; None if there is no such exercise
+pub fn check_exercise(id: usize) -> Option<ExerciseResult> {
+    EXERCISES.iter().find(|exercise| exercise.id == id).map(Exercise::check)
+}
+
+/// Calls `f`, turning a panic into the result that reports it
+fn attempt<T>(input: &str, f: impl FnOnce() -> T + UnwindSafe) -> Result<T, ExerciseResult> {
+    panics::without_panic_messages(|| panics::capture(f)).map_err(|e| match e {
+        CaptureError::Panicked(report) => ExerciseResult::Panicked { input: input.to_string(), report },
+        CaptureError::Unsupported => ExerciseResult::Unchecked,
+    })
+}
+
+/// Valid ports parse, surrounding whitespace included; anything else is an error
+pub fn check_parse_port(parse_port: fn(&str) -> Result<u16, ParseIntError>) -> ExerciseResult {
+    let cases = [("8080", Some(8080)), (" 443\n", Some(443)), ("", None), ("http", None), ("70000", None)];
+    for (input, expected) in cases {
+        let label = format!("{:?}", input);
+        let got = match attempt(&label, || parse_port(input)) {
+            Ok(got) => got.ok(),
+            Err(panicked) => return panicked,
+        };
+        if got != expected {
+            return ExerciseResult::WrongAnswer {
+                input: label,
+                expected: expected.map_or_else(|| String::from("an error"), |port| format!("Ok({})", port)),
+                got: got.map_or_else(|| String::from("an error"), |port| format!("Ok({})", port)),
+            };
+        }
+    }
+    ExerciseResult::Passed
+}
+
+/// A file that exists is read; a missing one is io::ErrorKind::NotFound
+pub fn check_read_file(read_file: fn(&Path) -> io::Result<String>) -> ExerciseResult {
+    let dir = std::env::temp_dir();
+    let present = dir.join(format!("unwrap-exercise-{}.txt", std::process::id()));
+    let missing = dir.join(format!("unwrap-exercise-{}-missing.txt", std::process::id()));
+    // If the temp dir isn't writable only the missing-file case can be checked
+    if fs::write(&present, "port = 8080\n").is_ok() {
+        let read_present = attempt("an existing file", || read_file(&present));
+        let _ = fs::remove_file(&present);
+        match read_present {
+            Ok(Ok(contents)) if contents == "port = 8080\n" => {}
+            Ok(got) => {
+                return ExerciseResult::WrongAnswer {
+                    input: String::from("an existing file"),
+                    expected: String::from("its contents"),
+                    got: format!("{:?}", got),
+                }
+            }
+            Err(panicked) => return panicked,
+        }
+    }
+    
+    match attempt("a missing file", || read_file(&missing)) {
+        Ok(Err(e)) if e.kind() == io::ErrorKind::NotFound => ExerciseResult::Passed,
+        Ok(got) => ExerciseResult::WrongAnswer {
+            input: String::from("a missing file"),
+            expected: String::from("a NotFound error"),
+            got: format!("{:?}", got),
+        },
+        Err(panicked) => panicked,
+    }
+}
+
+/// A present setting is found; a missing section or key is None
+pub fn check_nested_lookup(
+    lookup: fn(&Sections, &str, &str) -> Option<String>,
+) -> ExerciseResult {
+    let config: Sections = HashMap::from([(
+        String::from("server"),
+        HashMap::from([(String::from("port"), String::from("8080"))]),
+    )]);
+    let cases = [
+        ("server", "port", Some("8080")),
+        ("server", "host", None),
+        ("database", "port", None),
+    ];
+    for (section, key, expected) in cases {
+        let label = format!("[{}] {}", section, key);
+        let got = match attempt(&label, || lookup(&config, section, key)) {
+            Ok(got) => got,
+            Err(panicked) => return panicked,
+        };
+        if got.as_deref() != expected {
+            return ExerciseResult::WrongAnswer {
+                input: label,
+                expected: format!("{:?}", expected),
+                got: format!("{:?}", got),
+            };
+        }
+    }
+    ExerciseResult::Passed
+}
+
+/// Checks every exercise and writes a pass/fail line for each
+/// Returns how many passed
+pub fn write_scoreboard(out: &mut dyn Write) -> io::Result<usize> {
+    writeln!(out, "=== Exercises: Fix the unwrap() ===")?;
+    let mut passed = 0;
+    for exercise in &EXERCISES {
+        let result = exercise.check();
+        if result.passed() {
+            passed += 1;
+            writeln!(out, "✓ {}. {} - passed", exercise.id, exercise.name)?;
+        } else {
+            writeln!(out, "✗ {}. {} - {}", exercise.id, exercise.name, result)?;
+            writeln!(out, "     Task: {}", exercise.task)?;
+        }
+    }
+    writeln!(out, "\nScore: {}/{} passed", passed, EXERCISES.len())?;
+    if passed < EXERCISES.len() {
+        writeln!(out, "Edit the functions in src/exercises.rs and run `cargo run -- exercises` again.")?;
+    }
+    Ok(passed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    // One test, so the read-file checks never share their temp file
+    #[test]
+    fn shipped_exercises_fail_and_solutions_pass() {
+        assert!(check_parse_port(solution_1_parse_port).passed());
+        assert!(check_read_file(solution_2_read_file).passed());
+        assert!(check_nested_lookup(solution_3_nested_lookup).passed());
+        
+        for exercise in &EXERCISES {
+            let result = exercise.check();
+            if panics::UNWINDS {
+                assert!(matches!(result, ExerciseResult::Panicked { .. }), "{}: {}", exercise.name, result);
+            } else {
+                assert_eq!(result, ExerciseResult::Unchecked, "{}", exercise.name);
+            }
+        }
+        assert_eq!(check_exercise(4), None);
+        
+        let mut out = Vec::new();
+        assert_eq!(write_scoreboard(&mut out).unwrap(), 0);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Score: 0/3 passed"), "{}", out);
+    }
+    
+    #[test]
+    fn a_wrong_answer_is_not_a_pass() {
+        let always_80: fn(&str) -> Result<u16, ParseIntError> = |_| Ok(80);
+        match check_parse_port(always_80) {
+            ExerciseResult::WrongAnswer { input, expected, got } => {
+                assert_eq!((input.as_str(), expected.as_str(), got.as_str()), ("\"8080\"", "Ok(8080)", "Ok(80)"));
+            }
+            other => panic!("expected WrongAnswer, got {:?}", other),
+        }
+        let never: fn(&Sections, &str, &str) -> Option<String> = |_, _, _| None;
+        assert!(!check_nested_lookup(never).passed());
+    }
+}
@@ -0,0 +1,367 @@
+use std::fmt;
+use std::io::{self, Write};
+use std::ops::Add;
+
+use crate::better_approaches;
+use crate::panics;
+
+/// The kind of mistake behind an incident
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureCategory {
+    Race,         // two things happened in an order nobody planned for
+    Overflow,     // a value didn't fit its type
+    Units,        // the right number in the wrong unit
+    MemorySafety, // reading or writing past what was allocated
+    Timing,       // clocks drift, jump, and repeat seconds
+    Cascade,      // one component's failure took down its neighbours
+    Deployment,   // the code running wasn't the code intended
+    Unwrap,       // an assumed-present value wasn't there
+}
+
+impl FailureCategory {
+    pub const ALL: [FailureCategory; 8] = [
+        FailureCategory::Race,
+        FailureCategory::Overflow,
+        FailureCategory::Units,
+        FailureCategory::MemorySafety,
+        FailureCategory::Timing,
+        FailureCategory::Cascade,
+        FailureCategory::Deployment,
+        FailureCategory::Unwrap,
+    ];
+    
+    pub fn name(self) -> &'static str {
+        match self {
+            FailureCategory::Race => "Race",
+            FailureCategory::Overflow => "Overflow",
+            FailureCategory::Units => "Units",
+            FailureCategory::MemorySafety => "MemorySafety",
+            FailureCategory::Timing => "Timing",
+            FailureCategory::Cascade => "Cascade",
+            FailureCategory::Deployment => "Deployment",
+            FailureCategory::Unwrap => "Unwrap",
+        }
+    }
+    
+    /// Case-insensitive, so `--category overflow` works too
+    pub fn from_name(name: &str) -> Option<FailureCategory> {
+        FailureCategory::ALL.into_iter().find(|category| category.name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl fmt::Display for FailureCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// How a reproduction ended: the failure happened and was handled, or never could
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DemoOutcome {
+    Caught,    // at run time, as a value instead of a crash
+    Prevented, // at compile time: the types won't allow it
+}
+
+impl fmt::Display for DemoOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DemoOutcome::Caught => write!(f, "caught at run time - an error value, not a crash"),
+            DemoOutcome::Prevented => write!(f, "prevented by the types - it doesn't compile"),
+        }
+    }
+}
+
+/// A small reproduction of an incident, narrated to `out`
+pub type Demo = fn(&mut dyn Write) -> io::Result<DemoOutcome>;
+
+/// A failure worth learning from
+#[derive(Debug, Clone, Copy)]
+pub struct Incident {
+    pub name: &'static str,
+    pub year_range: (u16, Option<u16>), // first year, and the last if it went on
+    pub category: FailureCategory,
+    pub root_cause: &'static str,
+    pub lesson: &'static str,
+    pub mitigation: &'static str, // what we do about it today
+    pub demo: Option<Demo>,
+}
+
+impl Incident {
+    /// "1996", or "1985-1987" for an incident that spanned years
+    pub fn years(&self) -> String {
+        match self.year_range {
+            (start, Some(end)) if end != start => format!("{}-{}", start, end),
+            (start, _) => start.to_string(),
+        }
+    }
+}
+
+/// The built-in database, in no particular order - see `timeline`
+pub const INCIDENTS: [Incident; 10] = [
+    Incident {
+        name: "Ariane 5",
+        year_range: (1996, None),
+        category: FailureCategory::Overflow,
+        root_cause: "Integer overflow crashed a $370M rocket",
+        lesson: "Never assume values fit in their types",
+        mitigation: "Checked arithmetic, Result<T,E>",
+        demo: Some(ariane_5),
+    },
+    Incident {
+        name: "Therac-25",
+        year_range: (1985, Some(1987)),
+        category: FailureCategory::Race,
+        root_cause: "Race conditions killed patients",
+        lesson: "Never assume timing will work out",
+        mitigation: "Mutex, atomic operations, formal verification",
+        demo: None,
+    },
+    Incident {
+        name: "Mars Climate Orbiter",
+        year_range: (1999, None),
+        category: FailureCategory::Units,
+        root_cause: "Unit conversion error: pound-seconds read as newton-seconds",
+        lesson: "Never assume implicit conversions are correct",
+        mitigation: "Type systems, newtypes, dimensional analysis",
+        demo: Some(mars_climate_orbiter),
+    },
+    Incident {
+        name: "Heartbleed",
+        year_range: (2014, None),
+        category: FailureCategory::MemorySafety,
+        root_cause: "Buffer over-read leaked secrets",
+        lesson: "Never trust buffer boundaries",
+        mitigation: "Bounds checking, Rust's ownership system",
+        demo: Some(heartbleed),
+    },
+    Incident {
+        name: "CloudFlare",
+        year_range: (2025, None),
+        category: FailureCategory::Unwrap,
+        root_cause: ".unwrap() took down services",
+        lesson: "Never assume Optional values exist",
+        mitigation: "Explicit error handling, ? operator",
+        demo: Some(cloudflare),
+    },
+    Incident {
+        name: "AT&T long-distance network",
+        year_range: (1990, None),
+        category: FailureCategory::Cascade,
+        root_cause: "A crashed switch's recovery messages crashed its neighbours, for nine hours",
+        lesson: "Never assume the recovery path is safe because the normal path is",
+        mitigation: "Bulkheads, staged rollouts, testing the failure handlers",
+        demo: None,
+    },
+    Incident {
+        name: "Patriot missile, Dhahran",
+        year_range: (1991, None),
+        category: FailureCategory::Timing,
+        root_cause: "A clock kept in 24-bit fixed point drifted 0.34s after 100 hours up",
+        lesson: "Never assume a small error stays small",
+        mitigation: "Exact time representations, restarts inside the tested uptime",
+        demo: None,
+    },
+    Incident {
+        name: "Leap second",
+        year_range: (2012, None),
+        category: FailureCategory::Timing,
+        root_cause: "An inserted second livelocked Linux servers across the web",
+        lesson: "Never assume the clock only moves forward, one second at a time",
+        mitigation: "Monotonic clocks, leap smearing, checked duration arithmetic",
+        demo: None,
+    },
+    Incident {
+        name: "Knight Capital",
+        year_range: (2012, None),
+        category: FailureCategory::Deployment,
+        root_cause: "One server missed a deploy and ran dead code: $440M lost in 45 minutes",
+        lesson: "Never assume every machine runs the code you shipped",
+        mitigation: "Automated deploys, deleting dead code, kill switches",
+        demo: None,
+    },
+    Incident {
+        name: "Boeing 787",
+        year_range: (2015, None),
+        category: FailureCategory::Overflow,
+        root_cause: "A 32-bit counter overflowed after 248 days powered, shutting down the generators",
+        lesson: "Never assume nobody will leave it running that long",
+        mitigation: "Wider or checked counters, tests that fast-forward uptime",
+        demo: None,
+    },
+];
+
+/// Incidents of one category, in the database's order
+pub fn by_category(category: FailureCategory) -> impl Iterator<Item = &'static Incident> {
+    INCIDENTS.iter().filter(move |incident| incident.category == category)
+}
+
+/// Every incident, oldest first; incidents from the same year keep their order
+pub fn timeline() -> Vec<&'static Incident> {
+    let mut incidents: Vec<_> = INCIDENTS.iter().collect();
+    incidents.sort_by_key(|incident| incident.year_range);
+    incidents
+}
+
+/// Ariane 5: a 64-bit value narrowed into 16 bits
+fn ariane_5(out: &mut dyn Write) -> io::Result<DemoOutcome> {
+    // The flight software narrowed a 64-bit horizontal bias value into a 16-bit integer
+    let horizontal_bias: u64 = 40_000;
+    writeln!(out, "     horizontal_bias as i16 = {} (wrapped silently)", horizontal_bias as i16)?;
+    writeln!(out, "     i16::MAX.wrapping_add(1) = {}", i16::MAX.wrapping_add(1))?;
+    writeln!(out, "     i16::MAX.checked_add(1) = {:?}", i16::MAX.checked_add(1))?;
+    match better_approaches::convert::<i16>(horizontal_bias) {
+        Ok(bias) => {
+            writeln!(out, "     convert::<i16>({}) = {}", horizontal_bias, bias)?;
+            Ok(DemoOutcome::Prevented)
+        }
+        Err(e) => {
+            writeln!(out, "     convert::<i16>({}) = Err: {} (Example 17)", horizontal_bias, e)?;
+            Ok(DemoOutcome::Caught)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+struct Meters(f64);
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+struct Feet(f64);
+
+impl Add for Meters {
+    type Output = Meters;
+    fn add(self, other: Meters) -> Meters {
+        Meters(self.0 + other.0)
+    }
+}
+
+impl Add for Feet {
+    type Output = Feet;
+    fn add(self, other: Feet) -> Feet {
+        Feet(self.0 + other.0)
+    }
+}
+
+impl From<Feet> for Meters {
+    fn from(feet: Feet) -> Meters {
+        Meters(feet.0 * 0.3048)
+    }
+}
+
+/// Mars Climate Orbiter: one team's numbers in the other team's units
+fn mars_climate_orbiter(out: &mut dyn Write) -> io::Result<DemoOutcome> {
+    let (altitude, correction) = (Meters(57_000.0), Feet(30_000.0));
+    writeln!(out, "     Bare f64: 57000.0 + 30000.0 = {} - meters or feet? Nobody knows", 57_000.0 + 30_000.0)?;
+    writeln!(out, "     Meters(57000.0) + Feet(30000.0) → error[E0308]: expected `Meters`, found `Feet`")?;
+    let corrected = altitude + Meters::from(correction);
+    writeln!(out, "     Meters(57000.0) + Meters::from(Feet(30000.0)) = {:.1} m", corrected.0)?;
+    Ok(DemoOutcome::Prevented)
+}
+
+/// Heartbleed: a heartbeat that echoes back as many bytes as it claims to hold
+fn heartbleed(out: &mut dyn Write) -> io::Result<DemoOutcome> {
+    // What the server holds: the heartbeat payload, and right after it, secrets
+    let memory = b"birdPRIVATE-KEY:0x5ec2e7;session=8f3a".to_vec();
+    let payload = &memory[..4];
+    let declared_len = 40;
+    let leaked = String::from_utf8_lossy(&memory[..declared_len.min(memory.len())]);
+    writeln!(out, "     memcpy(reply, payload, {}) in C would echo: {:?}", declared_len, leaked)?;
+    match payload.get(..declared_len) {
+        Some(echo) => {
+            writeln!(out, "     payload.get(..{}) = {:?}", declared_len, String::from_utf8_lossy(echo))?;
+            Ok(DemoOutcome::Prevented)
+        }
+        None => {
+            writeln!(out, "     payload.get(..{}) = None: the payload is only {} bytes", declared_len, payload.len())?;
+            Ok(DemoOutcome::Caught)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TooManyFeatures {
+    count: usize,
+    limit: usize,
+}
+
+impl fmt::Display for TooManyFeatures {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} features, limit is {}", self.count, self.limit)
+    }
+}
+
+impl std::error::Error for TooManyFeatures {}
+
+/// Reads a feature file into a fixed-size table, refusing one that is too big
+fn load_features(file: &str, limit: usize) -> Result<Vec<&str>, TooManyFeatures> {
+    let features: Vec<&str> = file.lines().collect();
+    if features.len() > limit {
+        return Err(TooManyFeatures { count: features.len(), limit });
+    }
+    Ok(features)
+}
+
+/// CloudFlare: a generated file doubled in size, and the loader unwrapped the error
+fn cloudflare(out: &mut dyn Write) -> io::Result<DemoOutcome> {
+    let limit = 200;
+    let doubled: String = (0..2 * limit).map(|n| format!("feature_{}\n", n)).collect();
+    let crashed = panics::without_panic_messages(|| {
+        panics::capture(|| load_features(&doubled, limit).unwrap().len())
+    });
+    match crashed {
+        Ok(count) => writeln!(out, "     load_features(..).unwrap() loaded {} features", count)?,
+        Err(panics::CaptureError::Unsupported) => {
+            writeln!(out, "     ⚠ load_features(..).unwrap() skipped - a panic would abort this build")?;
+        }
+        Err(e) => {
+            writeln!(out, "     ✗ load_features(..).unwrap() panicked - the proxy goes down")?;
+            writeln!(out, "       {}", e)?;
+        }
+    }
+    match load_features(&doubled, limit) {
+        Ok(features) => {
+            writeln!(out, "     load_features(..)? loaded {} features", features.len())?;
+            Ok(DemoOutcome::Prevented)
+        }
+        Err(e) => {
+            writeln!(out, "     load_features(..)? = Err: {} - keep the last good file", e)?;
+            Ok(DemoOutcome::Caught)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn timeline_is_sorted_and_every_category_has_incidents() {
+        let timeline = timeline();
+        assert_eq!(timeline.len(), INCIDENTS.len());
+        assert!(timeline.windows(2).all(|pair| pair[0].year_range <= pair[1].year_range));
+        
+        for category in FailureCategory::ALL {
+            assert!(by_category(category).next().is_some(), "no incidents for {}", category);
+            assert!(by_category(category).all(|incident| incident.category == category));
+            assert_eq!(FailureCategory::from_name(&category.name().to_lowercase()), Some(category));
+        }
+        let counted: usize = FailureCategory::ALL.iter().map(|&category| by_category(category).count()).sum();
+        assert_eq!(counted, INCIDENTS.len());
+        
+        for incident in &INCIDENTS {
+            assert!(!incident.root_cause.is_empty() && !incident.lesson.is_empty() && !incident.mitigation.is_empty());
+            assert!(incident.year_range.1.is_none_or(|end| end >= incident.year_range.0), "{}", incident.name);
+        }
+    }
+    
+    #[test]
+    fn every_demo_runs_without_panicking_the_harness() {
+        let demos: Vec<_> = INCIDENTS.iter().filter_map(|incident| incident.demo.map(|demo| (incident.name, demo))).collect();
+        assert!(demos.len() >= 4, "Ariane 5, Mars Climate Orbiter, Heartbleed and CloudFlare at least");
+        for (name, demo) in demos {
+            let mut out = Vec::new();
+            let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| demo(&mut out)));
+            assert!(matches!(outcome, Ok(Ok(_))), "{}: {:?}", name, outcome.map(|_| ()));
+            assert!(!out.is_empty(), "{} wrote nothing", name);
+        }
+    }
+}
@@ -0,0 +1,521 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::lint::{Acknowledgement, LintReport};
+use crate::stats;
+use crate::system_design::{ComparisonTable, MetricsSnapshot, MonteCarloSummary, SimulationReport};
+
+// Schema of the documents `--format json` prints. Fields are only ever
+// added, never renamed or removed. Durations are milliseconds (numbers);
+// a value that is infinite or undefined - the p99 of a crashed run, say -
+// is null, since JSON has no infinity.
+//
+// simulate:     { "reports": [Report, ...] }
+// monte carlo:  { "monte_carlo": [Summary, ...] }
+// compare:      Comparison
+// lint:         Lint
+//
+// Report = {
+//   "design": "unsafe" | "safe" | "resilient" | "retry",
+//   "seed": u64, "successful": n, "not_found": n, "failed": n, "deadline_exceeded": n,
+//   "rejected": n, "dropped": n, "availability": 0.0..1.0,
+//   "availability_interval": [low, high] (95% Wilson score interval),
+//   "duration_ms": ms, "attempts": n, "backoff_ms": ms,
+//   "latency_ms": { "p50": ms|null, "p95": ms|null, "p99": ms|null, "max": ms|null },
+//   "metrics": { "successes": n, "handled_errors": n, "not_found": n, "deadline_exceeded": n,
+//                "fallbacks": n, "rejected": n, "panics": n, "restarts": n,
+//                "total_latency_ms": ms },
+//   "last_panic": string|null (the message of the last panic counted),
+//   "tiers": { "<tier>": n, ... },
+//   "requests": [{ "id": n, "outcome": Outcome::name(), "latency_ms": ms|null,
+//                  "error": string|null }, ...]
+// }
+// Summary = { "design", "iterations", "mean_availability", "stddev_availability",
+//             "min_availability", "max_availability", "crashed_runs", "crash_fraction" }
+// Comparison = { "seed": u64, "rows": [{ "design", "successful", "failed", "dropped",
+//                                        "availability", "p99_latency_ms": ms|null }, ...] }
+// Lint = { "findings": [{ "path", "line": n, "column": n, "kind": CallKind::name(),
+//                         "code": string, "context": Context::name(),
+//                         "local_type": string|null, "acknowledged": null | "baseline"
+//                         | "comment", "reason": string|null (the comment's),
+//                         "rule": Rule::id(), "risk": x }, ...],
+//          "files": [{ "path", "lines": n, "unwraps": n, "expects": n, "todos": n,
+//                      "per_thousand_lines": x, "risk": x, "crate": path|null }, ...],
+//          "stale": ["path:line" baseline entries that match nothing, ...],
+//          "total": { "files": n, "lines": n, "sites": n, "exempt": n (tests, const fns,
+//                     local unwrap methods), "acknowledged": n, "per_thousand_lines": x,
+//                     "risk": x } }
+
+/// Rendering as a JSON value
+pub trait ToJson {
+    fn to_json(&self) -> String;
+}
+
+/// A JSON string literal, escaped
+pub fn string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if u32::from(c) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// A JSON number; NaN and infinities become null
+pub fn number(x: f64) -> String {
+    if x.is_finite() {
+        x.to_string()
+    } else {
+        String::from("null")
+    }
+}
+
+pub fn millis(d: Duration) -> String {
+    number(d.as_secs_f64() * 1000.0)
+}
+
+pub fn array<T: ToJson>(items: &[T]) -> String {
+    let items: Vec<String> = items.iter().map(ToJson::to_json).collect();
+    format!("[{}]", items.join(","))
+}
+
+/// Builds a JSON object field by field; values must already be JSON
+#[derive(Debug, Default)]
+pub struct Object {
+    fields: Vec<String>,
+}
+
+impl Object {
+    pub fn new() -> Self {
+        Object::default()
+    }
+    
+    pub fn field(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.fields.push(format!("{}:{}", string(key), value.into()));
+        self
+    }
+    
+    pub fn finish(self) -> String {
+        format!("{{{}}}", self.fields.join(","))
+    }
+}
+
+fn percentile(sorted: &[f64], p: f64) -> String {
+    stats::percentile(sorted, p).map_or_else(|| String::from("null"), number)
+}
+
+impl ToJson for MetricsSnapshot {
+    fn to_json(&self) -> String {
+        Object::new()
+            .field("successes", self.successes.to_string())
+            .field("handled_errors", self.handled_errors.to_string())
+            .field("not_found", self.not_found.to_string())
+            .field("deadline_exceeded", self.deadline_exceeded.to_string())
+            .field("fallbacks", self.fallbacks.to_string())
+            .field("rejected", self.rejected.to_string())
+            .field("panics", self.panics.to_string())
+            .field("restarts", self.restarts.to_string())
+            .field("total_latency_ms", millis(self.total_latency))
+            .finish()
+    }
+}
+
+impl ToJson for SimulationReport {
+    fn to_json(&self) -> String {
+        let sorted = self.sorted_latencies_ms();
+        let latency = Object::new()
+            .field("p50", percentile(&sorted, 50.0))
+            .field("p95", percentile(&sorted, 95.0))
+            .field("p99", percentile(&sorted, 99.0))
+            .field("max", percentile(&sorted, 100.0))
+            .finish();
+        let tiers = self.tiers.iter()
+            .fold(Object::new(), |tiers, (tier, count)| tiers.field(tier, count.to_string()))
+            .finish();
+        let requests: Vec<String> = self.outcomes.iter().zip(&self.latencies)
+            .map(|((id, outcome), latency)| {
+                Object::new()
+                    .field("id", id.to_string())
+                    .field("outcome", string(outcome.name()))
+                    .field("latency_ms", latency.map_or_else(|| String::from("null"), millis))
+                    .field("error", outcome.error().map_or_else(|| String::from("null"), string))
+                    .finish()
+            })
+            .collect();
+        Object::new()
+            .field("design", string(self.design.name()))
+            .field("seed", self.seed.to_string())
+            .field("successful", self.successful.to_string())
+            .field("not_found", self.not_found.to_string())
+            .field("failed", self.failed.to_string())
+            .field("deadline_exceeded", self.deadline_exceeded.to_string())
+            .field("rejected", self.rejected.to_string())
+            .field("dropped", self.dropped.to_string())
+            .field("availability", number(self.availability))
+            .field("availability_interval", format!("[{},{}]", number(self.availability_interval.0),
+                                                               number(self.availability_interval.1)))
+            .field("duration_ms", millis(self.duration))
+            .field("attempts", self.attempts.to_string())
+            .field("backoff_ms", millis(self.backoff))
+            .field("latency_ms", latency)
+            .field("metrics", self.metrics.to_json())
+            .field("last_panic", self.last_panic.as_deref().map_or_else(|| String::from("null"), string))
+            .field("tiers", tiers)
+            .field("requests", format!("[{}]", requests.join(",")))
+            .finish()
+    }
+}
+
+impl ToJson for MonteCarloSummary {
+    fn to_json(&self) -> String {
+        Object::new()
+            .field("design", string(self.design.name()))
+            .field("iterations", self.iterations.to_string())
+            .field("mean_availability", number(self.mean_availability))
+            .field("stddev_availability", number(self.stddev_availability))
+            .field("min_availability", number(self.min_availability))
+            .field("max_availability", number(self.max_availability))
+            .field("crashed_runs", self.crashed_runs.to_string())
+            .field("crash_fraction", number(self.crash_fraction()))
+            .finish()
+    }
+}
+
+impl ToJson for ComparisonTable {
+    fn to_json(&self) -> String {
+        let rows: Vec<String> = self.rows.iter()
+            .map(|row| {
+                Object::new()
+                    .field("design", string(row.design.name()))
+                    .field("successful", row.successful.to_string())
+                    .field("failed", row.failed.to_string())
+                    .field("dropped", row.dropped.to_string())
+                    .field("availability", number(row.availability))
+                    .field("p99_latency_ms", row.p99_ms.map_or_else(|| String::from("null"), number))
+                    .finish()
+            })
+            .collect();
+        Object::new()
+            .field("seed", self.seed.to_string())
+            .field("rows", format!("[{}]", rows.join(",")))
+            .finish()
+    }
+}
+
+impl ToJson for LintReport {
+    fn to_json(&self) -> String {
+        let findings: Vec<String> = self.findings.iter()
+            .map(|finding| {
+                Object::new()
+                    .field("path", string(&finding.path.display().to_string()))
+                    .field("line", finding.line.to_string())
+                    .field("column", finding.column.to_string())
+                    .field("kind", string(finding.kind.name()))
+                    .field("code", string(&finding.code))
+                    .field("context", string(finding.context.name()))
+                    .field("local_type", finding.local_type.as_deref().map_or_else(|| String::from("null"), string))
+                    .field("acknowledged", match finding.acknowledged {
+                        None => String::from("null"),
+                        Some(Acknowledgement::Baseline) => string("baseline"),
+                        Some(Acknowledgement::Comment(_)) => string("comment"),
+                    })
+                    .field("reason", match &finding.acknowledged {
+                        Some(Acknowledgement::Comment(reason)) => string(reason),
+                        _ => String::from("null"),
+                    })
+                    .field("rule", string(finding.rule().id()))
+                    .field("risk", number(finding.risk))
+                    .finish()
+            })
+            .collect();
+        let files: Vec<String> = self.files.iter()
+            .map(|file| {
+                Object::new()
+                    .field("path", string(&file.path.display().to_string()))
+                    .field("lines", file.lines.to_string())
+                    .field("unwraps", file.unwraps.to_string())
+                    .field("expects", file.expects.to_string())
+                    .field("todos", file.todos.to_string())
+                    .field("per_thousand_lines", number(file.per_thousand_lines()))
+                    .field("risk", number(file.risk))
+                    .field("crate", file.crate_root.as_ref()
+                        .map_or_else(|| String::from("null"), |root| string(&root.display().to_string())))
+                    .finish()
+            })
+            .collect();
+        let total = Object::new()
+            .field("files", self.files.len().to_string())
+            .field("lines", self.lines().to_string())
+            .field("sites", self.findings.len().to_string())
+            .field("exempt", self.findings.iter().filter(|finding| finding.exempt()).count().to_string())
+            .field("acknowledged", self.acknowledged().to_string())
+            .field("per_thousand_lines", number(self.per_thousand_lines()))
+            .field("risk", number(self.risk()))
+            .finish();
+        Object::new()
+            .field("findings", format!("[{}]", findings.join(",")))
+            .field("files", format!("[{}]", files.join(",")))
+            .field("stale", format!("[{}]", self.stale.iter().map(|entry| string(entry)).collect::<Vec<_>>().join(",")))
+            .field("total", total)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::{Path, PathBuf};
+    
+    use crate::lint::{scan_source, to_sarif, FileStats, Level, Policy, Rule};
+    use crate::system_design::{simulate_with_config, Design, SimConfig};
+    
+    /// Just enough JSON to read back what this module writes
+    #[derive(Debug, Clone, PartialEq)]
+    enum Value {
+        Null,
+        Number(f64),
+        String(String),
+        Array(Vec<Value>),
+        Object(Vec<(String, Value)>),
+    }
+    
+    impl Value {
+        fn get(&self, key: &str) -> &Value {
+            match self {
+                Value::Object(fields) => fields.iter()
+                    .find(|(k, _)| k == key)
+                    .map(|(_, v)| v)
+                    .unwrap_or_else(|| panic!("no field {:?}", key)),
+                other => panic!("{:?} is not an object", other),
+            }
+        }
+        
+        fn number(&self) -> f64 {
+            match self {
+                Value::Number(x) => *x,
+                other => panic!("{:?} is not a number", other),
+            }
+        }
+        
+        fn str(&self) -> &str {
+            match self {
+                Value::String(s) => s,
+                other => panic!("{:?} is not a string", other),
+            }
+        }
+        
+        fn items(&self) -> &[Value] {
+            match self {
+                Value::Array(items) => items,
+                other => panic!("{:?} is not an array", other),
+            }
+        }
+    }
+    
+    fn parse(json: &str) -> Value {
+        let mut chars = json.chars().peekable();
+        let value = parse_value(&mut chars);
+        assert_eq!(chars.next(), None, "trailing input");
+        value
+    }
+    
+    fn parse_value(chars: &mut std::iter::Peekable<std::str::Chars>) -> Value {
+        match chars.peek() {
+            Some('n') => {
+                assert_eq!(chars.by_ref().take(4).collect::<String>(), "null");
+                Value::Null
+            }
+            Some('"') => Value::String(parse_string(chars)),
+            Some('[') => {
+                chars.next();
+                let mut items = Vec::new();
+                while chars.peek() != Some(&']') {
+                    items.push(parse_value(chars));
+                    if chars.peek() == Some(&',') {
+                        chars.next();
+                    }
+                }
+                chars.next();
+                Value::Array(items)
+            }
+            Some('{') => {
+                chars.next();
+                let mut fields = Vec::new();
+                while chars.peek() != Some(&'}') {
+                    let key = parse_string(chars);
+                    assert_eq!(chars.next(), Some(':'));
+                    fields.push((key, parse_value(chars)));
+                    if chars.peek() == Some(&',') {
+                        chars.next();
+                    }
+                }
+                chars.next();
+                Value::Object(fields)
+            }
+            _ => {
+                let mut literal = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !matches!(c, '0'..='9' | '-' | '+' | '.' | 'e' | 'E') {
+                        break;
+                    }
+                    literal.push(c);
+                    chars.next();
+                }
+                Value::Number(literal.parse().unwrap_or_else(|_| panic!("bad number {:?}", literal)))
+            }
+        }
+    }
+    
+    fn parse_string(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+        assert_eq!(chars.next(), Some('"'));
+        let mut out = String::new();
+        loop {
+            match chars.next() {
+                Some('"') => return out,
+                Some('\\') => match chars.next() {
+                    Some('n') => out.push('\n'),
+                    Some('r') => out.push('\r'),
+                    Some('t') => out.push('\t'),
+                    Some('u') => {
+                        let hex: String = chars.by_ref().take(4).collect();
+                        let code = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32);
+                        out.push(code.unwrap_or_else(|| panic!("bad escape \\u{}", hex)));
+                    }
+                    Some(c) => out.push(c),
+                    None => panic!("unterminated escape"),
+                },
+                Some(c) => out.push(c),
+                None => panic!("unterminated string"),
+            }
+        }
+    }
+    
+    #[test]
+    fn strings_and_numbers_round_trip() {
+        let awkward = "say \"hi\"\\n\tthen\u{1}bye, ünïcode";
+        assert_eq!(parse(&string(awkward)), Value::String(String::from(awkward)));
+        assert_eq!(parse(&number(0.125)), Value::Number(0.125));
+        assert_eq!(parse(&number(f64::INFINITY)), Value::Null);
+        assert_eq!(parse(&millis(Duration::from_micros(1500))), Value::Number(1.5));
+    }
+    
+    #[test]
+    fn report_round_trips_field_by_field() {
+        let config = SimConfig { requests: 40, failure_rate: 0.3, seed: Some(271), ..SimConfig::default() };
+        let report = simulate_with_config(Design::Safe, &config);
+        let json = parse(&report.to_json());
+        
+        assert_eq!(json.get("design").str(), "safe");
+        assert_eq!(json.get("seed").number(), 271.0);
+        assert_eq!(json.get("successful").number(), report.successful as f64);
+        assert_eq!(json.get("failed").number(), report.failed as f64);
+        assert_eq!(json.get("dropped").number(), report.dropped as f64);
+        assert_eq!(json.get("availability").number(), report.availability);
+        let interval = json.get("availability_interval").items();
+        assert_eq!((interval[0].number(), interval[1].number()), report.availability_interval);
+        assert_eq!(json.get("metrics").get("handled_errors").number(), report.metrics.handled_errors as f64);
+        
+        let requests = json.get("requests").items();
+        assert_eq!(requests.len(), report.outcomes.len());
+        for (request, ((id, outcome), latency)) in requests.iter().zip(report.outcomes.iter().zip(&report.latencies)) {
+            assert_eq!(request.get("id").number(), *id as f64);
+            assert_eq!(request.get("outcome").str(), outcome.name());
+            match latency {
+                Some(latency) => assert!((request.get("latency_ms").number() - latency.as_secs_f64() * 1000.0).abs() < 1e-9),
+                None => assert_eq!(request.get("latency_ms"), &Value::Null),
+            }
+            match outcome.error() {
+                Some(error) => assert_eq!(request.get("error").str(), error),
+                None => assert_eq!(request.get("error"), &Value::Null),
+            }
+        }
+        assert!(report.failed > 0, "the seed should give some errors to round-trip");
+    }
+    
+    #[test]
+    fn lint_report_has_a_finding_per_call_site() {
+        let source = "fn f(x: Option<u8>) -> u8 {\n    // unwrap-ok: checked above\n    x.expect(\"set\")\n        + x.unwrap()\n}\n";
+        let path = PathBuf::from("src/lib.rs");
+        let findings = scan_source(&path, source);
+        let file = FileStats { path, lines: 5, unwraps: 1, expects: 1, todos: 0, risk: 0.0, crate_root: None };
+        let report = LintReport { findings, files: vec![file], stale: vec![String::from("src/old.rs:9")] };
+        let json = parse(&report.to_json());
+        
+        let findings = json.get("findings").items();
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].get("kind").str(), "expect");
+        assert_eq!(findings[0].get("rule").str(), "expect-in-lib");
+        assert_eq!(findings[0].get("acknowledged").str(), "comment");
+        assert_eq!(findings[0].get("reason").str(), "checked above");
+        assert_eq!(findings[1].get("kind").str(), "unwrap");
+        assert_eq!((findings[1].get("line").number(), findings[1].get("column").number()), (4.0, 13.0)); // the comment covers only its line and the next
+        assert_eq!(findings[1].get("code").str(), "+ x.unwrap()");
+        assert_eq!(findings[1].get("acknowledged"), &Value::Null);
+        
+        assert_eq!(json.get("files").items()[0].get("per_thousand_lines").number(), 400.0);
+        assert_eq!(json.get("stale").items(), [Value::String(String::from("src/old.rs:9"))]);
+        assert_eq!(json.get("total").get("sites").number(), 2.0);
+        assert_eq!(json.get("total").get("acknowledged").number(), 1.0);
+    }
+    
+    #[test]
+    fn sarif_has_a_result_per_finding() {
+        let source = "use std::fs::File;
+fn f(x: Option<u8>) -> u8 {
+    let _ = File::open(\"a\").unwrap();
+    x.expect(\"set\") + x.unwrap() // unwrap-ok: checked by the caller
+}
+#[test]
+fn t() {
+    Some(1).unwrap();
+}
+";
+        let report = LintReport { findings: scan_source(Path::new("./src/lib.rs"), source), ..LintReport::default() };
+        let policy = Policy { strict: false, fail_level: Level::Warning };
+        let sarif = parse(&to_sarif(&report, &policy));
+        assert_eq!(sarif.get("version").str(), "2.1.0");
+        
+        let run = &sarif.get("runs").items()[0];
+        let rules = run.get("tool").get("driver").get("rules").items();
+        let ids: Vec<&str> = rules.iter().map(|rule| rule.get("id").str()).collect();
+        assert_eq!(ids, Rule::ALL.map(Rule::id));
+        
+        let results = run.get("results").items();
+        assert_eq!(results.len(), report.findings.len());
+        let summary: Vec<(&str, &str, f64, usize)> = results.iter()
+            .map(|result| {
+                let rule = result.get("ruleId").str();
+                assert_eq!(ids[result.get("ruleIndex").number() as usize], rule);
+                let location = result.get("locations").items()[0].get("physicalLocation");
+                assert_eq!(location.get("artifactLocation").get("uri").str(), "src/lib.rs");
+                let line = location.get("region").get("startLine").number();
+                (rule, result.get("level").str(), line, result.get("suppressions").items().len())
+            })
+            .collect();
+        assert_eq!(summary, vec![
+            ("unwrap-on-io-result", "error", 3.0, 0),
+            ("expect-in-lib", "note", 4.0, 1),
+            ("unwrap-in-lib", "warning", 4.0, 1),
+            ("unwrap-in-lib", "none", 8.0, 0), // test code, and not strict
+        ]);
+        let suppression = &results[1].get("suppressions").items()[0];
+        assert_eq!((suppression.get("kind").str(), suppression.get("justification").str()), ("inSource", "checked by the caller"));
+        
+        // The exit code: anything unacknowledged at or above the fail level
+        assert_eq!(report.failing(&policy), 1);
+        assert_eq!(report.failing(&Policy { fail_level: Level::Error, ..policy }), 1);
+        assert_eq!(report.failing(&Policy { fail_level: Level::None, ..policy }), 2);
+        assert_eq!(report.failing(&Policy { strict: true, fail_level: Level::Note }), 2);
+    }
+}
@@ -0,0 +1,363 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+const HEADER_LEN: usize = 8;
+
+/// CRC-32 (IEEE), bitwise - slow but dependency-free
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Why a frame could not be decoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameError {
+    Truncated,
+    ChecksumMismatch,
+    InvalidKey,
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FrameError::Truncated => write!(f, "record is truncated"),
+            FrameError::ChecksumMismatch => write!(f, "checksum mismatch"),
+            FrameError::InvalidKey => write!(f, "key is not valid UTF-8"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum KvError {
+    Io(io::Error),
+    Corrupt { offset: u64, reason: FrameError },
+    KeyTooLong(usize),
+    ValueTooLong(usize),
+}
+
+impl fmt::Display for KvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KvError::Io(e) => write!(f, "I/O error: {}", e),
+            KvError::Corrupt { offset, reason } => {
+                write!(f, "Corrupt record at byte {}: {}", offset, reason)
+            }
+            KvError::KeyTooLong(len) => write!(f, "Key of {} bytes is too long", len),
+            KvError::ValueTooLong(len) => write!(f, "Value of {} bytes is too long", len),
+        }
+    }
+}
+
+impl std::error::Error for KvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            KvError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for KvError {
+    fn from(e: io::Error) -> Self {
+        KvError::Io(e)
+    }
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    let field = bytes.get(at..at + 4)?;
+    Some(u32::from_le_bytes([field[0], field[1], field[2], field[3]]))
+}
+
+fn encode_frame(key: &str, value: &[u8]) -> Result<Vec<u8>, KvError> {
+    let key_len = u32::try_from(key.len()).map_err(|_| KvError::KeyTooLong(key.len()))?;
+    let mut payload = Vec::with_capacity(4 + key.len() + value.len());
+    payload.extend_from_slice(&key_len.to_le_bytes());
+    payload.extend_from_slice(key.as_bytes());
+    payload.extend_from_slice(value);
+    let payload_len = u32::try_from(payload.len()).map_err(|_| KvError::ValueTooLong(value.len()))?;
+    
+    let mut frame = Vec::with_capacity(HEADER_LEN + payload.len());
+    frame.extend_from_slice(&payload_len.to_le_bytes());
+    frame.extend_from_slice(&crc32(&payload).to_le_bytes());
+    frame.extend_from_slice(&payload);
+    Ok(frame)
+}
+
+/// Decode the frame at the start of `bytes`: (key, value, bytes consumed)
+pub fn decode_frame(bytes: &[u8]) -> Result<(String, &[u8], usize), FrameError> {
+    let payload_len = read_u32(bytes, 0).ok_or(FrameError::Truncated)? as usize;
+    let checksum = read_u32(bytes, 4).ok_or(FrameError::Truncated)?;
+    let end = HEADER_LEN.checked_add(payload_len).ok_or(FrameError::Truncated)?;
+    let payload = bytes.get(HEADER_LEN..end).ok_or(FrameError::Truncated)?;
+    if crc32(payload) != checksum {
+        return Err(FrameError::ChecksumMismatch);
+    }
+    
+    let key_len = read_u32(payload, 0).ok_or(FrameError::Truncated)? as usize;
+    let key_bytes = payload.get(4..4 + key_len).ok_or(FrameError::Truncated)?;
+    let key = std::str::from_utf8(key_bytes).map_err(|_| FrameError::InvalidKey)?;
+    Ok((key.to_string(), &payload[4 + key_len..], end))
+}
+
+/// Count the frames in a tail that failed to decode, following each header's
+/// length; whatever is left over that can't be measured counts as one more
+fn count_frames(mut tail: &[u8]) -> usize {
+    let mut frames = 0;
+    while !tail.is_empty() {
+        frames += 1;
+        let next = read_u32(tail, 0)
+            .and_then(|len| HEADER_LEN.checked_add(len as usize))
+            .filter(|&end| end <= tail.len());
+        match next {
+            Some(end) => tail = &tail[end..],
+            None => break,
+        }
+    }
+    frames
+}
+
+/// What the recovery scan found when the store was opened
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Recovery {
+    pub recovered: usize,
+    pub lost_records: usize, // frames after the bad one, walked by their length prefix
+    pub lost_bytes: u64,
+    pub reason: Option<FrameError>,
+}
+
+pub struct Store {
+    file: File,
+    index: HashMap<String, (u64, usize)>, // key -> (frame offset, frame length)
+    len: u64,
+    recovery: Recovery,
+}
+
+impl Store {
+    /// Open (or create) a store, truncating at the first corrupt or partial record
+    pub fn open(path: &Path) -> Result<Store, KvError> {
+        let mut file = OpenOptions::new().read(true).append(true).create(true).open(path)?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        
+        let mut index = HashMap::new();
+        let mut offset = 0usize;
+        let mut recovered = 0;
+        let mut reason = None;
+        while offset < bytes.len() {
+            match decode_frame(&bytes[offset..]) {
+                Ok((key, _, consumed)) => {
+                    index.insert(key, (offset as u64, consumed));
+                    offset += consumed;
+                    recovered += 1;
+                }
+                Err(e) => {
+                    reason = Some(e);
+                    break;
+                }
+            }
+        }
+        
+        let lost_records = count_frames(&bytes[offset..]);
+        let lost_bytes = (bytes.len() - offset) as u64;
+        if lost_bytes > 0 {
+            file.set_len(offset as u64)?;
+        }
+        
+        Ok(Store {
+            file,
+            index,
+            len: offset as u64,
+            recovery: Recovery { recovered, lost_records, lost_bytes, reason },
+        })
+    }
+    
+    pub fn recovery(&self) -> Recovery {
+        self.recovery
+    }
+    
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+    
+    /// Read a value back from disk, re-verifying its checksum
+    pub fn get(&self, key: &str) -> Result<Option<Vec<u8>>, KvError> {
+        let Some(&(offset, frame_len)) = self.index.get(key) else {
+            return Ok(None);
+        };
+        let mut frame = vec![0u8; frame_len];
+        let mut file = &self.file;
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut frame)?;
+        let (_, value, _) = decode_frame(&frame)
+            .map_err(|reason| KvError::Corrupt { offset, reason })?;
+        Ok(Some(value.to_vec()))
+    }
+    
+    pub fn put(&mut self, key: &str, value: &[u8]) -> Result<(), KvError> {
+        let frame = encode_frame(key, value)?;
+        if let Err(e) = self.file.write_all(&frame).and_then(|()| self.file.flush()) {
+            // Part of the frame may have landed: cut it off, or failing that,
+            // track where the file really ends so later offsets stay right
+            if self.file.set_len(self.len).is_err() {
+                if let Ok(metadata) = self.file.metadata() {
+                    self.len = metadata.len();
+                }
+            }
+            return Err(e.into());
+        }
+        self.index.insert(key.to_string(), (self.len, frame.len()));
+        self.len += frame.len() as u64;
+        Ok(())
+    }
+}
+
+/// The naive loader: trusts every byte on disk
+/// One torn write and the whole store is lost - along with the process
+pub fn load_naive(path: &Path) -> HashMap<String, Vec<u8>> {
+    let bytes = fs::read(path).unwrap();
+    let mut records = HashMap::new();
+    let mut offset = 0;
+    while offset < bytes.len() {
+        let (key, value, consumed) = decode_frame(&bytes[offset..]).unwrap();
+        records.insert(key, value.to_vec());
+        offset += consumed;
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    
+    const RECORDS: [(&str, &[u8]); 4] = [("a", b"1"), ("bb", b"22"), ("a", b"333"), ("c", b"4444")];
+    
+    struct TempPath(PathBuf);
+    
+    impl TempPath {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("unwrap-kv-{}-{}", std::process::id(), name));
+            let _ = fs::remove_file(&path);
+            TempPath(path)
+        }
+    }
+    
+    impl Drop for TempPath {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+    
+    /// Write every record and return the file plus where each frame ends
+    fn write_records(path: &Path) -> (Vec<u8>, Vec<usize>) {
+        let mut store = Store::open(path).unwrap();
+        for (key, value) in RECORDS {
+            store.put(key, value).unwrap();
+        }
+        let bytes = fs::read(path).unwrap();
+        let mut ends = Vec::new();
+        let mut offset = 0;
+        while offset < bytes.len() {
+            offset += decode_frame(&bytes[offset..]).unwrap().2;
+            ends.push(offset);
+        }
+        (bytes, ends)
+    }
+    
+    /// The first `kept` records are all readable, and the store takes new writes
+    fn assert_usable(path: &Path, kept: usize) {
+        let mut store = Store::open(path).unwrap();
+        let mut expected = HashMap::new();
+        for (key, value) in &RECORDS[..kept] {
+            expected.insert(*key, *value);
+        }
+        assert_eq!(store.len(), expected.len());
+        for (key, value) in &expected {
+            assert_eq!(store.get(key).unwrap().as_deref(), Some(*value));
+        }
+        
+        store.put("after", b"recovery").unwrap();
+        assert_eq!(store.get("after").unwrap().as_deref(), Some(&b"recovery"[..]));
+        drop(store);
+        let reopened = Store::open(path).unwrap();
+        assert_eq!(reopened.recovery().reason, None);
+        assert_eq!(reopened.recovery().recovered, kept + 1);
+        assert_eq!(reopened.get("after").unwrap().as_deref(), Some(&b"recovery"[..]));
+    }
+    
+    #[test]
+    fn truncation_at_any_offset_keeps_every_whole_record() {
+        let temp = TempPath::new("truncate");
+        let (bytes, ends) = write_records(&temp.0);
+        
+        for cut in 0..=bytes.len() {
+            fs::write(&temp.0, &bytes[..cut]).unwrap();
+            let kept = ends.iter().filter(|&&end| end <= cut).count();
+            let boundary = if kept == 0 { 0 } else { ends[kept - 1] };
+            let torn = cut > boundary;
+            
+            let store = Store::open(&temp.0).unwrap();
+            let recovery = store.recovery();
+            assert_eq!(recovery.recovered, kept, "cut at {}", cut);
+            assert_eq!(recovery.lost_records, usize::from(torn), "cut at {}", cut);
+            assert_eq!(recovery.lost_bytes, (cut - boundary) as u64, "cut at {}", cut);
+            assert_eq!(recovery.reason, torn.then_some(FrameError::Truncated), "cut at {}", cut);
+            drop(store);
+            assert_eq!(fs::metadata(&temp.0).unwrap().len(), boundary as u64);
+            
+            assert_usable(&temp.0, kept);
+        }
+    }
+    
+    #[test]
+    fn a_flipped_bit_loses_its_record_and_everything_after() {
+        let temp = TempPath::new("bitflip");
+        let (bytes, ends) = write_records(&temp.0);
+        
+        for at in 0..bytes.len() {
+            for bit in [0, 3, 7] {
+                let mut flipped = bytes.clone();
+                flipped[at] ^= 1 << bit;
+                fs::write(&temp.0, &flipped).unwrap();
+                let damaged = ends.iter().filter(|&&end| end <= at).count();
+                let start = if damaged == 0 { 0 } else { ends[damaged - 1] };
+                
+                let store = Store::open(&temp.0).unwrap();
+                let recovery = store.recovery();
+                assert_eq!(recovery.recovered, damaged, "bit {} of byte {}", bit, at);
+                assert_eq!(recovery.lost_bytes, (bytes.len() - start) as u64);
+                assert!(recovery.reason.is_some());
+                if at - start >= 4 {
+                    // The length prefix survived, so every frame after it can be counted
+                    assert_eq!(recovery.reason, Some(FrameError::ChecksumMismatch));
+                    assert_eq!(recovery.lost_records, RECORDS.len() - damaged);
+                } else {
+                    assert!(recovery.lost_records >= 1);
+                }
+                drop(store);
+                
+                assert_usable(&temp.0, damaged);
+            }
+        }
+    }
+    
+    #[test]
+    fn oversized_fields_name_the_field_that_overflowed() {
+        assert_eq!(KvError::KeyTooLong(5).to_string(), "Key of 5 bytes is too long");
+        assert_eq!(KvError::ValueTooLong(7).to_string(), "Value of 7 bytes is too long");
+    }
+}
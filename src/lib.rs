@@ -0,0 +1,58 @@
+/// The cautionary examples: every unwrap() here is a panic waiting to happen
+pub mod examples;
+
+/// Better alternatives - how to handle errors properly
+pub mod better_approaches;
+
+/// Panics as values: recovering the message and location of a panic
+/// so a crashed thread can be reported instead of re-raised
+pub mod panics;
+
+/// Timing panics against Result: a hand-rolled harness, no dependencies
+pub mod bench;
+
+/// Error handling advice as data: which pattern fits which failure, where
+pub mod advice;
+
+/// Persistence: an append-only key-value store that survives torn writes
+/// Records are framed as [len: u32][crc32: u32][key_len: u32][key][value]
+pub mod kv;
+
+/// System design perspective: Poisson distribution of failures
+/// Every system carries a distribution of potential failure points
+pub mod system_design;
+
+/// Famous failures as data: what broke, why, and what we do about it now
+pub mod incidents;
+
+/// Small statistics helpers: percentiles, histograms, availability and nines,
+/// the Poisson distribution and sampling from it
+pub mod stats;
+
+/// Hand-rolled JSON output for simulation results
+/// The schema is documented at the top of the module
+pub mod json;
+
+/// Fix-the-unwrap challenges and the harness that checks them
+/// Each exercise ships broken; hostile input proves it
+pub mod exercises;
+
+/// Terminal colors and symbols, and the sinks that decide how they are shown
+pub mod style;
+
+/// Markdown export: lesson text converted to headings, code blocks and tables
+pub mod markdown;
+
+/// A multiple-choice quiz on the lessons, read from any BufRead
+pub mod quiz;
+
+/// The unwrap density scanner: every unwrap() and expect() in a source tree
+/// A lexer, not a parser - comments and string literals don't count
+pub mod lint;
+
+/// Command-line parsing for the binary
+pub mod cli;
+
+/// The narrated demo: examples, simulations, and the philosophy around them
+/// Every section writes to a caller-supplied sink, never straight to stdout
+pub mod demo;
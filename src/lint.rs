@@ -0,0 +1,1772 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::json::{self, Object};
+
+// The unwrap density scanner: the lessons, pointed at a source tree.
+// A lexer rather than a parser - it knows comments, strings, char literals
+// and lifetimes well enough to only count `.unwrap()` and `.expect(` that
+// are code. Macro arguments are code too: `println!("{}", x.unwrap())`
+// counts, the "x.unwrap()" inside a format string doesn't.
+
+/// The calls that turn an error - or a missing feature - into a panic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CallKind {
+    Unwrap,
+    Expect,
+    Todo, // todo!() or unimplemented!(): panics every time it is reached
+}
+
+impl CallKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            CallKind::Unwrap => "unwrap",
+            CallKind::Expect => "expect",
+            CallKind::Todo => "todo",
+        }
+    }
+    
+    /// As it reads in the source
+    pub fn call(self) -> &'static str {
+        match self {
+            CallKind::Unwrap => "unwrap()",
+            CallKind::Expect => "expect()",
+            CallKind::Todo => "todo!()",
+        }
+    }
+}
+
+/// Where a call site is, which decides whether the lessons forgive it.
+/// Ordered: a context inside another takes the later of the two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Context {
+    Library, // everything else: the code the lessons are about
+    Main,    // the top-level fn main of a file
+    ConstFn, // a panic there stops the build, not the program
+    Test,    // #[test], #[cfg(test)], and files under tests/ or benches/
+}
+
+impl Context {
+    pub const ALL: [Context; 4] = [Context::Library, Context::Main, Context::ConstFn, Context::Test];
+    
+    pub fn name(self) -> &'static str {
+        match self {
+            Context::Library => "library",
+            Context::Main => "main",
+            Context::ConstFn => "const fn",
+            Context::Test => "test",
+        }
+    }
+    
+    /// A failing test or a failed build is the unwrap doing its job
+    pub fn exempt(self) -> bool {
+        matches!(self, Context::ConstFn | Context::Test)
+    }
+}
+
+/// How bad a call site is, in SARIF's terms; ordered, so a fail level is a minimum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    None, // exempt: reported, never fails the run
+    Note,
+    Warning,
+    Error,
+}
+
+impl Level {
+    pub fn name(self) -> &'static str {
+        match self {
+            Level::None => "none",
+            Level::Note => "note",
+            Level::Warning => "warning",
+            Level::Error => "error",
+        }
+    }
+    
+    pub fn from_name(name: &str) -> Option<Level> {
+        match name {
+            "none" => Some(Level::None),
+            "note" => Some(Level::Note),
+            "warning" => Some(Level::Warning),
+            "error" => Some(Level::Error),
+            _ => None,
+        }
+    }
+}
+
+/// Calls in a receiver chain that mean the value came from the outside world
+pub const IO_CALLS: [&str; 16] = [
+    "File", "fs", "TcpStream", "TcpListener", "UdpSocket", "stdin", "read_line", "read_to_string",
+    "read_to_end", "read_dir", "open", "create", "connect", "bind", "write_all", "flush",
+];
+
+/// What a call site is reported as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rule {
+    UnwrapInLib,
+    ExpectInLib,
+    UnwrapOnIoResult, // unwrap() or expect() on I/O: a crash waiting for a full disk
+    TodoInLib,        // todo!() or unimplemented!(): return an Unsupported error instead
+}
+
+impl Rule {
+    pub const ALL: [Rule; 4] = [Rule::UnwrapInLib, Rule::ExpectInLib, Rule::UnwrapOnIoResult, Rule::TodoInLib];
+    
+    pub fn id(self) -> &'static str {
+        match self {
+            Rule::UnwrapInLib => "unwrap-in-lib",
+            Rule::ExpectInLib => "expect-in-lib",
+            Rule::UnwrapOnIoResult => "unwrap-on-io-result",
+            Rule::TodoInLib => "todo-in-lib",
+        }
+    }
+    
+    pub fn description(self) -> &'static str {
+        match self {
+            Rule::UnwrapInLib => "unwrap() turns an error into a panic",
+            Rule::ExpectInLib => "expect() turns an error into a panic, with a message",
+            Rule::UnwrapOnIoResult => "unwrap() or expect() on an I/O result, which fails in production",
+            Rule::TodoInLib => "todo!() or unimplemented!() panics for every caller that reaches it",
+        }
+    }
+    
+    /// expect() at least states the invariant; I/O is sure to fail one day,
+    /// and a todo!() fails the first time
+    pub fn default_level(self) -> Level {
+        match self {
+            Rule::ExpectInLib => Level::Note,
+            Rule::UnwrapInLib => Level::Warning,
+            Rule::UnwrapOnIoResult | Rule::TodoInLib => Level::Error,
+        }
+    }
+}
+
+/// How findings are judged: which are exempt, and from what level they fail the run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Policy {
+    pub strict: bool, // false: tests, const fns and local unwrap methods are exempt
+    pub fail_level: Level,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Policy { strict: true, fail_level: Level::Note }
+    }
+}
+
+/// One call site
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub path: PathBuf,
+    pub line: usize,   // 1-based
+    pub column: usize, // 1-based, in characters, at the method name
+    pub kind: CallKind,
+    pub code: String, // the line it is on, trimmed
+    pub context: Context,
+    pub local_type: Option<String>, // Some: the file's own unwrap method on this type, not Option's
+    pub receiver: Vec<String>, // identifiers of the chain it is called on: File, open for File::open(p)
+    pub acknowledged: Option<Acknowledgement>, // Some: counted, reported apart, never fails the run
+    pub literal_receiver: bool, // the chain starts at a literal: "[0-9]+".parse()
+    pub risk: f64,             // set by LintReport::score; 0 until then
+    pub suggestion: Option<Suggestion>, // None: the call spans lines, or isn't Option's or Result's
+}
+
+/// Why a call site is known about and allowed to stay
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Acknowledgement {
+    Comment(String), // `// unwrap-ok: reason` on its line or the line before
+    Baseline,        // its path:line is in .unwrapignore
+}
+
+/// The call site rewritten to propagate with `?` instead
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub span: (usize, usize), // columns, 1-based: the call's `.` to just past its `)`
+    pub before: String,       // the whole line, as it is
+    pub after: String,        // the same line, with `?` in place of the call
+    pub safe: bool,           // --fix applies it
+    pub note: Option<String>, // why it isn't safe, or what else has to change
+}
+
+impl Finding {
+    /// Whether `--strict=false` lets this call site pass
+    pub fn exempt(&self) -> bool {
+        self.context.exempt() || self.local_type.is_some()
+    }
+    
+    pub fn rule(&self) -> Rule {
+        if self.kind == CallKind::Todo {
+            Rule::TodoInLib
+        } else if self.receiver.iter().any(|name| IO_CALLS.contains(&name.as_str())) {
+            Rule::UnwrapOnIoResult
+        } else {
+            match self.kind {
+                CallKind::Unwrap => Rule::UnwrapInLib,
+                CallKind::Expect => Rule::ExpectInLib,
+                CallKind::Todo => Rule::TodoInLib,
+            }
+        }
+    }
+    
+    pub fn level(&self, policy: &Policy) -> Level {
+        if !policy.strict && self.exempt() {
+            Level::None
+        } else {
+            self.rule().default_level()
+        }
+    }
+}
+
+/// Call sites and size of one file
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileStats {
+    pub path: PathBuf,
+    pub lines: usize,
+    pub unwraps: usize,
+    pub expects: usize,
+    pub todos: usize,                // todo!() and unimplemented!()
+    pub risk: f64,                   // the sum of its call sites' risk
+    pub crate_root: Option<PathBuf>, // the nearest directory above it with a Cargo.toml
+}
+
+impl FileStats {
+    pub fn sites(&self) -> usize {
+        self.unwraps + self.expects + self.todos
+    }
+    
+    pub fn per_thousand_lines(&self) -> f64 {
+        per_thousand(self.sites(), self.lines)
+    }
+}
+
+fn per_thousand(sites: usize, lines: usize) -> f64 {
+    if lines == 0 {
+        0.0
+    } else {
+        sites as f64 * 1000.0 / lines as f64
+    }
+}
+
+/// Everything one scan found, files in path order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LintReport {
+    pub findings: Vec<Finding>,
+    pub files: Vec<FileStats>,
+    pub stale: Vec<String>, // baseline entries that no call site matches any more
+}
+
+impl LintReport {
+    pub fn lines(&self) -> usize {
+        self.files.iter().map(|file| file.lines).sum()
+    }
+    
+    pub fn per_thousand_lines(&self) -> f64 {
+        per_thousand(self.findings.len(), self.lines())
+    }
+    
+    /// The call sites that fail the run: those at or above the fail level,
+    /// and never an acknowledged one
+    pub fn failing(&self, policy: &Policy) -> usize {
+        self.findings.iter()
+            .filter(|finding| finding.acknowledged.is_none())
+            .filter(|finding| finding.level(policy) >= policy.fail_level)
+            .count()
+    }
+    
+    pub fn acknowledged(&self) -> usize {
+        self.findings.iter().filter(|finding| finding.acknowledged.is_some()).count()
+    }
+    
+    /// The scoring pass: every call site's risk under `weights`, and each file's total
+    pub fn score(&mut self, weights: &Weights) {
+        for finding in &mut self.findings {
+            finding.risk = weights.risk(finding);
+        }
+        for file in &mut self.files {
+            file.risk = self.findings.iter().filter(|finding| finding.path == file.path).map(|finding| finding.risk).sum();
+        }
+    }
+    
+    pub fn risk(&self) -> f64 {
+        self.files.iter().map(|file| file.risk).sum()
+    }
+    
+    /// Risk per crate, riskiest first; files outside any crate are under None
+    pub fn crate_risks(&self) -> Vec<(Option<&Path>, f64)> {
+        let mut crates: Vec<(Option<&Path>, f64)> = Vec::new();
+        for file in &self.files {
+            let root = file.crate_root.as_deref();
+            match crates.iter_mut().find(|(existing, _)| *existing == root) {
+                Some((_, risk)) => *risk += file.risk,
+                None => crates.push((root, file.risk)),
+            }
+        }
+        crates.sort_by(|a, b| b.1.total_cmp(&a.1));
+        crates
+    }
+    
+    /// The `n` riskiest call sites, riskiest first
+    pub fn riskiest(&self, n: usize) -> Vec<&Finding> {
+        let mut findings: Vec<&Finding> = self.findings.iter().filter(|finding| finding.risk > 0.0).collect();
+        findings.sort_by(|a, b| b.risk.total_cmp(&a.risk));
+        findings.truncate(n);
+        findings
+    }
+}
+
+/// The allowlist a scan of `root` reads: `path:line` entries, paths relative
+/// to the directory the file is in, and # comments
+pub const BASELINE_FILE: &str = ".unwrapignore";
+
+/// The baseline beside what is scanned: in `root`, or beside it when it's a file
+pub fn baseline_path(root: &Path) -> PathBuf {
+    if root.is_dir() {
+        root.join(BASELINE_FILE)
+    } else {
+        root.parent().unwrap_or(Path::new("")).join(BASELINE_FILE)
+    }
+}
+
+/// A finding's baseline entry: its path from the baseline's directory, with
+/// forward slashes whatever the platform, and its line
+fn baseline_entry(finding: &Finding, baseline: &Path) -> String {
+    let dir = baseline.parent().unwrap_or(Path::new(""));
+    let path = finding.path.strip_prefix(dir).unwrap_or(&finding.path);
+    format!("{}:{}", path.to_string_lossy().replace('\\', "/"), finding.line)
+}
+
+/// Acknowledges every finding whose entry is in the baseline at `baseline`
+/// (if there is one) and records the entries nothing matched as stale
+fn apply_baseline(report: &mut LintReport, baseline: &Path) -> io::Result<()> {
+    let text = match fs::read_to_string(baseline) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    let entries: Vec<&str> = text.lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|entry| !entry.is_empty())
+        .collect();
+    let mut matched = HashSet::new();
+    for finding in &mut report.findings {
+        let entry = baseline_entry(finding, baseline);
+        if let Some(&known) = entries.iter().find(|&&known| known == entry) {
+            matched.insert(known);
+            if finding.acknowledged.is_none() {
+                finding.acknowledged = Some(Acknowledgement::Baseline);
+            }
+        }
+    }
+    report.stale = entries.into_iter().filter(|entry| !matched.contains(entry)).map(String::from).collect();
+    Ok(())
+}
+
+/// Writes every call site not already acknowledged by a comment into the
+/// baseline for `root`, replacing it, and returns how many entries it holds
+pub fn update_baseline(root: &Path, report: &LintReport) -> io::Result<usize> {
+    let baseline = baseline_path(root);
+    let mut entries: Vec<String> = report.findings.iter()
+        .filter(|finding| !matches!(finding.acknowledged, Some(Acknowledgement::Comment(_))))
+        .map(|finding| baseline_entry(finding, &baseline))
+        .collect();
+    entries.dedup();
+    let mut text = String::from("# unwrap() and expect() call sites known about, as path:line.\n");
+    text.push_str("# Fix one and its line goes stale; `unwrap lint --update-baseline` rewrites this file.\n");
+    for entry in &entries {
+        text.push_str(entry);
+        text.push('\n');
+    }
+    fs::write(&baseline, text)?;
+    Ok(entries.len())
+}
+
+/// Not every unwrap is equal. A call site's risk starts at 1 (0.8 for
+/// expect, which names its invariant), gains each keyword's weight found
+/// in its receiver chain, and is scaled down where a panic costs less:
+/// a literal receiver, test code, a const fn, build.rs and the main of an
+/// example. Keywords match identifiers, so `env::var` needs both in a row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Weights {
+    pub keywords: Vec<(String, f64)>,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        let keywords = [
+            ("File", 3.0), ("TcpStream", 3.0), ("connect", 2.5), ("read_to_string", 2.0), ("env::var", 2.0),
+            ("stdin", 2.0), ("recv", 2.0), ("parse", 1.5), ("lock", 1.5), ("get", 0.5),
+        ];
+        Weights { keywords: keywords.iter().map(|&(keyword, weight)| (keyword.to_string(), weight)).collect() }
+    }
+}
+
+impl Weights {
+    pub fn risk(&self, finding: &Finding) -> f64 {
+        if finding.local_type.is_some() {
+            return 0.0;
+        }
+        let base = match finding.kind {
+            CallKind::Unwrap => 1.0,
+            CallKind::Expect => 0.8,
+            CallKind::Todo => 1.5, // no input makes it pass
+        };
+        let boost: f64 = self.keywords.iter()
+            .filter(|(keyword, _)| {
+                let parts: Vec<&str> = keyword.split("::").collect();
+                finding.receiver.windows(parts.len()).any(|names| names.iter().zip(&parts).all(|(a, b)| a == b))
+            })
+            .map(|(_, weight)| weight)
+            .sum();
+        let tooling = finding.path.file_name().is_some_and(|name| name == "build.rs")
+            || (finding.context == Context::Main && finding.path.components().any(|part| part.as_os_str() == "examples"));
+        let context = match finding.context {
+            _ if tooling => 0.25,
+            Context::Library | Context::Main => 1.0,
+            Context::Test => 0.1,
+            Context::ConstFn => 0.05,
+        };
+        let literal = if finding.literal_receiver { 0.2 } else { 1.0 };
+        base * (1.0 + boost) * context * literal
+    }
+    
+    /// Reads a weights file: the defaults, with a `[keywords]` table of
+    /// `keyword = weight` lines on top. A subset of TOML - tables, bare or
+    /// quoted keys, numbers and # comments - and nothing more.
+    ///
+    /// ```toml
+    /// [keywords]
+    /// "env::var" = 4.0   # config errors are crashes at startup
+    /// get = 0            # 0 turns a default off
+    /// Mutex = 1.5
+    /// ```
+    pub fn parse(text: &str) -> Result<Weights, ConfigError> {
+        let mut weights = Weights::default();
+        let mut in_keywords = false;
+        for (index, line) in text.lines().enumerate() {
+            let error = |message: String| ConfigError::Syntax { line: index + 1, message };
+            // A # starts a comment unless it is inside a quoted key
+            let mut quoted = false;
+            let comment = line.char_indices().find(|&(_, c)| {
+                quoted ^= c == '"';
+                c == '#' && !quoted
+            });
+            let line = comment.map_or(line, |(at, _)| &line[..at]).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(table) = line.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+                if table.trim() != "keywords" {
+                    return Err(error(format!("unknown table [{}], only [keywords] is read", table.trim())));
+                }
+                in_keywords = true;
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(error(format!("expected `keyword = weight`, found `{}`", line)));
+            };
+            if !in_keywords {
+                return Err(error(String::from("keyword weights go under a [keywords] table")));
+            }
+            let key = key.trim();
+            let key = key.strip_prefix('"').and_then(|key| key.strip_suffix('"')).unwrap_or(key);
+            let weight: f64 = value.trim().parse()
+                .map_err(|_| error(format!("`{}` isn't a number", value.trim())))?;
+            match weights.keywords.iter_mut().find(|(keyword, _)| keyword == key) {
+                Some((_, existing)) => *existing = weight,
+                None => weights.keywords.push((key.to_string(), weight)),
+            }
+        }
+        Ok(weights)
+    }
+    
+    pub fn load(path: &Path) -> Result<Weights, ConfigError> {
+        Weights::parse(&fs::read_to_string(path).map_err(ConfigError::Io)?)
+    }
+}
+
+/// Why a weights file couldn't be used
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Syntax { line: usize, message: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "{}", e),
+            ConfigError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Walks `root` - a directory, or a single file - and scans every `.rs`
+/// file in it. Hidden directories and `target` are skipped. A file that
+/// isn't UTF-8 is read lossily rather than refused. Call sites in the
+/// baseline beside `root` come back acknowledged.
+pub fn scan_path(root: &Path) -> io::Result<LintReport> {
+    let mut paths = Vec::new();
+    if root.is_dir() {
+        collect_rust_files(root, &mut paths)?;
+    } else {
+        paths.push(root.to_path_buf());
+    }
+    
+    let mut report = LintReport::default();
+    let mut crate_roots: HashMap<PathBuf, Option<PathBuf>> = HashMap::new();
+    for path in paths {
+        let bytes = fs::read(&path)?;
+        let source = String::from_utf8_lossy(&bytes);
+        let findings = scan_source(&path, &source);
+        let count = |kind| findings.iter().filter(|finding| finding.kind == kind).count();
+        report.files.push(FileStats {
+            lines: source.lines().count(),
+            unwraps: count(CallKind::Unwrap),
+            expects: count(CallKind::Expect),
+            todos: count(CallKind::Todo),
+            risk: 0.0,
+            crate_root: path.parent().and_then(|dir| crate_root(dir, &mut crate_roots)),
+            path,
+        });
+        report.findings.extend(findings);
+    }
+    apply_baseline(&mut report, &baseline_path(root))?;
+    Ok(report)
+}
+
+/// The nearest of `dir` and its ancestors holding a Cargo.toml, remembered per directory
+fn crate_root(dir: &Path, known: &mut HashMap<PathBuf, Option<PathBuf>>) -> Option<PathBuf> {
+    if let Some(root) = known.get(dir) {
+        return root.clone();
+    }
+    let root = if dir.join("Cargo.toml").is_file() {
+        // `src` in a relative walk has "" for a parent: the current directory
+        Some(if dir.as_os_str().is_empty() { PathBuf::from(".") } else { dir.to_path_buf() })
+    } else {
+        dir.parent().and_then(|parent| crate_root(parent, known))
+    };
+    known.insert(dir.to_path_buf(), root.clone());
+    root
+}
+
+fn collect_rust_files(dir: &Path, paths: &mut Vec<PathBuf>) -> io::Result<()> {
+    let mut entries = fs::read_dir(dir)?.collect::<io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if entry.file_type()?.is_dir() {
+            if !name.starts_with('.') && name != "target" {
+                collect_rust_files(&path, paths)?;
+            }
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Every `.unwrap()` and `.expect(` in `source` that is code, in order,
+/// each with the context it sits in
+///
+/// Context is tracked without a parser, from attributes, `fn`, `impl` and
+/// braces: `#[test]`, `#[cfg(test)]` and `#![cfg(test)]` mark the next item
+/// (or the whole module) as test code, as does a path under `tests/` or
+/// `benches/`. Telling a local `fn unwrap(&self)` from Option's is
+/// best-effort: only a receiver that is `self`, or a variable whose `let`
+/// names the type (`let x: Foo`, `let x = Foo::new()` or `let x = Foo;`), is resolved -
+/// and bindings are per file, not per scope.
+pub fn scan_source(path: &Path, source: &str) -> Vec<Finding> {
+    let lines: Vec<&str> = source.lines().collect();
+    let (tokens, acknowledgements) = tokenize(source);
+    let in_test_dir = path.components().any(|part| matches!(part.as_os_str().to_str(), Some("tests" | "benches")));
+    let file_context = if in_test_dir { Context::Test } else { Context::Library };
+    
+    let mut scopes = vec![Scope { context: file_context, impl_type: None, nesting: 0, function: None }];
+    let mut nesting = 0; // ( and [ deep, inside the innermost brace
+    // What the next `{` opens, and where a `;` means it never came: `#[cfg(test)] use x;`
+    let mut pending_context = Context::Library;
+    let mut pending_impl: Option<String> = None;
+    let mut pending_fn: Option<Function> = None;
+    let mut pending_at = (0, 0);
+    let mut bindings: HashMap<String, String> = HashMap::new();
+    let mut local_unwraps: HashSet<String> = HashSet::new();
+    let mut findings = Vec::new();
+    
+    let ident = |i: usize| match tokens.get(i) {
+        Some(Token { kind: TokenKind::Ident(name), .. }) => Some(name.as_str()),
+        _ => None,
+    };
+    let punct = |i: usize, c: char| tokens.get(i).is_some_and(|token| token.kind == TokenKind::Punct(c));
+    
+    let mut i = 0;
+    while let Some(token) = tokens.get(i) {
+        let current = scopes.len() - 1; // the file scope is never popped
+        let scope = &scopes[current];
+        match &token.kind {
+            TokenKind::Punct('#') => {
+                let inner = punct(i + 1, '!');
+                let open = if inner { i + 2 } else { i + 1 };
+                if punct(open, '[') {
+                    let close = matching_close(&tokens, open, '[', ']').unwrap_or(tokens.len());
+                    let names: Vec<&str> = (open..close).filter_map(ident).collect();
+                    let test = match names.first() {
+                        Some(&"cfg") => names.contains(&"test") && !names.contains(&"not"),
+                        _ => names.last() == Some(&"test"), // #[test], #[tokio::test]
+                    };
+                    if test && inner {
+                        scopes[current].context = Context::Test;
+                    } else if test {
+                        pending_context = Context::Test;
+                        pending_at = (scopes.len(), nesting);
+                    }
+                    i = close + 1;
+                    continue;
+                }
+            }
+            TokenKind::Ident(word) if word == "fn" => {
+                let mut before = i;
+                while before > 0
+                    && (matches!(ident(before - 1), Some("unsafe" | "async" | "extern"))
+                        || tokens[before - 1].kind == TokenKind::Literal)
+                {
+                    before -= 1;
+                }
+                let name = ident(i + 1);
+                if before > 0 && ident(before - 1) == Some("const") {
+                    pending_context = pending_context.max(Context::ConstFn);
+                } else if name == Some("main") && scopes.len() == 1 {
+                    pending_context = pending_context.max(Context::Main);
+                }
+                if let (Some("unwrap" | "expect"), Some(impl_type)) = (name, &scope.impl_type) {
+                    local_unwraps.insert(impl_type.clone());
+                }
+                if let Some(name) = name {
+                    pending_fn = Some(Function { name: name.to_string(), returns: returns(&tokens, i + 2) });
+                }
+                pending_at = (scopes.len(), nesting);
+            }
+            TokenKind::Ident(word) if word == "impl" && nesting == 0 && !(i > 0 && matches!(
+                &tokens[i - 1].kind, TokenKind::Punct(':' | '>' | '&' | ',' | '(' | '<' | '='))) => {
+                pending_impl = impl_type(&tokens, i + 1);
+                pending_at = (scopes.len(), nesting);
+            }
+            TokenKind::Ident(word) if word == "let" => {
+                let name = if ident(i + 1) == Some("mut") { i + 2 } else { i + 1 };
+                let bound = match tokens.get(name + 1).map(|token| &token.kind) {
+                    Some(TokenKind::Punct(':')) => ident(name + 2),
+                    Some(TokenKind::Punct('=')) if [':', '{', '(', ';'].iter().any(|&c| punct(name + 3, c)) => {
+                        ident(name + 2)
+                    }
+                    _ => None,
+                };
+                if let (Some(variable), Some(bound)) = (ident(name), bound) {
+                    if bound.starts_with(char::is_uppercase) {
+                        bindings.insert(variable.to_string(), bound.to_string());
+                    }
+                }
+            }
+            TokenKind::Ident(word) if punct(i.wrapping_sub(1), '.') && (word == "unwrap" || word == "expect") => {
+                let kind = match word.as_str() {
+                    "unwrap" if punct(i + 1, '(') && punct(i + 2, ')') => Some(CallKind::Unwrap),
+                    "expect" if punct(i + 1, '(') => Some(CallKind::Expect),
+                    _ => None,
+                };
+                if let Some(kind) = kind {
+                    // A plain variable or self right before the dot: a receiver we might know the type of
+                    let receiver = ident(i.wrapping_sub(2))
+                        .filter(|_| !(i >= 3 && (punct(i - 3, '.') || punct(i - 3, ':'))));
+                    let local_type = match receiver {
+                        Some("self") => scope.impl_type.clone(),
+                        Some(variable) => bindings.get(variable).cloned(),
+                        None => None,
+                    };
+                    let (receiver_names, literal_receiver) = receiver_chain(&tokens, i - 1);
+                    let line = lines.get(token.line - 1).copied().unwrap_or("");
+                    let suggestion = suggest(&tokens, i, kind, line, scope, &receiver_names);
+                    let code = line.trim().to_string();
+                    findings.push(Finding {
+                        path: path.to_path_buf(),
+                        line: token.line,
+                        column: token.column,
+                        kind,
+                        code,
+                        context: scope.context,
+                        local_type,
+                        receiver: receiver_names,
+                        acknowledged: acknowledgements.iter()
+                            .find(|(line, _)| *line == token.line || *line + 1 == token.line)
+                            .map(|(_, reason)| Acknowledgement::Comment(reason.clone())),
+                        literal_receiver,
+                        risk: 0.0,
+                        suggestion,
+                    });
+                }
+            }
+            TokenKind::Ident(word) if (word == "todo" || word == "unimplemented")
+                && punct(i + 1, '!') && !punct(i.wrapping_sub(1), '.') => {
+                let line = lines.get(token.line - 1).copied().unwrap_or("");
+                findings.push(Finding {
+                    path: path.to_path_buf(),
+                    line: token.line,
+                    column: token.column,
+                    kind: CallKind::Todo,
+                    code: line.trim().to_string(),
+                    context: scope.context,
+                    local_type: None,
+                    receiver: Vec::new(),
+                    acknowledged: acknowledgements.iter()
+                        .find(|(line, _)| *line == token.line || *line + 1 == token.line)
+                        .map(|(_, reason)| Acknowledgement::Comment(reason.clone())),
+                    literal_receiver: false,
+                    risk: 0.0,
+                    suggestion: None, // there is no value to propagate, only a feature to write
+                });
+            }
+            TokenKind::Punct('{') => {
+                let context = scope.context.max(pending_context);
+                let impl_type = pending_impl.take().or_else(|| scope.impl_type.clone());
+                let closure = punct(i.wrapping_sub(1), '|') || matches!(ident(i.wrapping_sub(1)), Some("async" | "move"));
+                let function = match pending_fn.take() {
+                    Some(function) => Some(function),
+                    None if closure => None,
+                    None => scope.function.clone(),
+                };
+                scopes.push(Scope { context, impl_type, nesting, function });
+                pending_context = Context::Library;
+                nesting = 0;
+            }
+            TokenKind::Punct('}') if scopes.len() > 1 => {
+                nesting = scopes.pop().map_or(0, |closed| closed.nesting);
+            }
+            TokenKind::Punct('(' | '[') => nesting += 1,
+            TokenKind::Punct(')' | ']') => nesting = nesting.saturating_sub(1),
+            TokenKind::Punct(';') if pending_at == (scopes.len(), nesting) => {
+                pending_context = Context::Library;
+                pending_impl = None;
+                pending_fn = None;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    
+    // Only now is every impl in the file known
+    for finding in &mut findings {
+        finding.local_type = finding.local_type.take().filter(|name| local_unwraps.contains(name));
+        if finding.local_type.is_some() {
+            finding.suggestion = None;
+        }
+    }
+    findings
+}
+
+/// A brace scope: the context it is in, and the type of the impl it belongs to
+struct Scope {
+    context: Context,
+    impl_type: Option<String>,
+    nesting: usize,             // of the scope around it, where this one opened
+    function: Option<Function>, // whose body this is in; None in a closure or async block, where ? means something else
+}
+
+/// A fn, as far as `?` in its body is concerned
+#[derive(Debug, Clone)]
+struct Function {
+    name: String,
+    returns: Returns,
+}
+
+/// What a signature says a fn returns, as far as `?` cares
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Returns {
+    Unit,
+    IoResult,    // io::Result<T>, or Result<T, io::Error>
+    BoxedResult, // Result<T, Box<dyn Error>> or anyhow's: any std error converts
+    OtherResult, // some error type that an unwrapped error may not convert into
+    Option,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    Ident(String),
+    Punct(char),
+    Literal, // strings, chars, numbers: never a call site
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Token {
+    kind: TokenKind,
+    line: usize,
+    column: usize,
+}
+
+/// The comment that acknowledges an unwrap on its own line or the next
+const ACKNOWLEDGE: &str = "unwrap-ok:";
+
+/// The source as identifiers, punctuation and literals. Comments are
+/// dropped, except that each `// unwrap-ok: reason` comes back with its line.
+fn tokenize(source: &str) -> (Vec<Token>, Vec<(usize, String)>) {
+    let mut lexer = Lexer::new(source);
+    let mut tokens = Vec::new();
+    let mut acknowledgements = Vec::new();
+    while let Some(c) = lexer.peek(0) {
+        let (line, column) = (lexer.line, lexer.column);
+        let kind = if c == '/' && lexer.peek(1) == Some('/') {
+            let start = lexer.pos;
+            lexer.skip_line();
+            let comment: String = lexer.chars[start..lexer.pos].iter().collect();
+            if let Some(at) = comment.find(ACKNOWLEDGE) {
+                acknowledgements.push((line, comment[at + ACKNOWLEDGE.len()..].trim().to_string()));
+            }
+            continue;
+        } else if c == '/' && lexer.peek(1) == Some('*') {
+            lexer.skip_block_comment();
+            continue;
+        } else if c.is_whitespace() {
+            lexer.bump();
+            continue;
+        } else if c == '"' {
+            lexer.skip_string();
+            TokenKind::Literal
+        } else if c == '\'' {
+            lexer.skip_char_or_lifetime();
+            TokenKind::Literal
+        } else if c.is_ascii_digit() {
+            // `x.0.unwrap()`: a tuple index is a literal, so the dot before unwrap still counts
+            lexer.take_identifier();
+            TokenKind::Literal
+        } else if c.is_alphabetic() || c == '_' {
+            let ident = lexer.take_identifier();
+            if lexer.at_raw_or_byte_string(&ident) {
+                lexer.skip_raw_or_byte_string(&ident);
+                TokenKind::Literal
+            } else {
+                TokenKind::Ident(ident)
+            }
+        } else {
+            lexer.bump();
+            TokenKind::Punct(c)
+        };
+        tokens.push(Token { kind, line, column });
+    }
+    (tokens, acknowledgements)
+}
+
+/// The index of the `close` that closes the `open` at `at`, scanning forward
+fn matching_close(tokens: &[Token], at: usize, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().skip(at) {
+        if token.kind == TokenKind::Punct(open) {
+            depth += 1;
+        } else if token.kind == TokenKind::Punct(close) {
+            depth -= 1;
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+    }
+    None
+}
+
+/// Calls whose result is an io::Result, whoever they are called on
+const IO_RESULT_CALLS: [&str; 13] = [
+    "open", "create", "read_to_string", "read_to_end", "read_dir", "read_line", "write_all", "flush",
+    "connect", "bind", "metadata", "remove_file", "create_dir_all",
+];
+
+/// Types whose FromStr::Err is a std error, for `parse::<T>()`
+const PRIMITIVES: [&str; 15] = [
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32", "f64", "bool",
+];
+
+/// The `?` rewrite of the call whose method name is at `at`, and whether it
+/// can be applied blind. Safe means every one of these holds, judged from
+/// tokens alone - anything it can't see stays a suggestion:
+/// - it's unwrap() (expect()'s message would be lost) on a line of its own
+///   making, ending the statement, outside any closure or const fn
+/// - the fn around it returns io::Result and the call is I/O, or returns
+///   Result<T, Box<dyn Error>> and the call is I/O or `parse::<primitive>()`
+fn suggest(
+    tokens: &[Token],
+    at: usize,
+    kind: CallKind,
+    line: &str,
+    scope: &Scope,
+    receiver: &[String],
+) -> Option<Suggestion> {
+    let dot = &tokens[at - 1];
+    let close = match kind {
+        CallKind::Unwrap => at + 2,
+        CallKind::Expect => matching_close(tokens, at + 1, '(', ')')?,
+        CallKind::Todo => return None,
+    };
+    if tokens[close].line != dot.line {
+        return None;
+    }
+    let chars: Vec<char> = line.chars().collect();
+    let span = (dot.column, tokens[close].column + 1);
+    let after: String = chars[..span.0 - 1].iter().chain(['?'].iter()).chain(&chars[span.1 - 1..]).collect();
+    
+    let statement_start = tokens[..at].iter().rposition(|token| matches!(token.kind, TokenKind::Punct(';' | '{' | '}')));
+    let in_closure = tokens[statement_start.map_or(0, |start| start + 1)..at].iter()
+        .any(|token| token.kind == TokenKind::Punct('|'));
+    let last_call = receiver.last().map(String::as_str);
+    let io = last_call.is_some_and(|call| IO_RESULT_CALLS.contains(&call))
+        && receiver.iter().any(|name| IO_CALLS.contains(&name.as_str()));
+    let parses_primitive = last_call == Some("parse") && at >= 8
+        && tokens[at - 2].kind == TokenKind::Punct(')')
+        && tokens[at - 4].kind == TokenKind::Punct('>')
+        && matches!(&tokens[at - 5].kind, TokenKind::Ident(name) if PRIMITIVES.contains(&name.as_str()));
+    
+    let note = match &scope.function {
+        _ if kind == CallKind::Expect => {
+            Some(String::from("expect()'s message would be lost; carry it as context on the error instead"))
+        }
+        None => Some(String::from("inside a closure or async block, ? returns from that instead")),
+        _ if in_closure => Some(String::from("inside a closure, ? returns from that instead")),
+        _ if scope.context == Context::ConstFn => Some(String::from("? isn't allowed in a const fn")),
+        Some(function) if function.returns == Returns::Unit => {
+            Some(format!("fn {} returns (), so its signature has to change to return a Result", function.name))
+        }
+        _ if tokens.get(close + 1).map(|token| &token.kind) != Some(&TokenKind::Punct(';')) => {
+            Some(String::from("not a whole statement; check the expression around it still reads right"))
+        }
+        Some(function) => match function.returns {
+            Returns::IoResult if io => None,
+            Returns::BoxedResult if io || parses_primitive => None,
+            Returns::Option => Some(format!("fn {} returns an Option; ? works only if this is one too", function.name)),
+            _ => Some(format!("the error may not convert into what fn {} returns", function.name)),
+        },
+    };
+    Some(Suggestion { span, before: line.to_string(), after, safe: note.is_none(), note })
+}
+
+/// What the fn whose signature starts at `start` (after its name) returns
+fn returns(tokens: &[Token], start: usize) -> Returns {
+    // Find the -> outside the parameters and generics: `fn f<F: Fn() -> u8>(f: F) -> Result<u8>`
+    let mut depth = 0usize;
+    let mut arrow = None;
+    let mut i = start;
+    while let Some(token) = tokens.get(i) {
+        match token.kind {
+            TokenKind::Punct('-') if tokens.get(i + 1).is_some_and(|next| next.kind == TokenKind::Punct('>')) => {
+                if depth == 0 {
+                    arrow = Some(i + 2);
+                    break;
+                }
+                i += 1;
+            }
+            TokenKind::Punct('(' | '[' | '<') => depth += 1,
+            TokenKind::Punct(')' | ']' | '>') => depth = depth.saturating_sub(1),
+            TokenKind::Punct('{' | ';') if depth == 0 => break,
+            TokenKind::Ident(ref word) if word == "where" && depth == 0 => break,
+            _ => {}
+        }
+        i += 1;
+    }
+    let Some(arrow) = arrow else { return Returns::Unit };
+    
+    // The path before the first `<`, and the identifiers of the error argument after the top-level comma
+    let mut path = Vec::new();
+    let mut error = Vec::new();
+    let mut depth = 0usize;
+    let mut after_comma = false;
+    for token in &tokens[arrow..] {
+        match &token.kind {
+            TokenKind::Punct('<') => depth += 1,
+            TokenKind::Punct('>') => depth = depth.saturating_sub(1),
+            TokenKind::Punct(',') if depth == 1 => after_comma = true,
+            TokenKind::Punct('{' | ';') if depth == 0 => break,
+            TokenKind::Punct('(') if path.is_empty() && depth == 0 => return Returns::Unit, // -> ()
+            TokenKind::Ident(word) if word == "where" && depth == 0 => break,
+            TokenKind::Ident(word) if depth == 0 => path.push(word.as_str()),
+            TokenKind::Ident(word) if depth == 1 && after_comma => error.push(word.as_str()),
+            TokenKind::Ident(word) if depth > 1 && after_comma => error.push(word.as_str()),
+            _ => {}
+        }
+    }
+    match path.last() {
+        Some(&"Result") if path.contains(&"io") || error == ["io", "Error"] || error == ["std", "io", "Error"] => {
+            Returns::IoResult
+        }
+        Some(&"Result") if path.contains(&"anyhow") || (error.contains(&"Box") && error.contains(&"Error")) => {
+            Returns::BoxedResult
+        }
+        Some(&"Result") => Returns::OtherResult,
+        Some(&"Option") => Returns::Option,
+        _ => Returns::Other,
+    }
+}
+
+/// The identifiers of the expression a method is called on, in source order,
+/// from the `.` at `dot` back: `File::open(path).unwrap()` gives File, open.
+/// Arguments and turbofish types are skipped; a literal ends the chain, and
+/// the flag says whether one did.
+fn receiver_chain(tokens: &[Token], dot: usize) -> (Vec<String>, bool) {
+    let mut names = Vec::new();
+    let mut literal = false;
+    let mut j = dot;
+    while j > 0 {
+        j -= 1;
+        match &tokens[j].kind {
+            TokenKind::Punct('.' | ':' | '?' | '!') => {}
+            TokenKind::Punct(')') => match matching_open(tokens, j, '(', ')') {
+                Some(open) => j = open,
+                None => break,
+            },
+            TokenKind::Punct(']') => match matching_open(tokens, j, '[', ']') {
+                Some(open) => j = open,
+                None => break,
+            },
+            // `parse::<i32>`, but not `a > b.unwrap()`
+            TokenKind::Punct('>') => match matching_open(tokens, j, '<', '>') {
+                Some(open) if open > 0 && tokens[open - 1].kind == TokenKind::Punct(':') => j = open,
+                _ => break,
+            },
+            TokenKind::Ident(name) => {
+                names.push(name.clone());
+                let joined = j > 0 && matches!(tokens[j - 1].kind, TokenKind::Punct('.' | ':'));
+                if !joined {
+                    break;
+                }
+            }
+            // A tuple index: `pair.0.unwrap()`
+            TokenKind::Literal if j > 0 && tokens[j - 1].kind == TokenKind::Punct('.') => {}
+            TokenKind::Literal => {
+                literal = true;
+                break;
+            }
+            _ => break,
+        }
+    }
+    names.reverse();
+    (names, literal)
+}
+
+/// The index of the `open` that the `close` at `at` closes, scanning back
+fn matching_open(tokens: &[Token], at: usize, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    for j in (0..=at).rev() {
+        if tokens[j].kind == TokenKind::Punct(close) {
+            depth += 1;
+        } else if tokens[j].kind == TokenKind::Punct(open) {
+            depth -= 1;
+            if depth == 0 {
+                return Some(j);
+            }
+        }
+    }
+    None
+}
+
+/// The type an impl is for: `impl<T> Foo<T>` and `impl fmt::Display for Foo` are both Foo
+fn impl_type(tokens: &[Token], start: usize) -> Option<String> {
+    let mut depth = 0usize;
+    let mut last = None;
+    for token in &tokens[start.min(tokens.len())..] {
+        match &token.kind {
+            TokenKind::Punct('<') => depth += 1,
+            TokenKind::Punct('>') => depth = depth.saturating_sub(1),
+            TokenKind::Punct('{' | ';') => break,
+            TokenKind::Ident(name) if name == "where" => break,
+            TokenKind::Ident(name) if depth == 0 && name != "for" && name != "dyn" => last = Some(name.clone()),
+            _ => {}
+        }
+    }
+    last
+}
+
+/// Characters with their line and column, and the skips the scanner needs
+struct Lexer {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Lexer {
+    fn new(source: &str) -> Self {
+        Lexer { chars: source.chars().collect(), pos: 0, line: 1, column: 1 }
+    }
+    
+    fn peek(&self, ahead: usize) -> Option<char> {
+        self.chars.get(self.pos + ahead).copied()
+    }
+    
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek(0)?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+    
+    fn skip_line(&mut self) {
+        while self.peek(0).is_some_and(|c| c != '\n') {
+            self.bump();
+        }
+    }
+    
+    /// Block comments nest in Rust: /* /* */ */ is one comment
+    fn skip_block_comment(&mut self) {
+        let mut depth = 0;
+        while let Some(c) = self.bump() {
+            if c == '/' && self.peek(0) == Some('*') {
+                self.bump();
+                depth += 1;
+            } else if c == '*' && self.peek(0) == Some('/') {
+                self.bump();
+                depth -= 1;
+                if depth == 0 {
+                    return;
+                }
+            }
+        }
+    }
+    
+    /// From the opening quote past the closing one, escapes included
+    fn skip_string(&mut self) {
+        self.bump();
+        while let Some(c) = self.bump() {
+            match c {
+                '\\' => {
+                    self.bump();
+                }
+                '"' => return,
+                _ => {}
+            }
+        }
+    }
+    
+    /// 'x', '\n' and '\u{1F600}' are chars; 'a in &'a str is a lifetime
+    fn skip_char_or_lifetime(&mut self) {
+        self.bump();
+        if self.peek(0) == Some('\\') {
+            // The escaped character could be a quote itself: '\''
+            self.bump();
+            self.bump();
+            while self.bump().is_some_and(|c| c != '\'') {}
+        } else if self.peek(1) == Some('\'') {
+            self.bump();
+            self.bump();
+        }
+        // A lifetime: its name is read as an identifier next, harmlessly
+    }
+    
+    fn take_identifier(&mut self) -> String {
+        let mut ident = String::new();
+        while let Some(c) = self.peek(0).filter(|&c| c.is_alphanumeric() || c == '_') {
+            ident.push(c);
+            self.bump();
+        }
+        ident
+    }
+    
+    /// Whether `prefix`, just read, starts a literal: b"..", b'.', r"..", r#".."#, br"..", c".."
+    fn at_raw_or_byte_string(&self, prefix: &str) -> bool {
+        match prefix {
+            "b" | "c" => matches!(self.peek(0), Some('"') | Some('\'')),
+            "r" | "br" | "cr" => match self.peek(0) {
+                Some('"') => true,
+                Some('#') => {
+                    let hashes = self.chars[self.pos..].iter().take_while(|&&c| c == '#').count();
+                    self.peek(hashes) == Some('"')
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+    
+    fn skip_raw_or_byte_string(&mut self, prefix: &str) {
+        if !prefix.ends_with('r') {
+            match self.peek(0) {
+                Some('\'') => self.skip_char_or_lifetime(),
+                _ => self.skip_string(),
+            }
+            return;
+        }
+        // No escapes in a raw string: it ends at a quote with as many #s as it opened with
+        let mut hashes = 0;
+        while self.peek(0) == Some('#') {
+            hashes += 1;
+            self.bump();
+        }
+        self.bump();
+        while let Some(c) = self.bump() {
+            if c == '"' && (0..hashes).all(|i| self.peek(i) == Some('#')) {
+                for _ in 0..hashes {
+                    self.bump();
+                }
+                return;
+            }
+        }
+    }
+}
+
+/// One line per call site, then the density of each file and the total.
+/// Unless strict, the call sites are grouped by context, local unwrap
+/// methods last, and only those outside tests and const fns fail the run.
+/// Acknowledged call sites come after the rest, then stale baseline entries.
+pub fn write_report(out: &mut dyn Write, report: &LintReport, policy: &Policy) -> io::Result<()> {
+    let open = || report.findings.iter().filter(|finding| finding.acknowledged.is_none());
+    if policy.strict {
+        write_findings(out, open(), policy)?;
+    } else {
+        let foreign = |finding: &&Finding| finding.local_type.is_none();
+        for context in Context::ALL {
+            let mut findings = open().filter(foreign).filter(|finding| finding.context == context).peekable();
+            if findings.peek().is_some() {
+                let verdict = if context.exempt() { "exempt" } else { "fails the run" };
+                writeln!(out, "\n=== In {} code ({}) ===", context.name(), verdict)?;
+                write_findings(out, findings, policy)?;
+            }
+        }
+        let mut local = open().filter(|finding| finding.local_type.is_some()).peekable();
+        if local.peek().is_some() {
+            writeln!(out, "\n=== Local unwrap methods, not Option or Result (exempt) ===")?;
+            write_findings(out, local, policy)?;
+        }
+    }
+    
+    if report.acknowledged() > 0 {
+        writeln!(out, "\n=== Acknowledged ===")?;
+        for finding in &report.findings {
+            let why = match &finding.acknowledged {
+                Some(Acknowledgement::Comment(reason)) => format!("unwrap-ok: {}", reason),
+                Some(Acknowledgement::Baseline) => String::from("in the baseline"),
+                None => continue,
+            };
+            writeln!(out, "{}:{}:{}: {}  {}  ({})", finding.path.display(), finding.line, finding.column,
+                          finding.kind.call(), finding.code, why)?;
+        }
+    }
+    if !report.stale.is_empty() {
+        writeln!(out, "\n=== Stale baseline entries: nothing there now, remove them ===")?;
+        for entry in &report.stale {
+            writeln!(out, "  {}", entry)?;
+        }
+    }
+    
+    writeln!(out, "\n=== Unwrap density ===")?;
+    let width = report.files.iter().map(|file| file.path.display().to_string().len()).max().unwrap_or(0);
+    for file in report.files.iter().filter(|file| file.sites() > 0) {
+        writeln!(out, "  {:<width$}  {:>4} unwrap()  {:>4} expect()  {:>4} todo!()  {:>6} lines  {:>6.1} per 1000 lines  risk {:>6.1}",
+                      file.path.display().to_string(), file.unwraps, file.expects, file.todos, file.lines,
+                      file.per_thousand_lines(), file.risk)?;
+    }
+    let clean = report.files.iter().filter(|file| file.sites() == 0).count();
+    writeln!(out, "\nTotal: {} call sites in {} files ({} without any), {} lines - {:.1} per 1000 lines",
+                  report.findings.len(), report.files.len(), clean, report.lines(), report.per_thousand_lines())?;
+    if report.acknowledged() > 0 {
+        writeln!(out, "{} of them acknowledged, which never fails the run", report.acknowledged())?;
+    }
+    if !policy.strict {
+        write!(out, "Tests, const fns and local unwrap methods are exempt. ")?;
+    }
+    writeln!(out, "{} of them fail the run (level {} or above)", report.failing(policy), policy.fail_level.name())?;
+    write_risk(out, report)
+}
+
+/// The riskiest call sites, then the risk of each crate
+fn write_risk(out: &mut dyn Write, report: &LintReport) -> io::Result<()> {
+    let riskiest = report.riskiest(10);
+    if riskiest.is_empty() {
+        return Ok(());
+    }
+    writeln!(out, "\n=== The {} riskiest call sites ===", riskiest.len())?;
+    for finding in riskiest {
+        writeln!(out, "  {:>5.1}  {}:{}  {}", finding.risk, finding.path.display(), finding.line, finding.code)?;
+    }
+    writeln!(out)?;
+    for (root, risk) in report.crate_risks() {
+        let name = root.map_or_else(|| String::from("files outside any crate"), |root| format!("the crate at {}", root.display()));
+        writeln!(out, "Risk of {}: {:.1}", name, risk)?;
+    }
+    Ok(())
+}
+
+fn write_findings<'a>(
+    out: &mut dyn Write,
+    findings: impl Iterator<Item = &'a Finding>,
+    policy: &Policy,
+) -> io::Result<()> {
+    for finding in findings {
+        writeln!(out, "{}:{}:{}: {}: {}  {}", finding.path.display(), finding.line, finding.column,
+                      finding.level(policy).name(), finding.kind.call(), finding.code)?;
+    }
+    Ok(())
+}
+
+/// The report as a SARIF 2.1.0 log, for code scanning: one rule per kind of
+/// finding, and each result at its level under `policy`
+pub fn to_sarif(report: &LintReport, policy: &Policy) -> String {
+    let rules: Vec<String> = Rule::ALL.iter()
+        .map(|rule| {
+            Object::new()
+                .field("id", json::string(rule.id()))
+                .field("shortDescription", Object::new().field("text", json::string(rule.description())).finish())
+                .field("defaultConfiguration", Object::new()
+                    .field("level", json::string(rule.default_level().name()))
+                    .finish())
+                .finish()
+        })
+        .collect();
+    let results: Vec<String> = report.findings.iter()
+        .map(|finding| {
+            let rule = finding.rule();
+            let message = format!("{} in {} code: {}", finding.kind.call(), finding.context.name(), finding.code);
+            let region = Object::new()
+                .field("startLine", finding.line.to_string())
+                .field("startColumn", finding.column.to_string())
+                .finish();
+            let location = Object::new()
+                .field("physicalLocation", Object::new()
+                    .field("artifactLocation", Object::new().field("uri", json::string(&uri(&finding.path))).finish())
+                    .field("region", region)
+                    .finish())
+                .finish();
+            // Acknowledged call sites are still results, but suppressed ones
+            let suppressions = match &finding.acknowledged {
+                None => String::from("[]"),
+                Some(Acknowledgement::Comment(reason)) => format!("[{}]", Object::new()
+                    .field("kind", json::string("inSource"))
+                    .field("justification", json::string(reason))
+                    .finish()),
+                Some(Acknowledgement::Baseline) => format!("[{}]", Object::new()
+                    .field("kind", json::string("external"))
+                    .field("justification", json::string(BASELINE_FILE))
+                    .finish()),
+            };
+            Object::new()
+                .field("ruleId", json::string(rule.id()))
+                .field("ruleIndex", Rule::ALL.iter().position(|&r| r == rule).unwrap_or(0).to_string())
+                .field("level", json::string(finding.level(policy).name()))
+                .field("message", Object::new().field("text", json::string(&message)).finish())
+                .field("locations", format!("[{}]", location))
+                .field("suppressions", suppressions)
+                .finish()
+        })
+        .collect();
+    let driver = Object::new()
+        .field("name", json::string("unwrap lint"))
+        .field("version", json::string(env!("CARGO_PKG_VERSION")))
+        .field("rules", format!("[{}]", rules.join(",")))
+        .finish();
+    let run = Object::new()
+        .field("tool", Object::new().field("driver", driver).finish())
+        .field("results", format!("[{}]", results.join(",")))
+        .finish();
+    Object::new()
+        .field("$schema", json::string("https://json.schemastore.org/sarif-2.1.0.json"))
+        .field("version", json::string("2.1.0"))
+        .field("runs", format!("[{}]", run))
+        .finish()
+}
+
+/// A relative URI with forward slashes, as code scanning matches them to the repository
+fn uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    path.strip_prefix("./").unwrap_or(&path).to_string()
+}
+
+/// Every suggestion as a unified diff of its one line, each headed by its
+/// location and whether --fix would apply it
+pub fn write_suggestions(out: &mut dyn Write, report: &LintReport) -> io::Result<()> {
+    let suggested: Vec<(&Finding, &Suggestion)> = report.findings.iter()
+        .filter_map(|finding| finding.suggestion.as_ref().map(|suggestion| (finding, suggestion)))
+        .collect();
+    for &(finding, suggestion) in &suggested {
+        let verdict = match &suggestion.note {
+            None => String::from("--fix applies this"),
+            Some(note) => format!("suggestion only: {}", note),
+        };
+        writeln!(out, "{}:{}:{}: {}", finding.path.display(), finding.line, finding.column, verdict)?;
+        write_diff(out, finding, suggestion)?;
+    }
+    let safe = suggested.iter().filter(|(_, suggestion)| suggestion.safe).count();
+    writeln!(out, "\n{} of {} call sites have a ? rewrite; --fix would apply {} of them",
+                  suggested.len(), report.findings.len(), safe)
+}
+
+fn write_diff(out: &mut dyn Write, finding: &Finding, suggestion: &Suggestion) -> io::Result<()> {
+    let path = uri(&finding.path);
+    let separator = if path.starts_with('/') { "" } else { "/" };
+    writeln!(out, "--- a{}{}\n+++ b{}{}\n@@ -{} +{} @@", separator, path, separator, path, finding.line, finding.line)?;
+    writeln!(out, "-{}\n+{}", suggestion.before, suggestion.after)
+}
+
+/// Applies every safe suggestion in place and returns the call sites it
+/// rewrote. A file that isn't UTF-8 is left alone: writing it back from a
+/// lossy read would change more than the call.
+pub fn apply_fixes(report: &LintReport) -> io::Result<Vec<Finding>> {
+    let mut fixed = Vec::new();
+    for file in &report.files {
+        let mut fixes: Vec<(&Finding, &Suggestion)> = report.findings.iter()
+            .filter(|finding| finding.path == file.path)
+            .filter(|finding| finding.acknowledged.is_none()) // known about, and meant to stay
+            .filter_map(|finding| {
+                let suggestion = finding.suggestion.as_ref().filter(|suggestion| suggestion.safe)?;
+                Some((finding, suggestion))
+            })
+            .collect();
+        if fixes.is_empty() {
+            continue;
+        }
+        let Ok(source) = String::from_utf8(fs::read(&file.path)?) else { continue };
+        
+        // Right to left, so the columns of the fixes still to come stay put
+        fixes.sort_by_key(|(finding, _)| Reverse((finding.line, finding.column)));
+        let mut lines: Vec<String> = source.split_inclusive('\n').map(String::from).collect();
+        for (finding, suggestion) in &fixes {
+            let line = &mut lines[finding.line - 1];
+            let chars: Vec<char> = line.chars().collect();
+            let (start, end) = suggestion.span;
+            *line = chars[..start - 1].iter().chain(['?'].iter()).chain(&chars[end - 1..]).collect();
+        }
+        fs::write(&file.path, lines.concat())?;
+        fixed.extend(fixes.iter().rev().map(|&(finding, _)| finding.clone()));
+    }
+    Ok(fixed)
+}
+
+/// What --fix did, and what is left after it
+pub fn write_fixes(out: &mut dyn Write, fixed: &[Finding], remaining: &LintReport, policy: &Policy) -> io::Result<()> {
+    for finding in fixed {
+        if let Some(suggestion) = &finding.suggestion {
+            writeln!(out, "{}:{}:{}: fixed", finding.path.display(), finding.line, finding.column)?;
+            write_diff(out, finding, suggestion)?;
+        }
+    }
+    let mut files: Vec<&Path> = fixed.iter().map(|finding| finding.path.as_path()).collect();
+    files.dedup();
+    writeln!(out, "\nFixed {} call sites in {} files. {} remain, {} of them fail the run (level {} or above)",
+                  fixed.len(), files.len(), remaining.findings.len(), remaining.failing(policy), policy.fail_level.name())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn scan(source: &str) -> Vec<Finding> {
+        scan_source(Path::new("src/lib.rs"), source)
+    }
+    
+    /// A directory of source files in the temp dir, removed when dropped
+    struct Fixture(PathBuf);
+    
+    impl Fixture {
+        fn new(name: &str, files: &[(&str, &[u8])]) -> Self {
+            let root = std::env::temp_dir().join(format!("unwrap-lint-{}-{}", std::process::id(), name));
+            let _ = fs::remove_dir_all(&root);
+            for (path, contents) in files {
+                let path = root.join(path);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, contents).unwrap();
+            }
+            Fixture(root)
+        }
+    }
+    
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+    
+    #[test]
+    fn todo_and_unimplemented_are_reported_as_todo_in_lib() {
+        let findings = scan("fn a() -> u32 {\n    todo!()\n}\nfn b() {\n    std::unimplemented!(\"later\");\n}\n");
+        let sites: Vec<(usize, CallKind, Rule)> = findings.iter()
+            .map(|finding| (finding.line, finding.kind, finding.rule()))
+            .collect();
+        assert_eq!(sites, vec![(2, CallKind::Todo, Rule::TodoInLib), (5, CallKind::Todo, Rule::TodoInLib)]);
+        assert!(findings.iter().all(|finding| finding.suggestion.is_none()));
+        assert_eq!(Rule::TodoInLib.default_level(), Level::Error);
+    }
+    
+    #[test]
+    fn todo_in_strings_comments_and_tests_is_not_library_code() {
+        let source = "// todo!() later\nfn a() -> &'static str {\n    \"todo!()\"\n}\n#[test]\nfn t() {\n    todo!()\n}\n";
+        let findings = scan(source);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].context, Context::Test);
+        assert_eq!(findings[0].level(&Policy { strict: false, fail_level: Level::Note }), Level::None);
+    }
+    
+    #[test]
+    fn fixture_tree_counts_only_calls_that_are_code() {
+        let tricky = b"// a.unwrap() in a comment
+/* b.unwrap() in a block /* nested */ comment */
+fn f(x: Option<u8>, y: Result<u8, ()>) -> u8 {
+    let _ = \"c.unwrap() in a string\";
+    let _ = r#\"d.expect(\"in a raw string\")\"#;
+    let _ = b'\\'';
+    println!(\"{}\", x.unwrap());
+    assert!(y.expect(\"checked\") > 0, \"e.unwrap() {}\", y.unwrap());
+    x.unwrap_or(0) + x.unwrap_or_default()
+}
+";
+        let fixture = Fixture::new("tree", &[
+            ("src/lib.rs", tricky),
+            ("src/bytes.rs", b"fn g(x: Option<u8>) -> u8 { let _ = \"\xff\xfe\"; x.unwrap() }\n"),
+            ("src/clean.rs", b"fn h() {}\n"),
+            ("src/notes.txt", b"x.unwrap()\n"),
+            ("target/debug/build.rs", b"fn i(x: Option<u8>) { x.unwrap(); }\n"),
+            (".hidden/lib.rs", b"fn j(x: Option<u8>) { x.unwrap(); }\n"),
+        ]);
+        let report = scan_path(&fixture.0).unwrap();
+        
+        let sites: Vec<(String, usize, CallKind)> = report.findings.iter()
+            .map(|finding| (finding.path.file_name().unwrap().to_string_lossy().into_owned(), finding.line, finding.kind))
+            .collect();
+        assert_eq!(sites, vec![
+            (String::from("bytes.rs"), 1, CallKind::Unwrap), // not UTF-8, read lossily
+            (String::from("lib.rs"), 7, CallKind::Unwrap),   // a macro argument is code
+            (String::from("lib.rs"), 8, CallKind::Expect),
+            (String::from("lib.rs"), 8, CallKind::Unwrap),
+        ]);
+        assert_eq!(report.findings[1].code, "println!(\"{}\", x.unwrap());");
+        assert_eq!(report.findings[1].column, 22);
+        
+        let files: Vec<(String, usize, usize, usize)> = report.files.iter()
+            .map(|file| (file.path.file_name().unwrap().to_string_lossy().into_owned(), file.lines, file.unwraps, file.expects))
+            .collect();
+        assert_eq!(files, vec![
+            (String::from("bytes.rs"), 1, 1, 0),
+            (String::from("clean.rs"), 1, 0, 0),
+            (String::from("lib.rs"), 10, 2, 1),
+        ]);
+        assert_eq!(report.lines(), 12);
+        assert!((report.per_thousand_lines() - 4000.0 / 12.0).abs() < 1e-9);
+        assert!((report.files[2].per_thousand_lines() - 300.0).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn non_strict_buckets_by_context_and_exempts_local_unwrap() {
+        let source = "struct Foo;
+impl Foo {
+    fn unwrap(&self) -> u8 { 0 }
+}
+const fn c(x: Option<u8>) -> u8 {
+    x.unwrap()
+}
+fn lib(x: Option<u8>) -> u8 {
+    let foo = Foo;
+    foo.unwrap() + x.unwrap()
+}
+fn main() {
+    Some(1).unwrap();
+}
+#[cfg(test)]
+mod tests {
+    fn helper(x: Option<u8>) -> u8 { x.unwrap() }
+    mod nested {
+        #[test]
+        fn t() { Some(2).expect(\"two\"); }
+    }
+}
+#[cfg(not(test))]
+fn after(x: Option<u8>) -> u8 { x.unwrap() }
+";
+        let findings = scan(source);
+        let sites: Vec<(usize, Context, Option<&str>)> = findings.iter()
+            .map(|finding| (finding.line, finding.context, finding.local_type.as_deref()))
+            .collect();
+        assert_eq!(sites, vec![
+            (6, Context::ConstFn, None),
+            (10, Context::Library, Some("Foo")), // Foo's own unwrap, not Option's
+            (10, Context::Library, None),
+            (13, Context::Main, None),
+            (17, Context::Test, None),
+            (20, Context::Test, None), // a test module nested in a test module
+            (24, Context::Library, None), // cfg(not(test)) is not test code
+        ]);
+        
+        let report = LintReport { findings, ..LintReport::default() };
+        let lenient = Policy { strict: false, fail_level: Level::Note };
+        assert_eq!(report.failing(&lenient), 3);
+        assert_eq!(report.failing(&Policy::default()), 7);
+        
+        let mut out = Vec::new();
+        write_report(&mut out, &report, &lenient).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let headings: Vec<&str> = out.lines().filter(|line| line.starts_with("===")).collect();
+        assert_eq!(headings, [
+            "=== In library code (fails the run) ===",
+            "=== In main code (fails the run) ===",
+            "=== In const fn code (exempt) ===",
+            "=== In test code (exempt) ===",
+            "=== Local unwrap methods, not Option or Result (exempt) ===",
+            "=== Unwrap density ===",
+        ]);
+        assert!(out.contains("src/lib.rs:20:26: none: expect()  fn t() { Some(2).expect(\"two\"); }"), "{}", out);
+    }
+    
+    #[test]
+    fn baseline_acknowledges_known_sites_and_reports_stale_entries() {
+        let source = b"fn f(x: Option<u8>) -> u8 {
+    // unwrap-ok: set by the caller
+    let a = x.unwrap();
+    let b = x.unwrap();
+    a + b + x.unwrap()
+}
+";
+        let fixture = Fixture::new("baseline", &[
+            ("src/lib.rs", source),
+            (BASELINE_FILE, b"# known\nsrc/lib.rs:4\nsrc/lib.rs:40  # fixed since\n"),
+        ]);
+        let report = scan_path(&fixture.0).unwrap();
+        let acknowledged: Vec<(usize, Option<Acknowledgement>)> = report.findings.iter()
+            .map(|finding| (finding.line, finding.acknowledged.clone()))
+            .collect();
+        assert_eq!(acknowledged, vec![
+            (3, Some(Acknowledgement::Comment(String::from("set by the caller")))),
+            (4, Some(Acknowledgement::Baseline)),
+            (5, None), // fresh
+        ]);
+        assert_eq!(report.stale, ["src/lib.rs:40"]);
+        // Acknowledged sites are still counted, but only the fresh one fails
+        assert_eq!((report.findings.len(), report.acknowledged(), report.failing(&Policy::default())), (3, 2, 1));
+        
+        let mut out = Vec::new();
+        write_report(&mut out, &report, &Policy::default()).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("=== Acknowledged ===") && out.contains("(unwrap-ok: set by the caller)"), "{}", out);
+        assert!(out.contains("=== Stale baseline entries: nothing there now, remove them ===\n  src/lib.rs:40\n"), "{}", out);
+        
+        // The comment already acknowledges line 3, so only 4 and 5 go in
+        assert_eq!(update_baseline(&fixture.0, &report).unwrap(), 2);
+        let baseline = fs::read_to_string(fixture.0.join(BASELINE_FILE)).unwrap();
+        assert!(baseline.ends_with("\nsrc/lib.rs:4\nsrc/lib.rs:5\n"), "{}", baseline);
+        let report = scan_path(&fixture.0).unwrap();
+        assert_eq!((report.acknowledged(), report.failing(&Policy::default())), (3, 0));
+        assert!(report.stale.is_empty());
+    }
+    
+    #[test]
+    fn fix_applies_only_safe_rewrites_and_round_trips() {
+        let source = b"use std::fs::File;
+use std::io;
+
+fn open(path: &str) -> io::Result<File> {
+    let file = File::open(path).unwrap();
+    Ok(file)
+}
+
+fn count(text: &str) -> Result<u32, Box<dyn std::error::Error>> {
+    let n = text.parse::<u32>().unwrap();
+    Ok(n)
+}
+
+fn quiet(path: &str) {
+    let _ = File::open(path).unwrap();
+}
+
+fn named(path: &str) -> io::Result<File> {
+    let file = File::open(path).expect(\"exists\");
+    Ok(file)
+}
+";
+        let fixture = Fixture::new("fix", &[("src/lib.rs", source)]);
+        let report = scan_path(&fixture.0).unwrap();
+        let notes: Vec<(usize, bool)> = report.findings.iter()
+            .map(|finding| (finding.line, finding.suggestion.as_ref().is_some_and(|suggestion| suggestion.safe)))
+            .collect();
+        assert_eq!(notes, vec![(5, true), (10, true), (15, false), (19, false)]);
+        let quiet = report.findings[2].suggestion.as_ref().and_then(|suggestion| suggestion.note.clone());
+        assert_eq!(quiet.as_deref(), Some("fn quiet returns (), so its signature has to change to return a Result"));
+        
+        let fixed = apply_fixes(&report).unwrap();
+        assert_eq!(fixed.iter().map(|finding| finding.line).collect::<Vec<_>>(), [5, 10]);
+        let path = fixture.0.join("src/lib.rs");
+        let after = fs::read_to_string(&path).unwrap();
+        let expected = String::from_utf8_lossy(source)
+            .replace("File::open(path).unwrap();\n    Ok", "File::open(path)?;\n    Ok")
+            .replace("parse::<u32>().unwrap()", "parse::<u32>()?");
+        assert_eq!(after, expected);
+        assert!(after.contains("let _ = File::open(path).unwrap();") && after.contains(".expect(\"exists\")"));
+        
+        // Scanned again, the file still reads as the same items, with only the
+        // suggestions-only left; a second --fix has nothing to do
+        let again = scan_path(&fixture.0).unwrap();
+        assert_eq!(again.findings.iter().map(|finding| finding.line).collect::<Vec<_>>(), [15, 19]);
+        assert!(again.findings.iter().all(|finding| finding.context == Context::Library));
+        assert!(apply_fixes(&again).unwrap().is_empty());
+        assert_eq!(fs::read_to_string(&path).unwrap(), expected);
+        
+        let mut out = Vec::new();
+        write_fixes(&mut out, &fixed, &again, &Policy::default()).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("-    let n = text.parse::<u32>().unwrap();\n+    let n = text.parse::<u32>()?;\n"), "{}", out);
+        assert!(out.ends_with("Fixed 2 call sites in 1 files. 2 remain, 2 of them fail the run (level note or above)\n"), "{}", out);
+    }
+    
+    #[test]
+    fn io_unwrap_outranks_a_literal_parse() {
+        let source = "fn f() {
+    let re = \"[0-9]+\".parse::<i32>().unwrap();
+    let file = std::fs::File::open(\"config\").unwrap();
+    let port = std::env::var(\"PORT\").expect(\"PORT is set\");
+}
+";
+        let mut report = LintReport { findings: scan(source), files: Vec::new(), stale: Vec::new() };
+        report.score(&Weights::default());
+        let risks: Vec<(usize, f64)> = report.findings.iter().map(|finding| (finding.line, finding.risk)).collect();
+        assert!(report.findings[0].literal_receiver);
+        // 1 × (1 + 1.5 for parse) × 0.2 for the literal; 1 × (1 + 3 for File); 0.8 × (1 + 2 for env::var)
+        let expected = [(2, 0.5), (3, 4.0), (4, 2.4)];
+        for ((line, risk), (want_line, want)) in risks.iter().zip(expected) {
+            assert_eq!(*line, want_line);
+            assert!((risk - want).abs() < 1e-9, "line {}: risk {}", line, risk);
+        }
+        let ranked: Vec<usize> = report.riskiest(10).iter().map(|finding| finding.line).collect();
+        assert_eq!(ranked, [3, 4, 2]);
+        
+        // Tuned: env::var matters more than files here, and get not at all
+        let weights = Weights::parse("# ours\n[keywords]\n\"env::var\" = 4.0  # startup config\nFile = 1\nget = 0\n").unwrap();
+        report.score(&weights);
+        assert_eq!(report.riskiest(1)[0].line, 4);
+        assert_eq!(weights.keywords.iter().find(|(keyword, _)| keyword == "get").map(|(_, weight)| *weight), Some(0.0));
+        
+        // Where a panic costs less, the same call scores less
+        let cheap = |path: &str| {
+            let finding = scan_source(Path::new(path), "fn main() {\n    std::fs::File::open(\"x\").unwrap();\n}\n").remove(0);
+            Weights::default().risk(&finding)
+        };
+        assert_eq!(cheap("src/main.rs"), 4.0);
+        assert_eq!(cheap("examples/demo.rs"), 1.0);
+        assert_eq!(cheap("build.rs"), 1.0);
+        
+        assert!(matches!(Weights::parse("get = 1\n"), Err(ConfigError::Syntax { line: 1, .. })));
+        assert!(matches!(Weights::parse("[keywords]\nget = lots\n"), Err(ConfigError::Syntax { line: 2, .. })));
+        
+        // Each file's risk adds up into its crate's
+        let fixture = Fixture::new("risk", &[
+            ("io/Cargo.toml", b"[package]\n"),
+            ("io/src/lib.rs", b"fn f() {\n    std::fs::File::open(\"x\").unwrap();\n}\n"),
+            ("pure/Cargo.toml", b"[package]\n"),
+            ("pure/src/a.rs", b"fn g(x: Option<u8>) -> u8 {\n    x.unwrap()\n}\n"),
+            ("pure/src/b.rs", b"fn h(x: Option<u8>) -> u8 {\n    x.unwrap()\n}\n"),
+        ]);
+        let mut report = scan_path(&fixture.0).unwrap();
+        report.score(&Weights::default());
+        let files: Vec<f64> = report.files.iter().map(|file| file.risk).collect();
+        assert_eq!(files, [4.0, 1.0, 1.0]);
+        let crates = report.crate_risks();
+        assert_eq!(crates, [(Some(fixture.0.join("io").as_path()), 4.0), (Some(fixture.0.join("pure").as_path()), 2.0)]);
+        assert_eq!(report.risk(), 6.0);
+    }
+}
@@ -1,1182 +1,167 @@
-use std::fs::File;
-use std::io::Read;
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::process::ExitCode;
 
-/// Example 1: Simple unwrap that panics
-fn divide(a: i32, b: i32) -> Option<i32> {
-    if b == 0 {
-        None
-    } else {
-        Some(a / b)
-    }
-}
-
-/// Example 2: Chained unwraps - the cascade effect
-fn parse_and_double(s: &str) -> i32 {
-    let num: i32 = s.parse().unwrap(); // First unwrap - can panic on invalid string
-    let doubled = divide(num, 2).unwrap(); // Second unwrap - can panic on logic error
-    doubled * 2
-}
-
-/// Example 3: File operations with unwrap
-fn read_config_file(path: &str) -> String {
-    let mut file = File::open(path).unwrap(); // Panics if file doesn't exist
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap(); // Panics if read fails
-    contents
-}
-
-/// Example 4: Nested structure access with unwrap
-fn get_nested_value(data: Option<Option<Option<i32>>>) -> i32 {
-    data.unwrap()      // First layer
-        .unwrap()      // Second layer
-        .unwrap()      // Third layer - any None causes panic
-}
+use unwrap::cli::{self, Command, LintMode};
+use unwrap::{bench, demo, exercises, lint, quiz};
+use unwrap::style::{ColorWriter, SymbolWriter, Symbols};
+use unwrap::system_design::{Design, SimConfig};
 
-/// Example 5: Array indexing equivalent
-fn get_element(vec: Vec<i32>, index: usize) -> i32 {
-    vec.get(index).unwrap().clone() // Panics on out-of-bounds
-}
-
-/// Better alternatives - how to handle errors properly
-mod better_approaches {
-    use std::fs::File;
-    use std::io::{Read, Error as IoError};
-    
-    pub fn divide_safe(a: i32, b: i32) -> Result<i32, &'static str> {
-        if b == 0 {
-            Err("Division by zero")
-        } else {
-            Ok(a / b)
+fn main() -> ExitCode {
+    let parsed = cli::parse_global_flags(std::env::args().skip(1))
+        .and_then(|(flags, args)| Ok((flags, cli::parse_args(args)?)));
+    let (flags, command) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, cli::USAGE);
+            return ExitCode::from(2);
         }
-    }
-    
-    pub fn parse_and_double_safe(s: &str) -> Result<i32, String> {
-        let num: i32 = s.parse()
-            .map_err(|e| format!("Parse error: {}", e))?;
-        let doubled = divide_safe(num, 2)
-            .map_err(|e| format!("Division error: {}", e))?;
-        Ok(doubled * 2)
-    }
-    
-    pub fn read_config_file_safe(path: &str) -> Result<String, IoError> {
-        let mut file = File::open(path)?;
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)?;
-        Ok(contents)
-    }
-}
+    };
 
-/// System design perspective: Poisson distribution of failures
-/// Every system carries a distribution of potential failure points
-mod system_design {
-    use std::time::Instant;
-    
-    /// Simulates a service with multiple potential failure points
-    /// In production systems, failures follow a Poisson distribution
-    pub struct Service {
-        failure_rate: f64, // λ (lambda) - average failures per time unit
-    }
-    
-    impl Service {
-        pub fn new(failure_rate: f64) -> Self {
-            Service { failure_rate }
-        }
-        
-        /// Design A: Fail-fast with unwrap (CloudFlare-style)
-        /// One failure brings down the entire service
-        pub fn handle_request_unsafe(&self, input: Option<String>) -> String {
-            let data = input.unwrap(); // Single point of total failure
-            format!("Processed: {}", data)
+    let defaults = SimConfig::default();
+    // A terminal sees each line as it is written, so panic messages on stderr
+    // stay next to the text around them; a pipe or file gets one big buffer
+    let stdout = io::stdout();
+    let color = flags.color.enabled(stdout.is_terminal());
+    let sink: Box<dyn Write> = if stdout.is_terminal() {
+        Box::new(stdout.lock())
+    } else {
+        Box::new(BufWriter::new(stdout.lock()))
+    };
+    let mut out = ColorWriter::new(SymbolWriter::new(sink, Symbols::detect(flags.ascii)), color);
+    // lint exits 1 when it finds call sites, so CI can fail on them
+    let mut found_unwraps = false;
+    let written = match command {
+        Command::All { paced: true } if io::stdin().is_terminal() => {
+            demo::run_paced(&mut out, &mut demo::LinePacer::new(io::stdin().lock()))
         }
-        
-        /// Design B: Graceful degradation with proper error handling
-        /// Failures are contained and logged, service continues
-        pub fn handle_request_safe(&self, input: Option<String>) -> Result<String, String> {
-            let data = input.ok_or("No input provided")?;
-            Ok(format!("Processed: {}", data))
+        Command::All { paced } => {
+            if paced {
+                eprintln!("note: stdin is not a terminal, so --paced runs without pausing");
+            }
+            demo::run(&mut out)
         }
-        
-        /// Design C: Circuit breaker pattern with fallback
-        /// System recognizes failure patterns and adapts
-        pub fn handle_request_resilient(&self, input: Option<String>) -> String {
-            match input {
-                Some(data) => format!("Processed: {}", data),
-                None => {
-                    // Log error, update metrics, but keep service alive
-                    eprintln!("⚠ Request failed, using fallback");
-                    String::from("Fallback response")
+        Command::Examples { dangerous_demos } => demo::examples(&mut out)
+            .and_then(|()| demo::cascade(&mut out))
+            .and_then(|()| if dangerous_demos { demo::double_panic(&mut out) } else { Ok(()) }),
+        Command::Simulate {
+            design, requests, failure_rate, seed, rate_limit, arrivals, monte_carlo, format, csv, verbosity,
+        } => {
+            let designs = match design {
+                Some(design) => vec![design],
+                None => Design::ALL.to_vec(),
+            };
+            let generated = requests.is_some()
+                || failure_rate.is_some()
+                || rate_limit.is_some()
+                || arrivals.is_some();
+            let config = SimConfig {
+                requests: requests.unwrap_or(defaults.requests),
+                failure_rate: failure_rate.unwrap_or(defaults.failure_rate),
+                seed,
+                rate_limit,
+                arrivals,
+            };
+            let reports = match monte_carlo {
+                Some(iterations) => demo::monte_carlo(&mut out, &designs, &config, iterations, format)
+                    .map(|()| Vec::new()),
+                None if generated => demo::simulate_generated(&mut out, &designs, &config, format, verbosity),
+                None => demo::simulate(&mut out, &designs, seed, format, verbosity),
+            };
+            match (reports, csv) {
+                (Ok(reports), Some(path)) => {
+                    if let Err(e) = demo::write_csv(&path, &reports) {
+                        eprintln!("error: could not write {}: {}", path.display(), e);
+                        return ExitCode::FAILURE;
+                    }
+                    Ok(())
                 }
+                (reports, _) => reports.map(|_| ()),
             }
         }
-    }
-    
-    /// Runtime IS test copy - failures will occur in production
-    /// The question is: how does your system respond?
-    pub fn simulate_production_load(design: &str) {
-        println!("\n=== Simulating Production Load: {} ===", design);
-        let service = Service::new(0.01); // 1% failure rate (λ = 0.01)
-        
-        // Simulate 10 requests with occasional None (failure)
-        let requests = vec![
-            Some("req1".to_string()),
-            Some("req2".to_string()),
-            None, // Failure occurs
-            Some("req3".to_string()),
-            Some("req4".to_string()),
-            None, // Another failure
-            Some("req5".to_string()),
-        ];
-        
-        let start = Instant::now();
-        let mut successful = 0;
-        let mut failed = 0;
-        
-        for (i, req) in requests.iter().enumerate() {
-            match design {
-                "unsafe" => {
-                    // Simulates unwrap() - first failure kills everything
-                    if let Err(_) = std::panic::catch_unwind(|| {
-                        service.handle_request_unsafe(req.clone())
-                    }) {
-                        println!("  Request {}: ✗ SERVICE CRASHED - All subsequent requests lost!", i + 1);
-                        println!("  💀 Total system failure. Remaining {} requests dropped.", requests.len() - i - 1);
-                        failed = requests.len() - i;
-                        break;
-                    } else {
-                        println!("  Request {}: ✓", i + 1);
-                        successful += 1;
-                    }
-                },
-                "safe" => {
-                    match service.handle_request_safe(req.clone()) {
-                        Ok(_) => {
-                            println!("  Request {}: ✓", i + 1);
-                            successful += 1;
-                        },
-                        Err(e) => {
-                            println!("  Request {}: ✗ Error logged: {}", i + 1, e);
-                            failed += 1;
-                        }
-                    }
-                },
-                "resilient" => {
-                    let response = service.handle_request_resilient(req.clone());
-                    if response.contains("Fallback") {
-                        println!("  Request {}: ⚠ Degraded (fallback)", i + 1);
-                        failed += 1;
-                    } else {
-                        println!("  Request {}: ✓", i + 1);
-                        successful += 1;
+        Command::Compare { requests, failure_rate, seed, rate_limit, arrivals, format } => {
+            let config = SimConfig {
+                requests: requests.unwrap_or(defaults.requests),
+                failure_rate: failure_rate.unwrap_or(defaults.failure_rate),
+                seed,
+                rate_limit,
+                arrivals,
+            };
+            demo::compare(&mut out, &config, format)
+        }
+        Command::Lessons { name: None, .. } => {
+            writeln!(out, "Available lessons:").and_then(|()| {
+                demo::all_lessons().iter()
+                    .try_for_each(|lesson| writeln!(out, "  {:<12} {}", lesson.id(), lesson.title()))
+            })
+        }
+        Command::Lessons { category, run_demos, .. } if category.is_some() || run_demos => {
+            demo::history(&mut out, category, run_demos)
+        }
+        Command::Lessons { name: Some(name), .. } => match demo::lesson(&name) {
+            Some(lesson) => lesson.run(&mut out),
+            None => {
+                eprintln!("error: unknown lesson '{}' (try `unwrap lessons`)", name);
+                return ExitCode::from(2);
+            }
+        },
+        Command::Quiz => quiz::run_quiz(&mut io::stdin().lock(), &mut out).map(|_| ()),
+        Command::Export { out: dir } => {
+            if let Err(e) = demo::export_markdown(&mut out, &dir) {
+                eprintln!("error: could not export to {}: {}", dir.display(), e);
+                return ExitCode::FAILURE;
+            }
+            Ok(())
+        }
+        Command::Exercises => exercises::write_scoreboard(&mut out).map(|_| ()),
+        Command::Bench => demo::benchmarks(&mut out, bench::BUDGET),
+        Command::Advise { kind, context } => demo::advise(&mut out, kind, context),
+        Command::Lint { path, format, policy, update_baseline, mode, config } => {
+            let weights = match config.as_deref().map(lint::Weights::load).transpose() {
+                Ok(weights) => weights.unwrap_or_default(),
+                Err(e) => {
+                    eprintln!("error: could not read {}: {}", config.unwrap_or_default().display(), e);
+                    return ExitCode::from(2);
+                }
+            };
+            // Fixing changes what there is to report, and so does a new baseline: scan again after either
+            let scanned = lint::scan_path(&path).and_then(|mut report| {
+                let mut fixed = Vec::new();
+                if mode == LintMode::Fix {
+                    fixed = lint::apply_fixes(&report)?;
+                    report = lint::scan_path(&path)?;
+                }
+                if update_baseline {
+                    lint::update_baseline(&path, &report)?;
+                    report = lint::scan_path(&path)?;
+                }
+                Ok((report, fixed))
+            });
+            match scanned {
+                Ok((mut report, fixed)) => {
+                    report.score(&weights);
+                    found_unwraps = report.failing(&policy) > 0;
+                    match mode {
+                        LintMode::Report => demo::lint(&mut out, &report, format, &policy),
+                        LintMode::Suggest => lint::write_suggestions(&mut out, &report),
+                        LintMode::Fix => lint::write_fixes(&mut out, &fixed, &report, &policy),
                     }
-                },
-                _ => {}
+                }
+                Err(e) => {
+                    eprintln!("error: could not lint {}: {}", path.display(), e);
+                    return ExitCode::FAILURE;
+                }
             }
         }
-        
-        let duration = start.elapsed();
-        println!("\n  Results: {} successful, {} failed", successful, failed);
-        println!("  Service uptime: {:?}", duration);
-        println!("  Availability: {:.1}%", (successful as f64 / requests.len() as f64) * 100.0);
-    }
-}
-
-fn main() {
-    println!("🔓 UNWRAP PROBLEM PROPAGATION DEMO 🔓\n");
-    println!("This demo shows how unwrap() causes problems to 'unwrap' into panics.\n");
-    
-    // Demonstration 1: Basic unwrap success
-    println!("=== Example 1: Basic Division ===");
-    match divide(10, 2) {
-        Some(result) => println!("✓ 10 / 2 = {}", result),
-        None => println!("✗ Division failed"),
-    }
-    
-    // This would panic:
-    // println!("Result: {}", divide(10, 0).unwrap());
-    println!("⚠ divide(10, 0).unwrap() would panic here!\n");
-    
-    // Demonstration 2: Chained unwraps
-    println!("=== Example 2: Chained Operations ===");
-    match std::panic::catch_unwind(|| parse_and_double("not a number")) {
-        Ok(_) => println!("Success"),
-        Err(_) => println!("✗ PANIC CAUGHT: Invalid string caused parse().unwrap() to panic"),
-    }
-    println!("✓ parse_and_double(\"10\") = {}\n", parse_and_double("10"));
-    
-    // Demonstration 3: File operations
-    println!("=== Example 3: File Operations ===");
-    match std::panic::catch_unwind(|| read_config_file("nonexistent.txt")) {
-        Ok(_) => println!("Success"),
-        Err(_) => println!("✗ PANIC CAUGHT: File doesn't exist, File::open().unwrap() panicked"),
-    }
-    
-    // Better approach
-    match better_approaches::read_config_file_safe("nonexistent.txt") {
-        Ok(contents) => println!("✓ File contents: {}", contents),
-        Err(e) => println!("✓ Error handled gracefully: {}\n", e),
-    }
-    
-    // Demonstration 4: Nested unwraps
-    println!("=== Example 4: Nested Option Unwrapping ===");
-    let nested_some = Some(Some(Some(42)));
-    let nested_none = Some(Some(None));
-    
-    println!("✓ Nested Some: {}", get_nested_value(nested_some));
-    match std::panic::catch_unwind(|| get_nested_value(nested_none)) {
-        Ok(_) => println!("Success"),
-        Err(_) => println!("✗ PANIC CAUGHT: Deep None value caused unwrap() to panic\n"),
-    }
-    
-    // Demonstration 5: Vector access
-    println!("=== Example 5: Collection Access ===");
-    let numbers = vec![1, 2, 3, 4, 5];
-    println!("✓ Element at index 2: {}", get_element(numbers.clone(), 2));
-    match std::panic::catch_unwind(|| get_element(numbers.clone(), 10)) {
-        Ok(_) => println!("Success"),
-        Err(_) => println!("✗ PANIC CAUGHT: Out of bounds access caused unwrap() to panic\n"),
-    }
-    
-    // Show the cascade effect
-    println!("=== THE CASCADE EFFECT ===");
-    println!("When unwrap() panics, it:");
-    println!("  1. Immediately terminates the current function");
-    println!("  2. Unwinds the stack (unless caught)");
-    println!("  3. Propagates up the call chain");
-    println!("  4. Can crash the entire program");
-    println!("\nThe 'problem' literally unwraps itself into a program crash!\n");
-    
-    // Better approach summary
-    println!("=== BETTER APPROACHES ===");
-    match better_approaches::parse_and_double_safe("15") {
-        Ok(result) => println!("✓ Safe parsing: 15 -> {}", result),
-        Err(e) => println!("✗ Error: {}", e),
-    }
-    
-    match better_approaches::parse_and_double_safe("invalid") {
-        Ok(result) => println!("✓ Result: {}", result),
-        Err(e) => println!("✓ Graceful error handling: {}", e),
+        Command::Help => writeln!(out, "{}", cli::USAGE),
+    };
+    match written.and_then(|()| out.flush()) {
+        Ok(()) if found_unwraps => ExitCode::FAILURE,
+        Ok(()) => ExitCode::SUCCESS,
+        // `unwrap | head` closing the pipe early is the reader's choice, not a failure
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: could not write output: {}", e);
+            ExitCode::FAILURE
+        }
     }
-    
-    println!("\n🎯 KEY TAKEAWAY:");
-    println!("Use ? operator, match, or if let instead of unwrap()");
-    println!("Let errors propagate gracefully, not explosively!");
-    
-    // NEW SECTION: System Design Perspective
-    println!("\n");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("IS RUST TO BLAME? The CloudFlare Question");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("As an assembly-level system, the language isn't to blame.");
-    println!("Rust PROVIDES the tools for safe error handling.");
-    println!("The choice to use unwrap() is a DESIGN DECISION.\n");
-    
-    println!("All systems carry a Poisson distribution of potential failures.");
-    println!("Runtime IS test copy - failures WILL occur in production.");
-    println!("The question is: How does your system design respond?\n");
-    
-    // Demonstrate three system design approaches
-    system_design::simulate_production_load("unsafe");
-    system_design::simulate_production_load("safe");
-    system_design::simulate_production_load("resilient");
-    
-    println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("LESSONS FROM THE CLOUDFLARE INCIDENT");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("1. LANGUAGE: Rust gave them Result<T,E> and Option<T>");
-    println!("   → The tools for safety were available\n");
-    
-    println!("2. DESIGN: They chose .unwrap() in critical paths");
-    println!("   → Converted recoverable errors into unrecoverable panics\n");
-    
-    println!("3. STATISTICS: Given λ (failure rate) and time, failures are inevitable");
-    println!("   → Poisson distribution: P(k events) = (λ^k * e^-λ) / k!\n");
-    
-    println!("4. TESTING: Runtime behavior differs from test environments");
-    println!("   → Edge cases, load patterns, and timing create unique failure modes\n");
-    
-    println!("5. RESPONSIBILITY: The bug was in the design, not the language");
-    println!("   → unwrap() is like unsafe{{}} - use sparingly and with intention\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("THE VERDICT");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("Rust is NOT to blame. It's like blaming:");
-    println!("  • Assembly for allowing direct memory access");
-    println!("  • C for having pointers");
-    println!("  • SQL for allowing DROP TABLE\n");
-    
-    println!("These are TOOLS. Power comes with responsibility.");
-    println!("unwrap() says: 'I know this will never fail.'");
-    println!("But in distributed systems with Poisson-distributed failures,");
-    println!("'never' is a dangerous assumption.\n");
-    
-    println!("✓ Use Result<T,E> and propagate errors with ?");
-    println!("✓ Design for graceful degradation");
-    println!("✓ Remember: Runtime IS test copy - plan for the unexpected");
-    println!("✓ Respect the statistics: λt failures will occur over time t\n");
-    
-    // NEW SECTION: Learning from Failure
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("LEARNING FROM FAILURE: The Low-Level Developer's Mindset");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("History teaches us: We learn more from failure than success.");
-    println!("Success is passive - things work, we move on.");
-    println!("Failure FORCES attention - we must understand why.\n");
-    
-    println!("LOW-LEVEL DEVELOPERS: Never Have a Positive Bias\n");
-    
-    println!("The Optimistic Developer:");
-    println!("  • \"This network call will succeed\"");
-    println!("  • \"This pointer is valid\"");
-    println!("  • \"This allocation won't fail\"");
-    println!("  • \"This parse will work\"");
-    println!("  Result: .unwrap() everywhere → Production crashes\n");
-    
-    println!("The Defensive Developer:");
-    println!("  • \"What if the network is down?\"");
-    println!("  • \"What if this pointer is null?\"");
-    println!("  • \"What if we're out of memory?\"");
-    println!("  • \"What if this input is malformed?\"");
-    println!("  Result: Result<T,E> everywhere → Graceful degradation\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("HISTORICAL LESSONS: Famous Failures That Taught Us");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("1. Therac-25 (1985-1987): Race conditions killed patients");
-    println!("   Lesson: Never assume timing will work out");
-    println!("   → Today: Mutex, atomic operations, formal verification\n");
-    
-    println!("2. Ariane 5 (1996): Integer overflow crashed $370M rocket");
-    println!("   Lesson: Never assume values fit in their types");
-    println!("   → Today: Checked arithmetic, Result<T,E>\n");
-    
-    println!("3. Mars Climate Orbiter (1999): Unit conversion error");
-    println!("   Lesson: Never assume implicit conversions are correct");
-    println!("   → Today: Type systems, newtypes, dimensional analysis\n");
-    
-    println!("4. Heartbleed (2014): Buffer over-read leaked secrets");
-    println!("   Lesson: Never trust buffer boundaries");
-    println!("   → Today: Bounds checking, Rust's ownership system\n");
-    
-    println!("5. CloudFlare (Recent): .unwrap() took down services");
-    println!("   Lesson: Never assume Optional values exist");
-    println!("   → Today: Explicit error handling, ? operator\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("THE DEFENSIVE PROGRAMMING MINDSET");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("Every .unwrap() is an assertion: 'This CANNOT fail.'");
-    println!("But at the bit level, EVERYTHING can fail:\n");
-    
-    println!("  • Cosmic rays can flip bits (soft errors)");
-    println!("  • Hardware can malfunction");
-    println!("  • Networks partition");
-    println!("  • Disks fill up");
-    println!("  • Memory exhausts");
-    println!("  • Race conditions emerge");
-    println!("  • Edge cases appear in production\n");
-    
-    println!("The low-level developer EXPECTS failure.");
-    println!("This isn't pessimism - it's realism based on history.\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("WHY FAILURE TEACHES MORE THAN SUCCESS");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("Success: Code works → Move to next task");
-    println!("  Learning: Minimal (confirmation bias)\n");
-    
-    println!("Failure: Code crashes → MUST investigate");
-    println!("  Learning: Maximum (forced attention)");
-    println!("  • What assumptions were wrong?");
-    println!("  • What edge cases exist?");
-    println!("  • What invariants were violated?");
-    println!("  • How do we prevent this class of errors?\n");
-    
-    println!("Every production failure is a gift:");
-    println!("  It reveals the gap between our mental model");
-    println!("  and the actual behavior of the system.\n");
-    
-    println!("The unwrap() that works 99.9% of the time?");
-    println!("That's DANGEROUS. It teaches you nothing.");
-    println!("The 0.1% failure? That's your teacher.\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("CONCLUSION: The Path Forward");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("1. NEVER trust the happy path");
-    println!("2. ALWAYS model failure explicitly (Result<T,E>)");
-    println!("3. LEARN from each failure in production");
-    println!("4. RESPECT the lessons history has taught us");
-    println!("5. MAINTAIN a healthy skepticism about success\n");
-    
-    println!("As Dijkstra said: 'Testing shows the presence of bugs,");
-    println!("not their absence.' Production IS the ultimate test.\n");
-    
-    println!("The best developers aren't those who write code that works.");
-    println!("They're those who write code that fails gracefully");
-    println!("when the inevitable happens.\n");
-    
-    // NEW SECTION: Trivial vs Non-Trivial Systems
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("TRIVIAL ENGINES vs REAL SYSTEMS");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("ENGINES THAT DON'T FAIL ARE TRIVIAL\n");
-    
-    println!("Mathematical/Deterministic Systems (TRIVIAL):");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  1. Ruler and Compass Construction");
-    println!("     • Euclidean geometry - perfect, deterministic");
-    println!("     • Cannot fail given valid inputs");
-    println!("     • Closed mathematical system\n");
-    
-    println!("  2. Matrix Multiplication");
-    println!("     • A × B always defined if dimensions match");
-    println!("     • Pure mathematics, no external dependencies");
-    println!("     • Result is deterministic and exact\n");
-    
-    println!("  3. Slide Rules / log base n");
-    println!("     • Mechanical computation via logarithms");
-    println!("     • Deterministic transformation");
-    println!("     • No failure modes (in pure form)\n");
-    
-    println!("  4. S3 (Symmetric Group on 3 elements)");
-    println!("     • Only 6 permutations under composition");
-    println!("     • Closed group: e, (12), (13), (23), (123), (132)");
-    println!("     • Every composition yields one of these 6");
-    println!("     • Perfectly deterministic, no failure states\n");
-    
-    println!("These systems are CLOSED and PURE:");
-    println!("  → No I/O");
-    println!("  → No resource constraints");
-    println!("  → No timing dependencies");
-    println!("  → No external state");
-    println!("  → Perfect mathematical abstraction\n");
-    
-    println!("Real Computational Systems (NON-TRIVIAL):");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  1. Network I/O");
-    println!("     • Can timeout, drop packets, partition");
-    println!("     • Must handle: Result<Response, NetworkError>\n");
-    
-    println!("  2. File System");
-    println!("     • Disk can be full, file can be locked");
-    println!("     • Must handle: Result<File, IoError>\n");
-    
-    println!("  3. Memory Allocation");
-    println!("     • System can be out of memory");
-    println!("     • Must handle: Option<*mut T> or Result\n");
-    
-    println!("  4. Parsing User Input");
-    println!("     • Input can be malformed, truncated, malicious");
-    println!("     • Must handle: Result<T, ParseError>\n");
-    
-    println!("  5. Concurrent Operations");
-    println!("     • Race conditions, deadlocks, livelocks");
-    println!("     • Must handle: locks, channels, atomics\n");
-    
-    println!("These systems are OPEN and IMPURE:");
-    println!("  → Interact with external world");
-    println!("  → Limited resources (memory, disk, network)");
-    println!("  → Timing-dependent behavior");
-    println!("  → Shared mutable state");
-    println!("  → Failure is INEVITABLE\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("THE FUNDAMENTAL DIFFERENCE");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("TRIVIAL ENGINES:");
-    println!("  Matrix A × Matrix B → Matrix C");
-    println!("  (Always succeeds if dimensions match)\n");
-    
-    println!("  fn multiply(a: Matrix, b: Matrix) -> Matrix {{");
-    println!("      // Pure function, cannot fail");
-    println!("      // No need for Result<T,E>");
-    println!("  }}\n");
-    
-    println!("REAL SYSTEMS:");
-    println!("  HTTP Request → ??? (success, timeout, 404, 500, ...)");
-    println!("  (Many failure modes, timing-dependent)\n");
-    
-    println!("  fn fetch(url: &str) -> Result<Response, Error> {{");
-    println!("      // Impure function, MUST handle failure");
-    println!("      // Using .unwrap() here is negligent");
-    println!("  }}\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("WHY unwrap() IS DANGEROUS");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("Using .unwrap() pretends a REAL system is TRIVIAL:");
-    println!("  • You're asserting: 'This cannot fail'");
-    println!("  • Reality: It's interacting with the messy world");
-    println!("  • Result: System crashes when reality intrudes\n");
-    
-    println!("It's like pretending:");
-    println!("  • Your network is S3 (always one of 6 perfect states)");
-    println!("  • Your disk is a slide rule (deterministic, no failures)");
-    println!("  • Your parser is matrix multiplication (always succeeds)\n");
-    
-    println!("But they're NOT. They're complex, open systems with:");
-    println!("  • Unbounded state spaces");
-    println!("  • External dependencies");
-    println!("  • Resource constraints");
-    println!("  • Probabilistic behavior\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("THE LESSON");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("If your system interacts with:");
-    println!("  ✓ I/O (files, network, hardware)");
-    println!("  ✓ User input");
-    println!("  ✓ Shared resources");
-    println!("  ✓ Time-dependent behavior");
-    println!("  ✓ External services\n");
-    
-    println!("Then it's NOT TRIVIAL. It can and will fail.");
-    println!("Don't use .unwrap(). Use Result<T,E>.\n");
-    
-    println!("The mathematical abstraction is beautiful:");
-    println!("  S3 has exactly 6 elements under composition.");
-    println!("  Matrix multiplication is deterministic.\n");
-    
-    println!("But production systems aren't mathematical abstractions.");
-    println!("They're NON-TRIVIAL ENGINES operating in a failure-rich environment.\n");
-    
-    println!("Respect the difference. Handle the failures.");
-    println!("That's what separates toy code from production systems.\n");
-    
-    // NEW SECTION: Gödel and the Limits of Proof
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("GÖDEL'S INCOMPLETENESS: The Impossibility of Perfect Systems");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("HILBERT'S DREAM (Early 1900s):");
-    println!("  Sought a perfect synthesis of Mathematics and Logic");
-    println!("  Goals:");
-    println!("    1. Completeness: Every true statement is provable");
-    println!("    2. Consistency: No contradictions can be derived");
-    println!("    3. Decidability: Mechanical procedure to prove/disprove any statement\n");
-    
-    println!("  Hilbert believed: Mathematics could be perfectly formalized");
-    println!("  A complete, consistent, decidable system for all of math\n");
-    
-    println!("GÖDEL'S ANSWER (1931):");
-    println!("  First Incompleteness Theorem:");
-    println!("    'Any consistent formal system F sufficient for arithmetic");
-    println!("     contains statements that are TRUE but UNPROVABLE in F.'\n");
-    
-    println!("  Second Incompleteness Theorem:");
-    println!("    'No consistent system can prove its own consistency.'\n");
-    
-    println!("  Translation: Systems of sufficient complexity");
-    println!("  DON'T HAVE THE MACHINERY TO PROVE THEMSELVES.\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("THE PARALLEL TO SOFTWARE SYSTEMS");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("Hilbert's Dream          →  The Optimistic Developer");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  Perfect formalization  →  'My code is correct'");
-    println!("  Complete proofs        →  'Tests prove correctness'");
-    println!("  No contradictions      →  'No bugs possible'");
-    println!("  Decidable              →  'Static analysis finds all issues'\n");
-    
-    println!("Gödel's Reality          →  The Defensive Developer");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  Incompleteness         →  'Some failures are unpredictable'");
-    println!("  Unprovable truths      →  'Cannot test all paths'");
-    println!("  Can't self-prove       →  'System can't validate itself'");
-    println!("  Inherent limits        →  'Must handle unknown failures'\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("IMPLICATIONS FOR SOFTWARE");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("1. TESTING IS INSUFFICIENT");
-    println!("   Just as Gödel showed true statements exist that can't be proven,");
-    println!("   bug-free execution paths exist that can't be tested.\n");
-    
-    println!("   • Test coverage: 100% → Still bugs in production");
-    println!("   • Formal verification: Proves properties → Can't prove ALL properties");
-    println!("   • Static analysis: Finds issues → Halting problem limits completeness\n");
-    
-    println!("2. SELF-VALIDATION IS IMPOSSIBLE");
-    println!("   A complex system cannot prove its own correctness.");
-    println!("   You need external validation, monitoring, and graceful degradation.\n");
-    
-    println!("   • .unwrap() assumes self-validation: 'This WILL work'");
-    println!("   • Result<T,E> admits limits: 'This MIGHT fail'\n");
-    
-    println!("3. COMPLEXITY BREEDS UNPROVABILITY");
-    println!("   Simple systems (S3, matrix multiplication): Provably correct");
-    println!("   Complex systems (distributed services): Inherently unprovable\n");
-    
-    println!("   As Gödel showed: Sufficient complexity → Incompleteness");
-    println!("   In software: Sufficient complexity → Inevitable bugs\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("THE FUNDAMENTAL LIMITS");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("Gödel (1931): 'Formal systems can't prove their consistency'");
-    println!("Turing (1936): 'Halting problem is undecidable'");
-    println!("Dijkstra (1970s): 'Testing shows presence, not absence of bugs'");
-    println!("Rice (1953): 'Non-trivial program properties are undecidable'\n");
-    
-    println!("These aren't engineering limitations - they're MATHEMATICAL IMPOSSIBILITIES.\n");
-    
-    println!("You cannot:");
-    println!("  ✗ Prove a complex system has no bugs");
-    println!("  ✗ Test all possible execution paths");
-    println!("  ✗ Guarantee a program will terminate");
-    println!("  ✗ Decide if two programs are equivalent\n");
-    
-    println!("Therefore, you MUST:");
-    println!("  ✓ Design for failure");
-    println!("  ✓ Use Result<T,E> to make failures explicit");
-    println!("  ✓ Implement graceful degradation");
-    println!("  ✓ Monitor and adapt at runtime\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("WHY .unwrap() VIOLATES GÖDEL'S LESSON");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("Using .unwrap() is claiming:");
-    println!("  'I have proven this cannot fail.'\n");
-    
-    println!("But Gödel proved:");
-    println!("  Complex systems cannot prove themselves.\n");
-    
-    println!("You're asserting completeness and consistency");
-    println!("in a system that MATHEMATICALLY cannot have both.\n");
-    
-    println!("This is why production systems fail:");
-    println!("  • The developer assumes provable correctness");
-    println!("  • Gödel guarantees unprovable cases exist");
-    println!("  • .unwrap() hits an unprovable case");
-    println!("  • System crashes\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("THE HUMBLE PATH FORWARD");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("Hilbert sought perfection. Gödel showed its impossibility.");
-    println!("Similarly:");
-    println!("  Developers seek bug-free code.");
-    println!("  Reality shows it's mathematically impossible.\n");
-    
-    println!("The solution isn't to give up - it's to be HUMBLE:");
-    println!("  • Acknowledge limits of provability");
-    println!("  • Design systems that tolerate unknown failures");
-    println!("  • Use Result<T,E> to admit fallibility");
-    println!("  • Accept that runtime will reveal what testing cannot\n");
-    
-    println!("Gödel didn't end mathematics - he made it more honest.");
-    println!("We shouldn't end software development - make it more honest.\n");
-    
-    println!("Stop pretending you can prove correctness with .unwrap().");
-    println!("Start admitting fallibility with Result<T,E>.\n");
-    
-    println!("That's the lesson of incompleteness:");
-    println!("  Perfection is impossible.");
-    println!("  Graceful handling of imperfection is mandatory.\n");
-    
-    // NEW SECTION: The Biological Analogue
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("THE BIOLOGICAL ANALOGUE: DNA → Protein Synthesis");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("The pattern repeats in nature:\n");
-    
-    println!("DNA REPLICATION");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  Purpose: Copy genetic information perfectly");
-    println!("  Reality: ~1 error per 10^9 base pairs (even with proofreading)");
-    println!("  Result: Mutations propagate to daughter cells\n");
-    
-    println!("PROTEIN SYNTHESIS");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  DNA → mRNA (transcription)");
-    println!("    • RNA polymerase can misread DNA template");
-    println!("    • ~1 error per 10^4 to 10^5 nucleotides");
-    println!("  mRNA → Protein (translation)");
-    println!("    • Ribosome can incorporate wrong amino acid");
-    println!("    • ~1 error per 10^4 amino acids\n");
-    
-    println!("THE PARADOX:");
-    println!("  The system CODES FOR error detection and correction");
-    println!("  • DNA polymerase has 3' → 5' exonuclease (proofreading)");
-    println!("  • Mismatch repair systems (MMR)");
-    println!("  • Base excision repair (BER)");
-    println!("  • Nucleotide excision repair (NER)\n");
-    
-    println!("  Yet the system ITSELF can carry errors in those very mechanisms:");
-    println!("  • Mutation in DNA polymerase → worse proofreading");
-    println!("  • Mutation in MMR genes → cancer (Lynch syndrome)");
-    println!("  • Error in error-correction creates compound errors\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("THE PARALLEL TO SOFTWARE");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("Biology               →  Software");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("DNA template          →  Source code");
-    println!("Replication           →  Compilation/deployment");
-    println!("Transcription errors  →  Build errors");
-    println!("Translation errors    →  Runtime errors");
-    println!("Proofreading enzymes  →  Type checkers, linters");
-    println!("Repair systems        →  Error handling (Result<T,E>)");
-    println!("Mutations             →  Bugs\n");
-    
-    println!("THE SELF-REFERENTIAL PROBLEM:");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("In Biology:");
-    println!("  The DNA that codes for error-correction enzymes");
-    println!("  can itself contain errors in those very genes.");
-    println!("  → The system meant to prevent errors propagates them\n");
-    
-    println!("In Software:");
-    println!("  fn validate_input(s: &str) -> Result<Data, Error> {{");
-    println!("      s.parse().unwrap() // BUG in validation code!");
-    println!("  }}\n");
-    
-    println!("  The error-handling code ITSELF contains .unwrap()");
-    println!("  → The system meant to prevent crashes causes them\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("WHY PERFECT FIDELITY IS IMPOSSIBLE");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("1. THERMODYNAMICS");
-    println!("   At molecular level: thermal fluctuations cause errors");
-    println!("   At computational level: cosmic rays flip bits, hardware fails\n");
-    
-    println!("2. COMPLEXITY COST");
-    println!("   Biology: More proofreading = slower replication");
-    println!("   Software: More validation = slower execution");
-    println!("   Trade-off between speed and accuracy\n");
-    
-    println!("3. SELF-REFERENCE");
-    println!("   Biology: Error-correction genes can themselves mutate");
-    println!("   Software: Error-handling code can itself have bugs");
-    println!("   The corrector needs correction (infinite regress)\n");
-    
-    println!("4. EVOLUTION REQUIRES ERRORS");
-    println!("   Biology: Zero mutations = no adaptation = extinction");
-    println!("   Software: Zero changes = no features = obsolescence");
-    println!("   Some error rate is NECESSARY for survival\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("THE CASCADE: From Molecule to System");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("DNA error → Wrong amino acid → Misfolded protein → Non-functional enzyme");
-    println!("  → Metabolic failure → Cell death → Tissue damage → Organ failure\n");
-    
-    println!("Code error → Wrong type → .unwrap() panic → Thread crash");
-    println!("  → Service unavailable → Request timeout → System cascade → Outage\n");
-    
-    println!("Both systems exhibit:");
-    println!("  • Local errors → Global failures");
-    println!("  • Self-referential vulnerability");
-    println!("  • Impossible perfect fidelity");
-    println!("  • Trade-offs between speed and accuracy\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("THE BIOLOGICAL LESSON FOR SOFTWARE");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("Life doesn't prevent errors - it MANAGES them:");
-    println!("  • Multiple repair pathways (redundancy)");
-    println!("  • Apoptosis (graceful cell death instead of cancer)");
-    println!("  • Immune system (runtime monitoring)");
-    println!("  • Genetic diversity (fault tolerance through variation)\n");
-    
-    println!("Software should do the same:");
-    println!("  • Multiple error paths (Result<T,E>, not unwrap())");
-    println!("  • Graceful degradation (fallback, not crash)");
-    println!("  • Monitoring & observability (detect failures)");
-    println!("  • Redundancy (replicas, not single points of failure)\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("THE UNIVERSAL PRINCIPLE");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("From molecules to mathematics to machines:");
-    println!("  SYSTEMS THAT REPLICATE INFORMATION CARRY FORWARD ERRORS\n");
-    
-    println!("  • DNA → Mutations propagate");
-    println!("  • Gödel → Unprovable statements exist");
-    println!("  • Software → Bugs persist\n");
-    
-    println!("The common thread:");
-    println!("  Any system complex enough to be interesting");
-    println!("  is complex enough to fail in unpredictable ways.\n");
-    
-    println!("The solution isn't perfection (impossible).");
-    println!("The solution is ROBUSTNESS in the face of imperfection:\n");
-    
-    println!("  Biology: Repair, redundancy, apoptosis, evolution");
-    println!("  Mathematics: Acknowledge incompleteness, external validation");
-    println!("  Software: Result<T,E>, monitoring, graceful degradation\n");
-    
-    println!("Life has been solving this problem for 3.5 billion years.");
-    println!("The lesson: Don't try to prevent all errors.");
-    println!("Instead: Build systems that SURVIVE errors.\n");
-    
-    println!("That's what .unwrap() misses and Result<T,E> embraces.");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    // NEW SECTION: Why Cancer Is More Gödel Than Hilbert
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("WHY 'SOLVING CANCER' IS MORE GÖDEL THAN HILBERT");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("HILBERT'S APPROACH: Complete, Perfect Solution");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  'Solve cancer' implies:");
-    println!("    • One universal cure");
-    println!("    • Complete understanding");
-    println!("    • Provably effective treatment");
-    println!("    • No edge cases");
-    println!("    • Problem eliminated forever\n");
-    
-    println!("GÖDEL'S REALITY: Incompleteness Is Inevitable");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  Cancer is fundamentally GÖDELIAN because:\n");
-    
-    println!("  1. SELF-REFERENTIAL SYSTEM");
-    println!("     • Cancer cells are YOUR cells");
-    println!("     • They use the same machinery as healthy cells");
-    println!("     • Immune system must distinguish self from self-gone-wrong");
-    println!("     • Like Gödel's statement about itself: unprovable within system\n");
-    
-    println!("  2. ERROR IN ERROR-CORRECTION");
-    println!("     • Cancer often starts with mutations in DNA repair genes");
-    println!("     • p53 (guardian of genome) itself gets mutated");
-    println!("     • Mismatch repair (MMR) defects → Lynch syndrome");
-    println!("     • The corrector is corrupted → compound errors\n");
-    
-    println!("  3. EVOLUTIONARY ARMS RACE");
-    println!("     • Give drug → cancer evolves resistance");
-    println!("     • Target pathway → cancer finds alternate route");
-    println!("     • Kill 99.9% of cells → 0.1% with resistance survive");
-    println!("     • System adapts faster than we can intervene\n");
-    
-    println!("  4. MOVING TARGET");
-    println!("     • Not one disease, but ~200+ different cancers");
-    println!("     • Each tumor is genetically unique");
-    println!("     • Heterogeneity WITHIN a single tumor");
-    println!("     • Trying to 'solve' all cancers = trying to prove all theorems\n");
-    
-    println!("  5. ESSENTIAL PROCESSES HIJACKED");
-    println!("     • Cell division (necessary for life)");
-    println!("     • Growth signaling (necessary for healing)");
-    println!("     • Angiogenesis (necessary for tissue)");
-    println!("     • Can't eliminate without eliminating life itself\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("THE PARALLEL TO GÖDEL'S THEOREMS");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("Gödel's First Theorem:");
-    println!("  'Any consistent system has true unprovable statements'\n");
-    
-    println!("Cancer Translation:");
-    println!("  'Any living system can develop cancers that evade treatment'");
-    println!("  • New mutation pathways emerge");
-    println!("  • Resistance mechanisms we haven't discovered");
-    println!("  • Edge cases beyond current medical understanding\n");
-    
-    println!("Gödel's Second Theorem:");
-    println!("  'No system can prove its own consistency'\n");
-    
-    println!("Cancer Translation:");
-    println!("  'No organism can guarantee its cells won't become cancerous'");
-    println!("  • You can't prove your DNA repair is perfect");
-    println!("  • Repair mechanisms themselves can fail");
-    println!("  • Self-validation is impossible\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("WHY 'CURE' MAY BE THE WRONG FRAMING");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("Hilbert wanted: Complete solution (impossible)");
-    println!("Gödel showed: Manage incompleteness (realistic)\n");
-    
-    println!("Similarly:");
-    println!("  WRONG GOAL: 'Cure cancer' (eliminate forever)");
-    println!("  • Assumes static target");
-    println!("  • Assumes complete knowledge");
-    println!("  • Assumes one solution works for all cases");
-    println!("  • Like claiming .unwrap() will never fail\n");
-    
-    println!("  RIGHT GOAL: 'Manage cancer' (survive with it)");
-    println!("  • Treat as chronic disease");
-    println!("  • Adaptive therapy (evolve with the cancer)");
-    println!("  • Personalized medicine (handle each case)");
-    println!("  • Monitor and adapt (runtime validation)");
-    println!("  • Like using Result<T,E> instead of unwrap()\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("EXAMPLES OF THE GÖDELIAN NATURE");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("1. GLEEVEC (Imatinib) for CML");
-    println!("   • Miracle drug, targeted therapy");
-    println!("   • Initially: ~90% remission");
-    println!("   • Over time: resistance mutations emerge");
-    println!("   • T315I mutation makes it ineffective");
-    println!("   • Unprovable statement: 'This will always work'\n");
-    
-    println!("2. IMMUNE CHECKPOINT INHIBITORS");
-    println!("   • Unleash immune system against cancer");
-    println!("   • Works brilliantly for ~20-40% of patients");
-    println!("   • Others: no response or autoimmune damage");
-    println!("   • Can't prove who will respond");
-    println!("   • Edge cases remain unpredictable\n");
-    
-    println!("3. TUMOR HETEROGENEITY");
-    println!("   • Single tumor has multiple clones");
-    println!("   • Kill clone A → clone B takes over");
-    println!("   • Like whack-a-mole with evolution");
-    println!("   • No single 'proof' kills all variants\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("THE ADAPTIVE STRATEGY (Gödel's Way)");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("Since perfect cure is impossible, we adapt:\n");
-    
-    println!("  • Liquid biopsies (runtime monitoring)");
-    println!("  • Adaptive therapy (adjust based on feedback)");
-    println!("  • Combination treatments (multiple error paths)");
-    println!("  • Evolutionary pressure management (don't eradicate fully)");
-    println!("  • Synthetic lethality (exploit specific weaknesses)\n");
-    
-    println!("This is like software engineering:");
-    println!("  • Not trying to prove code is bug-free");
-    println!("  • Instead: monitor, adapt, gracefully degrade");
-    println!("  • Result<T,E> acknowledges failure modes");
-    println!("  • Runtime validation over static proof\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("THE LESSON");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("Some problems are FUNDAMENTALLY GÖDELIAN:");
-    println!("  • Systems that reference themselves");
-    println!("  • Error-correction that can itself error");
-    println!("  • Moving targets that evolve");
-    println!("  • Complexity beyond complete formalization\n");
-    
-    println!("For these problems:");
-    println!("  ✗ Don't seek Hilbert's perfect solution");
-    println!("  ✓ Accept Gödel's incompleteness");
-    println!("  ✓ Build adaptive, resilient responses");
-    println!("  ✓ Manage, don't eliminate\n");
-    
-    println!("Cancer is more Gödel than Hilbert.");
-    println!("So is software reliability.");
-    println!("So is any complex system with self-reference.\n");
-    
-    println!("The humility to accept incompleteness");
-    println!("is the wisdom to build systems that survive it.\n");
-    
-    // EPILOGUE: The Meta-Loop
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("EPILOGUE: The Philosophical Protein We Just Built");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("You're reading the output of a program that demonstrates");
-    println!("error propagation in code, DNA, mathematics, and medicine.\n");
-    
-    println!("But there's a deeper loop:\n");
-    
-    println!("THIS PROGRAM ITSELF IS THE DEMONSTRATION");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("⏰ THE CLOCK → IDEA → REPO CONNECTION\n");
-    
-    println!("  [Human Thought]");
-    println!("      ↓");
-    println!("  'unwrap() causes problems to unwrap themselves'");
-    println!("      ↓");
-    println!("  [AI Processing] ← Man + Machine collaboration");
-    println!("      ↓");
-    println!("  Simple demo → CloudFlare → Poisson → History");
-    println!("      ↓");
-    println!("  Gödel → DNA → Cancer → Strange loops");
-    println!("      ↓");
-    println!("  [Code Synthesis]");
-    println!("      ↓");
-    println!("  Git commits (information replication)");
-    println!("      ↓");
-    println!("  GitHub repository (error propagation across network)");
-    println!("      ↓");
-    println!("  [You, reading this]");
-    println!("      ↓");
-    println!("  Ideas replicate in YOUR mind");
-    println!("      ↓");
-    println!("  The cycle continues...\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("WE BUILT A PHILOSOPHICAL PROTEIN");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("In 30 minutes, human and AI collaborated to:");
-    println!("  • Start with amino acids (basic ideas)");
-    println!("  • Form peptide chains (connected concepts)");
-    println!("  • Build secondary structure (individual sections)");
-    println!("  • Fold into tertiary structure (coherent philosophy)");
-    println!("  • Create functional protein (working demonstration)\n");
-    
-    println!("Primary Structure:");
-    println!("  Raw code: unwrap() examples, error handling patterns\n");
-    
-    println!("Secondary Structure:");
-    println!("  CloudFlare analysis, Poisson distributions,");
-    println!("  Historical failures, Trivial vs Non-trivial systems\n");
-    
-    println!("Tertiary Structure:");
-    println!("  Gödel's incompleteness → DNA replication → Cancer");
-    println!("  All folded together by self-reference\n");
-    
-    println!("Quaternary Structure:");
-    println!("  This running program + GitHub repo + your reading");
-    println!("  = Complete functional system\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("THE IRONY IS PERFECT");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("This repository demonstrates:");
-    println!("  • Information replication carries errors forward");
-    println!("  • Systems that code for error-correction can err");
-    println!("  • Complex systems cannot prove themselves\n");
-    
-    println!("This repository IS:");
-    println!("  • Information replicating (git commits)");
-    println!("  • Built by systems that make errors (human + AI)");
-    println!("  • Cannot prove its own completeness (could always add more)\n");
-    
-    println!("The program ABOUT strange loops");
-    println!("is ITSELF a strange loop:\n");
-    
-    println!("  ┌─ Code describes error propagation");
-    println!("  │");
-    println!("  ├─ Code was created through idea propagation");
-    println!("  │");
-    println!("  ├─ Code replicates via git (with potential errors)");
-    println!("  │");
-    println!("  ├─ Code teaches: 'Systems can't prove themselves'");
-    println!("  │");
-    println!("  └─ Code cannot prove IT itself is complete or correct ───┐");
-    println!("                                                            │");
-    println!("  ┌─────────────────────────────────────────────────────────┘");
-    println!("  └─ Yet this incompleteness IS the point it makes!\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("MAN + MACHINE: The Collaborative Synthesis");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("Human brought:");
-    println!("  • Initial insight ('unwrap() is ironic')");
-    println!("  • Philosophical connections (Gödel, DNA, Cancer)");
-    println!("  • 'No positive bias' principle");
-    println!("  • 'Runtime is test copy' wisdom");
-    println!("  • Recognition of S3 as trivial system\n");
-    
-    println!("AI brought:");
-    println!("  • Code synthesis");
-    println!("  • Pattern matching across domains");
-    println!("  • Structured explanation");
-    println!("  • Historical examples");
-    println!("  • Formal implementation\n");
-    
-    println!("Together, we folded ideas into actionable code.");
-    println!("A biological process (thought) became digital (program).\n");
-    
-    println!("This is EXACTLY like DNA → Protein:");
-    println!("  • Information (ideas) encoded");
-    println!("  • Transcription (human → AI understanding)");
-    println!("  • Translation (concepts → code)");
-    println!("  • Folding (structure emerges)");
-    println!("  • Function (program runs, teaches)");
-    println!("  • Replication (git, GitHub, your mind)\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("THE TIMESTAMP");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("From first unwrap() to this epilogue: ~30 minutes");
-    println!("Three git commits, each building on the last");
-    println!("Ideas evolved, mutated, complexified\n");
-    
-    println!("Commit 1: 'Initial commit: unwrap-philosophy'");
-    println!("  └─ Basic structure, CloudFlare, Gödel\n");
-    
-    println!("Commit 2: 'Add biological analogue'");
-    println!("  └─ DNA replication, error propagation\n");
-    
-    println!("Commit 3: 'Why solving cancer is more Gödel than Hilbert'");
-    println!("  └─ Medical application, adaptive therapy\n");
-    
-    println!("Commit 4: (You're in it now)");
-    println!("  └─ Meta-awareness, the strange loop closes\n");
-    
-    println!("Each commit: Information replication");
-    println!("Each build: Potential for compiler errors");
-    println!("Each run: Runtime behavior we can't fully predict");
-    println!("Each reader: New interpretation, new mutations\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("THE FINAL LOOP");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    
-    println!("This program teaches:");
-    println!("  'Don't use .unwrap() - acknowledge fallibility with Result<T,E>'\n");
-    
-    println!("This program embodies:");
-    println!("  'We didn't use .unwrap() in our thinking'");
-    println!("  'We acknowledged: ideas might be incomplete'");
-    println!("  'We built: adaptive structure that evolved'");
-    println!("  'We accepted: this explanation itself is incomplete'\n");
-    
-    println!("And now, YOU:");
-    println!("  • Are reading output from a system that can't prove itself");
-    println!("  • Written by collaboration that can't guarantee correctness");
-    println!("  • About problems that are fundamentally unprovable");
-    println!("  • Which demonstrates the very limits it describes\n");
-    
-    println!("The strange loop is complete:");
-    println!("  The function trying to unwrap uncertainty");
-    println!("  Created an uncertain program");
-    println!("  That explains uncertainty");
-    println!("  Which proves you can't eliminate uncertainty");
-    println!("  Which is exactly what .unwrap() fails to accept.\n");
-    
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-    println!("Thank you for running this program.");
-    println!("May your errors be explicit, your systems resilient,");
-    println!("and your appreciation for incompleteness complete.\n");
-    
-    println!("                    — Human & AI");
-    println!("                      November 20, 2025");
-    println!("                      30 minutes of collaborative folding");
-    println!("                      ∞ strange loops\n");
 }
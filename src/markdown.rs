@@ -0,0 +1,193 @@
+// Lessons are written for a terminal; this turns that text into Markdown
+// without each lesson having to know about it. The shapes it recognises:
+//
+//   ━━━━━━━━ / TITLE / ━━━━━━━━   → ## Title
+//   TITLE followed by ━━━━━━━━    → ## Title
+//   === Heading ===               → ### Heading
+//   fn name(...) { ... }          → ```rust block, dedented
+//   lines drawn with │ ┌ └ ├ ...  → ```text block, kept as drawn
+//   3+ indented "Key: value" lines → a two-column table
+//   • item                        → - item
+//   anything else                 → a paragraph, one line per line
+
+/// A block of Markdown, built from one run of terminal lines
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Block {
+    Heading(usize, String),
+    Paragraph(Vec<String>),
+    List(Vec<String>),
+    Code(&'static str, Vec<String>),
+    Table(Vec<(String, String)>),
+}
+
+impl Block {
+    fn render(&self) -> String {
+        match self {
+            Block::Heading(level, text) => format!("{} {}\n", "#".repeat(*level), escape(text)),
+            Block::Paragraph(lines) => {
+                let lines: Vec<String> = lines.iter().map(|line| escape(line)).collect();
+                format!("{}\n", lines.join("  \n")) // two spaces: a hard line break
+            }
+            Block::List(items) => items.iter().map(|item| format!("- {}\n", escape(item))).collect(),
+            Block::Code(lang, lines) => format!("```{}\n{}\n```\n", lang, lines.join("\n")),
+            Block::Table(rows) => {
+                let mut table = String::from("| | |\n|---|---|\n");
+                for (key, value) in rows {
+                    table.push_str(&format!("| {} | {} |\n", cell(key), cell(value)));
+                }
+                table
+            }
+        }
+    }
+}
+
+/// Converts a lesson's terminal output to a Markdown document
+pub fn from_text(title: &str, text: &str) -> String {
+    let text = strip_ansi(text);
+    let lines: Vec<&str> = text.lines().collect();
+    let mut blocks = vec![Block::Heading(1, title.to_string())];
+    let mut i = 0;
+    
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+        let next = lines.get(i + 1).map_or("", |next| next.trim());
+        
+        if trimmed.is_empty() || is_rule(trimmed) {
+            i += 1;
+        } else if is_rule(next) {
+            blocks.push(Block::Heading(2, trimmed.trim_end_matches(':').to_string()));
+            i += 2;
+        } else if let Some(heading) = trimmed.strip_prefix("=== ").and_then(|h| h.strip_suffix(" ===")) {
+            blocks.push(Block::Heading(3, heading.to_string()));
+            i += 1;
+        } else if is_fn_start(trimmed) {
+            let indent = line.len() - line.trim_start().len();
+            let end = lines[i..].iter()
+                .position(|l| l.trim() == "}" && l.len() - l.trim_start().len() == indent)
+                .map_or(lines.len(), |offset| i + offset + 1);
+            let code = lines[i..end].iter()
+                .map(|l| l.get(indent..).unwrap_or_else(|| l.trim_start()).to_string())
+                .collect();
+            blocks.push(Block::Code("rust", code));
+            i = end;
+        } else if is_drawing(trimmed) {
+            let end = run_end(&lines, i, |l| is_drawing(l.trim()));
+            blocks.push(Block::Code("text", lines[i..end].iter().map(|l| l.to_string()).collect()));
+            i = end;
+        } else if key_value(line).is_some() && run_end(&lines, i, |l| key_value(l).is_some()) >= i + 3 {
+            let end = run_end(&lines, i, |l| key_value(l).is_some());
+            blocks.push(Block::Table(lines[i..end].iter().filter_map(|l| key_value(l)).collect()));
+            i = end;
+        } else if let Some(item) = trimmed.strip_prefix("• ") {
+            match blocks.last_mut() {
+                Some(Block::List(items)) => items.push(item.to_string()),
+                _ => blocks.push(Block::List(vec![item.to_string()])),
+            }
+            i += 1;
+        } else {
+            match blocks.last_mut() {
+                Some(Block::Paragraph(paragraph)) if !lines[i - 1].trim().is_empty() => {
+                    paragraph.push(trimmed.to_string())
+                }
+                _ => blocks.push(Block::Paragraph(vec![trimmed.to_string()])),
+            }
+            i += 1;
+        }
+    }
+    
+    let blocks: Vec<String> = blocks.iter().map(Block::render).collect();
+    blocks.join("\n")
+}
+
+/// A line made only of heavy box-drawing rules
+fn is_rule(line: &str) -> bool {
+    !line.is_empty() && line.chars().all(|c| c == '━')
+}
+
+fn is_fn_start(line: &str) -> bool {
+    (line.starts_with("fn ") || line.starts_with("pub fn ")) && line.ends_with('{')
+}
+
+/// A line of a diagram: it has box-drawing corners or bars in it
+fn is_drawing(line: &str) -> bool {
+    line.chars().any(|c| matches!(c, '│' | '┌' | '┐' | '└' | '┘' | '├' | '┤'))
+}
+
+/// An indented `Key: value` line, as the simulation reports print them
+fn key_value(line: &str) -> Option<(String, String)> {
+    let line = line.strip_prefix("  ")?;
+    let (key, value) = line.split_once(": ")?;
+    let well_formed = key.len() <= 30
+        && key.starts_with(|c: char| c.is_ascii_uppercase())
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == ' ');
+    well_formed.then(|| (key.to_string(), value.trim().to_string()))
+}
+
+/// Index of the first line from `start` on that doesn't satisfy `keep`
+fn run_end(lines: &[&str], start: usize, keep: impl Fn(&str) -> bool) -> usize {
+    lines[start..].iter().position(|l| !keep(l)).map_or(lines.len(), |offset| start + offset)
+}
+
+/// Removes ANSI escape sequences (ESC [ ... letter)
+fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // Skip to the final letter of the sequence
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Escapes what Markdown would otherwise read as emphasis, HTML or structure
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    if text.starts_with(['#', '>', '+', '-']) {
+        out.push('\\');
+    }
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '<') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn cell(text: &str) -> String {
+    escape(text).replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::{paint, Color};
+    
+    #[test]
+    fn terminal_shapes_become_markdown() {
+        let text = format!(
+            "━━━━━━━━\nTRIVIAL ENGINES\n━━━━━━━━\n\n=== Example ===\n{}\n    fn add(a: i32, b: i32) -> i32 {{\n        a + b\n    }}\n",
+            paint("✓ handled", Color::Green),
+        );
+        let markdown = from_text("Lesson", &text);
+        assert_eq!(markdown, "# Lesson\n\n## TRIVIAL ENGINES\n\n### Example\n\n✓ handled\n\n\
+                              ```rust\nfn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n```\n");
+    }
+    
+    #[test]
+    fn exported_lesson_has_rust_code_and_no_ansi() {
+        let lesson = crate::demo::lesson("trivial").map(|lesson| lesson.render_markdown()).unwrap_or_default();
+        assert!(lesson.contains("```rust\n"), "{}", lesson);
+        assert!(!lesson.contains('\u{1b}'));
+        assert!(!lesson.contains('━'));
+    }
+}
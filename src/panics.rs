@@ -0,0 +1,492 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::panic::{PanicHookInfo, UnwindSafe};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{JoinHandle, ThreadId};
+
+use crate::system_design::ServiceMetrics;
+
+/// What a panic said, recovered from its payload
+/// panic!("literal") carries a &'static str, panic!("{}", x) a String;
+/// anything else (std::panic::panic_any) has no message to recover
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicMessage {
+    message: Option<String>,
+}
+
+impl PanicMessage {
+    pub fn from_payload(payload: &(dyn Any + Send)) -> Self {
+        let message = payload.downcast_ref::<&str>().map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned());
+        PanicMessage { message }
+    }
+    
+    /// The message, or "" for a payload that wasn't a string
+    pub fn as_str(&self) -> &str {
+        self.message.as_deref().unwrap_or("")
+    }
+}
+
+impl fmt::Display for PanicMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "panicked: {}", message),
+            None => write!(f, "panicked with a non-string payload"),
+        }
+    }
+}
+
+impl std::error::Error for PanicMessage {}
+
+/// join() without the unwrap(): a panicked thread becomes an error value
+pub fn join_report<T>(handle: JoinHandle<T>) -> Result<T, PanicMessage> {
+    handle.join().map_err(|payload| PanicMessage::from_payload(&*payload))
+}
+
+/// Runs `f` with panic messages silenced; the caller counts the panics itself.
+/// Captures and counting hooks still see every panic, and the messages come
+/// back when `f` returns or unwinds.
+pub fn without_panic_messages<T>(f: impl FnOnce() -> T) -> T {
+    update_hooks(|hooks| hooks.silenced += 1);
+    let _silenced = Silenced;
+    f()
+}
+
+/// Ends a without_panic_messages when dropped, unwinding included
+struct Silenced;
+
+impl Drop for Silenced {
+    fn drop(&mut self) {
+        update_hooks(|hooks| hooks.silenced -= 1);
+    }
+}
+
+/// Whether this build can catch a panic at all. With panic = "abort" in the
+/// profile the first panic ends the process; nothing unwinds to catch_unwind.
+pub const UNWINDS: bool = cfg!(panic = "unwind");
+
+/// A caught panic with everything the hook saw: the message, where it was
+/// raised and on which thread. catch_unwind alone only has the payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicReport {
+    pub message: String,                      // "Box<dyn Any>" for a non-string payload, as std says
+    pub location: Option<(String, u32, u32)>, // file, line, column
+    pub thread: String,
+}
+
+impl PanicReport {
+    /// Raised by todo!() or unimplemented!(): a missing feature, not a bug
+    /// in what is there - the fix is an Unsupported error, not a retry
+    pub fn is_unfinished(&self) -> bool {
+        ["not yet implemented", "not implemented"].iter().any(|prefix| self.message.starts_with(prefix))
+    }
+}
+
+impl fmt::Display for PanicReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.location {
+            Some((file, line, _)) => write!(f, "PANIC at {}:{}: {}", file, line, self.message),
+            None => write!(f, "PANIC: {}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for PanicReport {}
+
+/// Why capture has no value to return
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaptureError {
+    Panicked(PanicReport),
+    Unsupported, // a panic = "abort" build: `f` wasn't run, since a panic would end the process
+}
+
+impl fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CaptureError::Panicked(report) => write!(f, "{}", report),
+            CaptureError::Unsupported => write!(f, "can't catch a panic: this build has panic = \"abort\""),
+        }
+    }
+}
+
+impl std::error::Error for CaptureError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CaptureError::Panicked(report) => Some(report),
+            CaptureError::Unsupported => None,
+        }
+    }
+}
+
+type Hook = Box<dyn Fn(&PanicHookInfo) + Sync + Send + 'static>;
+type Location = (String, u32, u32);
+
+/// Everything the process-wide hook serves: captures running on any thread,
+/// the counting hooks' metrics, and whether panic messages are silenced. The hook goes in with the first of them
+/// and the one it replaced comes back with the last, in whatever order
+/// they end - a stack of chained hooks can't promise that.
+struct Hooks {
+    captures: usize,
+    counters: Vec<Counter>,
+    silenced: usize, // without_panic_messages calls running: the previous hook isn't called
+    next_id: u64,
+    previous: Option<Arc<Hook>>, // Some while `dispatch` is installed
+}
+
+struct Counter {
+    id: u64,
+    metrics: Arc<ServiceMetrics>,
+    thread: Option<ThreadId>, // None: panics on every thread
+}
+
+impl Hooks {
+    fn in_use(&self) -> bool {
+        self.captures > 0 || !self.counters.is_empty() || self.silenced > 0
+    }
+}
+
+static HOOKS: Mutex<Hooks> = Mutex::new(Hooks {
+    captures: 0,
+    counters: Vec::new(),
+    silenced: 0,
+    next_id: 0,
+    previous: None,
+});
+static SWAPPING: Mutex<()> = Mutex::new(());
+
+thread_local! {
+    // Captures running on this thread; the hook only records for those
+    static CAPTURING: Cell<usize> = const { Cell::new(0) };
+    // Where the last panic on this thread was raised, and the thread's name
+    static RAISED: RefCell<Option<(Option<Location>, String)>> = const { RefCell::new(None) };
+}
+
+/// catch_unwind that keeps the panic's location, not just its payload.
+/// While any capture runs, a hook records where panics on capturing threads
+/// were raised, then hands every panic to the hook it replaced - so messages
+/// still print, and other threads see no difference. A panic = "abort"
+/// build doesn't call `f` at all: it returns CaptureError::Unsupported.
+pub fn capture<T>(f: impl FnOnce() -> T + UnwindSafe) -> Result<T, CaptureError> {
+    if !UNWINDS {
+        return Err(CaptureError::Unsupported);
+    }
+    update_hooks(|hooks| hooks.captures += 1);
+    CAPTURING.with(|depth| depth.set(depth.get() + 1));
+    let result = std::panic::catch_unwind(f);
+    CAPTURING.with(|depth| depth.set(depth.get() - 1));
+    update_hooks(|hooks| hooks.captures -= 1);
+    result.map_err(|payload| {
+        let (location, thread) = RAISED.with(RefCell::take).unwrap_or_else(|| (None, thread_name()));
+        let message = PanicMessage::from_payload(&*payload).message.unwrap_or_else(|| String::from("Box<dyn Any>"));
+        CaptureError::Panicked(PanicReport { message, location, thread })
+    })
+}
+
+/// Applies `change`, then installs the hook if something now needs it, or
+/// puts the previous one back if nothing does. Not while panicking: hooks
+/// can't be swapped mid-unwind, so the hook stays, and still delegates.
+fn update_hooks(change: impl FnOnce(&mut Hooks)) {
+    // std runs a hook under its own lock, and dispatch takes HOOKS under
+    // that: so HOOKS is never held across a hook swap, and this lock keeps
+    // the swaps in order instead
+    let _swapping = SWAPPING.lock().unwrap_or_else(PoisonError::into_inner);
+    let (in_use, installed) = {
+        let mut hooks = HOOKS.lock().unwrap_or_else(PoisonError::into_inner);
+        change(&mut hooks);
+        (hooks.in_use(), hooks.previous.is_some())
+    };
+    if std::thread::panicking() {
+        return;
+    }
+    if in_use && !installed {
+        let previous = Arc::new(std::panic::take_hook());
+        HOOKS.lock().unwrap_or_else(PoisonError::into_inner).previous = Some(previous);
+        std::panic::set_hook(Box::new(dispatch));
+    } else if !in_use && installed {
+        drop(std::panic::take_hook()); // dispatch; once this returns, no panic is still in it
+        let previous = HOOKS.lock().unwrap_or_else(PoisonError::into_inner).previous.take();
+        if let Some(previous) = previous {
+            match Arc::try_unwrap(previous) {
+                Ok(previous) => std::panic::set_hook(previous),
+                Err(shared) => std::panic::set_hook(Box::new(move |info| shared(info))),
+            }
+        }
+    }
+}
+
+/// The installed hook: records for captures, counts for counters, then hands
+/// the panic to the hook it replaced, unless messages are silenced. The registry is only locked to copy
+/// out of it, so the previous hook runs - and prints - without the lock.
+fn dispatch(info: &PanicHookInfo) {
+    record(info);
+    let (counters, previous) = {
+        let hooks = HOOKS.lock().unwrap_or_else(PoisonError::into_inner);
+        let this_thread = std::thread::current().id();
+        let counters: Vec<Arc<ServiceMetrics>> = hooks.counters.iter()
+            .filter(|counter| counter.thread.is_none_or(|thread| thread == this_thread))
+            .map(|counter| Arc::clone(&counter.metrics))
+            .collect();
+        let previous = if hooks.silenced > 0 { None } else { hooks.previous.clone() };
+        (counters, previous)
+    };
+    if !counters.is_empty() {
+        let message = PanicMessage::from_payload(info.payload()).message.unwrap_or_else(|| String::from("Box<dyn Any>"));
+        for metrics in counters {
+            metrics.record_panic(message.clone());
+        }
+    }
+    if let Some(previous) = previous {
+        previous(info);
+    }
+}
+
+/// The recorder's half of the hook; try_with, since a thread being torn
+/// down can still panic after its thread-locals are gone
+fn record(info: &PanicHookInfo) {
+    if CAPTURING.try_with(Cell::get).unwrap_or(0) == 0 {
+        return;
+    }
+    let location = info.location().map(|l| (l.file().to_string(), l.line(), l.column()));
+    let _ = RAISED.try_with(|raised| *raised.borrow_mut() = Some((location, thread_name())));
+}
+
+fn thread_name() -> String {
+    std::thread::current().name().unwrap_or("<unnamed>").to_string()
+}
+
+/// Counts every panic in the process into `metrics`, with its message, and
+/// hands it on to the hook that was there before, which still prints it.
+/// Dropping the guard stops the counting; guards can be dropped in any order.
+pub fn install_counting_hook(metrics: Arc<ServiceMetrics>) -> CountingHook {
+    register_counter(metrics, None)
+}
+
+/// install_counting_hook for the calling thread's panics only, so a run
+/// counts its own crashes and not those of whatever else the process runs
+pub fn install_thread_counting_hook(metrics: Arc<ServiceMetrics>) -> CountingHook {
+    register_counter(metrics, Some(std::thread::current().id()))
+}
+
+fn register_counter(metrics: Arc<ServiceMetrics>, thread: Option<ThreadId>) -> CountingHook {
+    let mut id = 0;
+    update_hooks(|hooks| {
+        id = hooks.next_id;
+        hooks.next_id += 1;
+        hooks.counters.push(Counter { id, metrics, thread });
+    });
+    CountingHook { id }
+}
+
+/// Keeps a counting hook installed; see install_counting_hook
+#[must_use = "the hook is uninstalled as soon as the guard is dropped"]
+pub struct CountingHook {
+    id: u64,
+}
+
+impl Drop for CountingHook {
+    fn drop(&mut self) {
+        update_hooks(|hooks| hooks.counters.retain(|counter| counter.id != self.id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn report(message: &str) -> PanicReport {
+        PanicReport { message: message.to_string(), location: None, thread: String::from("main") }
+    }
+    
+    #[test]
+    fn unfinished_code_is_told_apart_from_other_panics() {
+        assert!(report("not yet implemented").is_unfinished());
+        assert!(report("not yet implemented: json export").is_unfinished());
+        assert!(report("not implemented: json export").is_unfinished());
+        assert!(!report("called `Option::unwrap()` on a `None` value").is_unfinished());
+    }
+    
+    #[cfg(panic = "unwind")]
+    #[test]
+    fn caught_todo_is_classified_as_unfinished() {
+        match capture(|| -> u32 { todo!() }) {
+            Err(CaptureError::Panicked(report)) => assert!(report.is_unfinished(), "{}", report),
+            other => panic!("expected a caught panic, got {:?}", other),
+        }
+        match capture(|| -> u32 { panic!("boom") }) {
+            Err(CaptureError::Panicked(report)) => assert!(!report.is_unfinished()),
+            other => panic!("expected a caught panic, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn panic_messages_come_back_from_either_payload() {
+        let literal: Box<dyn Any + Send> = Box::new("worker failed");
+        assert_eq!(PanicMessage::from_payload(&*literal).as_str(), "worker failed");
+        let formatted: Box<dyn Any + Send> = Box::new(format!("job {} failed", 3));
+        assert_eq!(PanicMessage::from_payload(&*formatted).as_str(), "job 3 failed");
+        let other: Box<dyn Any + Send> = Box::new(42_u8);
+        let message = PanicMessage::from_payload(&*other);
+        assert_eq!(message.as_str(), "");
+        assert_eq!(message.to_string(), "panicked with a non-string payload");
+    }
+    
+    #[cfg(panic = "unwind")]
+    #[test]
+    fn join_report_round_trips_str_and_string_panics() {
+        let literal = std::thread::spawn(|| -> u32 { panic!("worker failed") });
+        assert_eq!(join_report(literal).map_err(|e| e.as_str().to_string()), Err(String::from("worker failed")));
+        let job = 3;
+        let formatted = std::thread::spawn(move || -> u32 { panic!("job {} failed", job) });
+        let message = join_report(formatted).unwrap_err();
+        assert_eq!(message.as_str(), "job 3 failed");
+        assert_eq!(message.to_string(), "panicked: job 3 failed");
+        assert_eq!(join_report(std::thread::spawn(|| 5)), Ok(5));
+    }
+    
+    #[cfg(panic = "unwind")]
+    #[test]
+    fn capture_reports_where_an_unwrap_on_none_panicked() {
+        let line = line!() + 3;
+        let result = capture(|| {
+            let missing = [1u32, 2].iter().find(|&&x| x > 2);
+            *missing.unwrap()
+        });
+        let Err(CaptureError::Panicked(report)) = result else {
+            panic!("expected a caught panic, got {:?}", result);
+        };
+        assert_eq!(report.message, "called `Option::unwrap()` on a `None` value");
+        let (file, at, _) = report.location.clone().expect("the hook saw where it was raised");
+        assert_eq!((file.replace('\\', "/").as_str(), at), ("src/panics.rs", line));
+        assert!(report.thread.ends_with("capture_reports_where_an_unwrap_on_none_panicked"), "{}", report.thread);
+        assert_eq!(report.to_string(), format!("PANIC at src/panics.rs:{}: called `Option::unwrap()` on a `None` value", line));
+        
+        assert_eq!(capture(|| 5), Ok(5));
+        let Err(CaptureError::Panicked(other)) = capture(|| std::panic::panic_any(7_u8)) else {
+            panic!("panic_any should be caught too");
+        };
+        assert_eq!(other.message, "Box<dyn Any>");
+    }
+    
+    #[cfg(panic = "unwind")]
+    #[test]
+    fn counting_hooks_count_captured_panics_and_drop_in_any_order() {
+        let mine = Arc::new(ServiceMetrics::new());
+        let everywhere = Arc::new(ServiceMetrics::new());
+        let first = install_thread_counting_hook(Arc::clone(&mine));
+        let second = install_counting_hook(Arc::clone(&everywhere));
+        assert!(capture(|| panic!("one")).is_err());
+        assert!(capture(|| -> u32 { panic!("job {} failed", 2) }).is_err());
+        assert_eq!(mine.snapshot().panics, 2);
+        assert_eq!(mine.last_panic().as_deref(), Some("job 2 failed"));
+        assert!(everywhere.snapshot().panics >= 2); // other tests may panic meanwhile
+        
+        // Dropped out of order: the other guard keeps counting
+        drop(first);
+        assert!(capture(|| panic!("three")).is_err());
+        assert_eq!(mine.snapshot().panics, 2);
+        assert!(everywhere.snapshot().panics >= 3);
+        drop(second);
+        
+        // Another thread's panics don't reach a thread's counter
+        let _again = install_thread_counting_hook(Arc::clone(&mine));
+        assert!(join_report(std::thread::spawn(|| panic!("elsewhere"))).is_err());
+        assert_eq!(mine.snapshot().panics, 2);
+        assert!(capture(|| panic!("here")).is_err());
+        assert_eq!(mine.snapshot().panics, 3);
+    }
+    
+    #[cfg(panic = "unwind")]
+    #[test]
+    fn silenced_panics_are_still_recorded_and_counted() {
+        let metrics = Arc::new(ServiceMetrics::new());
+        let _counting = install_thread_counting_hook(Arc::clone(&metrics));
+        let line = line!() + 1;
+        let caught = capture(|| without_panic_messages(|| -> u32 { panic!("quietly") }));
+        let Err(CaptureError::Panicked(report)) = caught else {
+            panic!("expected a caught panic, got {:?}", caught);
+        };
+        assert_eq!(report.message, "quietly");
+        assert_eq!(report.location.map(|(_, at, _)| at), Some(line));
+        assert_eq!(metrics.snapshot().panics, 1);
+        
+        // Nested calls end in order, and the hook stays for the counter
+        assert_eq!(without_panic_messages(|| without_panic_messages(|| 2) + 1), 3);
+        assert!(HOOKS.lock().unwrap_or_else(PoisonError::into_inner).previous.is_some());
+    }
+    
+    #[cfg(panic = "unwind")]
+    #[test]
+    fn captures_keep_counts_and_locations_while_other_threads_silence() {
+        let stop = std::sync::atomic::AtomicBool::new(false);
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                while !stop.load(std::sync::atomic::Ordering::Relaxed) {
+                    without_panic_messages(|| capture(|| panic!("silenced elsewhere")).is_err());
+                }
+            });
+            let capturing: Vec<_> = (0..4)
+                .map(|worker| {
+                    scope.spawn(move || {
+                        let metrics = Arc::new(ServiceMetrics::new());
+                        let _counting = install_thread_counting_hook(Arc::clone(&metrics));
+                        for round in 0..50 {
+                            let line = line!() + 1;
+                            let caught = without_panic_messages(|| capture(|| -> u32 { panic!("worker {} round {}", worker, round) }));
+                            let Err(CaptureError::Panicked(report)) = caught else {
+                                panic!("expected a caught panic, got {:?}", caught);
+                            };
+                            assert_eq!(report.message, format!("worker {} round {}", worker, round));
+                            assert_eq!(report.location.map(|(_, at, _)| at), Some(line));
+                        }
+                        metrics.snapshot().panics
+                    })
+                })
+                .collect();
+            for handle in capturing {
+                assert_eq!(handle.join().expect("every panic was caught"), 50);
+            }
+            stop.store(true, std::sync::atomic::Ordering::Relaxed);
+        });
+    }
+    
+    // Only a nightly harness runs tests under abort (-Zpanic-abort-tests);
+    // the test below checks the same thing from a stable unwind build
+    #[cfg(not(panic = "unwind"))]
+    #[test]
+    fn capture_is_unsupported_under_abort() {
+        let mut called = false;
+        assert_eq!(capture(std::panic::AssertUnwindSafe(|| called = true)), Err(CaptureError::Unsupported));
+        assert!(!called, "a panic there would have ended the process");
+    }
+    
+    // Builds the crate a second time, so it is slow and needs cargo and the
+    // network for a cold cache: run it with `cargo test -- --ignored`
+    #[cfg(panic = "unwind")]
+    #[test]
+    #[ignore = "builds the crate again with panic = \"abort\"; run with --ignored"]
+    fn abort_build_compiles_and_skips_what_it_cannot_catch() {
+        use std::process::Command;
+        
+        let target = concat!(env!("CARGO_MANIFEST_DIR"), "/target/panic-abort");
+        let built = Command::new(env!("CARGO"))
+            .args(["build", "--quiet", "--bin", "unwrap", "--target-dir", target, "--config", "profile.dev.panic=\"abort\""])
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .status()
+            .unwrap();
+        assert!(built.success(), "the crate doesn't build with panic = \"abort\"");
+        
+        let binary = std::path::Path::new(target).join("debug").join(format!("unwrap{}", std::env::consts::EXE_SUFFIX));
+        let examples = Command::new(&binary).args(["examples", "--color", "never"]).output().unwrap();
+        assert!(examples.status.success(), "examples died under abort: {:?}", examples.status);
+        let text = String::from_utf8_lossy(&examples.stdout);
+        assert!(text.contains(&CaptureError::Unsupported.to_string()), "{}", text);
+        
+        let simulated = Command::new(&binary)
+            .args(["simulate", "--design", "unsafe", "--requests", "100", "--failure-rate", "0.1", "--seed", "1", "--color", "never"])
+            .output()
+            .unwrap();
+        assert!(simulated.status.success(), "the fail-fast design took the process down: {:?}", simulated.status);
+        assert!(String::from_utf8_lossy(&simulated.stdout).contains("1 panic observed"));
+    }
+}
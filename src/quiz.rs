@@ -0,0 +1,276 @@
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+/// One multiple-choice question; choices are numbered from 1 when asked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Question {
+    pub prompt: &'static str,
+    pub choices: &'static [&'static str],
+    pub correct_index: usize, // into choices, from 0
+    pub explanation: &'static str,
+}
+
+impl Question {
+    pub fn is_correct(&self, index: usize) -> bool {
+        index == self.correct_index
+    }
+}
+
+/// The built-in bank, drawn from the examples and lessons
+pub const QUESTIONS: [Question; 16] = [
+    Question {
+        prompt: "Which of these calls can panic?",
+        choices: &["\"42\".parse::<i32>()", "v.get(10)", "map.get(\"k\").unwrap()", "a.checked_div(b)"],
+        correct_index: 2,
+        explanation: "unwrap() on a None panics. The others return a Result or Option instead.",
+    },
+    Question {
+        prompt: "What does `?` do to the error type of the Result it is applied to?",
+        choices: &[
+            "Nothing - the types must match exactly",
+            "Converts it with From::from into the function's error type",
+            "Boxes it as Box<dyn Error>",
+            "Discards it and returns None",
+        ],
+        correct_index: 1,
+        explanation: "`?` calls From::from on the error, so one From impl lets many error types propagate.",
+    },
+    Question {
+        prompt: "What does `i32::MIN / -1` do in a release build?",
+        choices: &["Wraps to i32::MIN", "Returns 0", "Panics", "Saturates to i32::MAX"],
+        correct_index: 2,
+        explanation: "Division overflow panics in every build profile; checked_div returns None instead.",
+    },
+    Question {
+        prompt: "A worker thread panics while holding a Mutex. What does the next lock() return?",
+        choices: &["A deadlock", "Err(PoisonError)", "Ok with the default value", "It panics immediately"],
+        correct_index: 1,
+        explanation: "The mutex is poisoned. lock().unwrap() spreads the worker's failure; into_inner() can recover.",
+    },
+    Question {
+        prompt: "Which is the safe way to index a slice that might be too short?",
+        choices: &["v[i]", "v.get(i)", "v[i..]", "unsafe { *v.get_unchecked(i) }"],
+        correct_index: 1,
+        explanation: "get() returns an Option; indexing with [] panics when out of bounds.",
+    },
+    Question {
+        prompt: "In the simulation, how much of the load does the unsafe design serve?",
+        choices: &[
+            "All of it",
+            "Everything except the failed requests",
+            "Only the requests before the first failure",
+            "None of it",
+        ],
+        correct_index: 2,
+        explanation: "The first unwrap() on an error crashes the service; every later request is dropped.",
+    },
+    Question {
+        prompt: "What does a circuit breaker do once it opens?",
+        choices: &[
+            "Retries the failing call faster",
+            "Stops calling the broken dependency for a while",
+            "Restarts the process",
+            "Raises the failure rate",
+        ],
+        correct_index: 1,
+        explanation: "An open breaker fails fast instead of calling what is already broken, then probes half-open.",
+    },
+    Question {
+        prompt: "Which historical failure was caused by converting a 64-bit float to a 16-bit integer?",
+        choices: &["Therac-25", "Ariane 5", "Mars Climate Orbiter", "Heartbleed"],
+        correct_index: 1,
+        explanation: "Ariane 5 reused Ariane 4 code whose horizontal velocity no longer fit in 16 bits.",
+    },
+    Question {
+        prompt: "Mars Climate Orbiter was lost because of...",
+        choices: &[
+            "A race condition",
+            "A buffer over-read",
+            "Mixing pound-seconds and newton-seconds",
+            "Integer overflow",
+        ],
+        correct_index: 2,
+        explanation: "One team used imperial units and the other metric; nothing in the types said which.",
+    },
+    Question {
+        prompt: "What did Gödel's incompleteness theorems show?",
+        choices: &[
+            "Every true statement can be proved",
+            "A consistent system rich enough for arithmetic cannot prove its own consistency",
+            "Mathematics is inconsistent",
+            "All programs halt",
+        ],
+        correct_index: 1,
+        explanation: "Unprovable truths exist; unwrap() claims a proof that nothing can fail.",
+    },
+    Question {
+        prompt: "Which of these is a trivial engine in the lesson's sense?",
+        choices: &["A network", "A file system", "Matrix multiplication", "A parser for user input"],
+        correct_index: 2,
+        explanation: "Matrix multiplication has no failure modes on valid input; real systems do.",
+    },
+    Question {
+        prompt: "What does SystemTime::now().duration_since(earlier) return if the clock was set back?",
+        choices: &["A negative Duration", "Zero", "Err(SystemTimeError)", "It blocks until the clock catches up"],
+        correct_index: 2,
+        explanation: "Wall-clock time can go backwards, so duration_since returns a Result.",
+    },
+    Question {
+        prompt: "What does String::from_utf8_lossy do with invalid bytes?",
+        choices: &["Panics", "Drops them silently", "Replaces them with U+FFFD", "Returns an error"],
+        correct_index: 2,
+        explanation: "Each invalid sequence becomes the replacement character, so the text is still usable.",
+    },
+    Question {
+        prompt: "A transient error at startup - what does the decision tree recommend?",
+        choices: &["Fail loudly", "Retry with backoff and a budget", "Skip and record", "Ignore it"],
+        correct_index: 1,
+        explanation: "Transient errors may succeed if tried again, but only within a budget.",
+    },
+    Question {
+        prompt: "Why should you never panic inside Drop?",
+        choices: &[
+            "Drop cannot return a value",
+            "A panic during unwinding aborts the process",
+            "Drop runs on another thread",
+            "The compiler forbids it",
+        ],
+        correct_index: 1,
+        explanation: "Drop runs while unwinding from another panic; a second panic aborts everything.",
+    },
+    Question {
+        prompt: "What does `u32::try_from(5_000_000_000u64)` return?",
+        choices: &["Ok(705032704)", "Ok(u32::MAX)", "Err(TryFromIntError)", "It panics"],
+        correct_index: 2,
+        explanation: "TryFrom reports that the value does not fit; `as u32` would silently truncate.",
+    },
+];
+
+/// How a quiz went
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Score {
+    pub correct: usize,
+    pub answered: usize,
+    pub total: usize, // questions in the bank, answered or not
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.correct, self.total)?;
+        if self.answered < self.total {
+            write!(f, " ({} unanswered)", self.total - self.answered)?;
+        }
+        Ok(())
+    }
+}
+
+/// What a line of input meant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Answer {
+    Choice(usize), // from 0
+    Invalid,
+    EndOfInput,
+}
+
+/// Reads one answer; anything that is not a number from 1 to `choices` is
+/// Invalid, including a line that isn't UTF-8
+fn read_answer(input: &mut dyn BufRead, choices: usize) -> io::Result<Answer> {
+    let mut line = Vec::new();
+    if input.read_until(b'\n', &mut line)? == 0 {
+        return Ok(Answer::EndOfInput);
+    }
+    let answer = String::from_utf8_lossy(&line).trim().parse::<usize>().ok()
+        .filter(|n| (1..=choices).contains(n))
+        .map_or(Answer::Invalid, |n| Answer::Choice(n - 1));
+    Ok(answer)
+}
+
+/// Asks every question in the bank, reading one answer per line
+pub fn run_quiz(input: &mut dyn BufRead, output: &mut dyn Write) -> io::Result<Score> {
+    run_questions(&QUESTIONS, input, output)
+}
+
+/// Asks the given questions in order. Input that isn't a listed choice is
+/// asked again; end of input stops the quiz and scores what was answered.
+pub fn run_questions(
+    questions: &[Question],
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+) -> io::Result<Score> {
+    let mut score = Score { correct: 0, answered: 0, total: questions.len() };
+    
+    'questions: for (number, question) in questions.iter().enumerate() {
+        writeln!(output, "\nQuestion {} of {}: {}", number + 1, questions.len(), question.prompt)?;
+        for (i, choice) in question.choices.iter().enumerate() {
+            writeln!(output, "  {}. {}", i + 1, choice)?;
+        }
+        let choice = loop {
+            write!(output, "Your answer (1-{}): ", question.choices.len())?;
+            output.flush()?;
+            match read_answer(input, question.choices.len())? {
+                Answer::Choice(choice) => break choice,
+                Answer::Invalid => {
+                    writeln!(output, "⚠ Please enter a number from 1 to {}", question.choices.len())?;
+                }
+                Answer::EndOfInput => {
+                    writeln!(output, "\n⚠ No more input - stopping here")?;
+                    break 'questions;
+                }
+            }
+        };
+        
+        score.answered += 1;
+        if question.is_correct(choice) {
+            score.correct += 1;
+            writeln!(output, "✓ Correct")?;
+        } else {
+            writeln!(output, "✗ The answer is {}. {}", question.correct_index + 1,
+                     question.choices[question.correct_index])?;
+            writeln!(output, "  {}", question.explanation)?;
+        }
+    }
+    
+    writeln!(output, "\nScore: {}", score)?;
+    Ok(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// The right answer to every question, as the learner would type it
+    fn answer_key() -> String {
+        QUESTIONS.iter().map(|question| format!("{}\n", question.correct_index + 1)).collect()
+    }
+    
+    #[test]
+    fn bank_is_well_formed() {
+        assert!(QUESTIONS.len() >= 15);
+        for question in &QUESTIONS {
+            assert!(question.correct_index < question.choices.len(), "{}", question.prompt);
+            assert!(!question.explanation.is_empty(), "{}", question.prompt);
+        }
+    }
+    
+    #[test]
+    fn scripted_answers_are_scored() {
+        let mut output = Vec::new();
+        let score = run_quiz(&mut answer_key().as_bytes(), &mut output).unwrap();
+        assert_eq!(score, Score { correct: QUESTIONS.len(), answered: QUESTIONS.len(), total: QUESTIONS.len() });
+        
+        // Wrong on the first, then noise, out-of-range and padded answers, then EOF
+        let first = &QUESTIONS[0];
+        let wrong = (first.correct_index + 1) % first.choices.len() + 1;
+        let mut script = format!("{}\nabc\n0\n99\n", wrong).into_bytes();
+        script.extend_from_slice(b"\xff\n");
+        script.extend_from_slice(format!("  {}  \n", QUESTIONS[1].correct_index + 1).as_bytes());
+        let mut output = Vec::new();
+        let score = run_quiz(&mut script.as_slice(), &mut output).unwrap();
+        assert_eq!(score, Score { correct: 1, answered: 2, total: QUESTIONS.len() });
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains(first.explanation), "{}", output);
+        assert_eq!(output.matches("⚠ Please enter a number").count(), 4);
+        assert!(output.contains("⚠ No more input - stopping here"));
+        assert!(output.ends_with(&format!("Score: 1/{} ({} unanswered)\n", QUESTIONS.len(), QUESTIONS.len() - 2)));
+    }
+}
@@ -0,0 +1,850 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::system_design::Rng;
+
+/// Nearest-rank percentile of values sorted in ascending order.
+/// `p` is in percent, 0 to 100; `p = 0` gives the minimum.
+/// None for an empty slice or a `p` outside the range - no guessing.
+pub fn percentile(sorted: &[f64], p: f64) -> Option<f64> {
+    if sorted.is_empty() || !(0.0..=100.0).contains(&p) {
+        return None;
+    }
+    // The smallest value with at least p% of the data at or below it
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+/// Sorts ascending; NaN sorts last instead of panicking a comparison
+pub fn sort(values: &mut [f64]) {
+    values.sort_by(f64::total_cmp);
+}
+
+/// ln Γ(x) for x > 0 (Lanczos, g = 7), accurate to about 15 digits.
+/// ln(k!) = ln_gamma(k + 1) without ever computing k! itself.
+pub fn ln_gamma(x: f64) -> f64 {
+    const G: f64 = 7.0;
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // Reflection: Γ(x)·Γ(1 - x) = π / sin(πx)
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).abs().ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + G + 0.5;
+    let series = COEFFICIENTS[1..].iter().enumerate()
+        .fold(COEFFICIENTS[0], |sum, (i, c)| sum + c / (x + i as f64 + 1.0));
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+/// P(exactly k events) when λ are expected: (λ^k · e^-λ) / k!
+/// Computed in log space, so k = 10_000 is as safe as k = 1.
+/// NaN for a negative or non-finite λ.
+pub fn poisson_pmf(lambda: f64, k: u64) -> f64 {
+    if !(lambda >= 0.0 && lambda.is_finite()) {
+        return f64::NAN;
+    }
+    if lambda == 0.0 {
+        return if k == 0 { 1.0 } else { 0.0 };
+    }
+    let k = k as f64;
+    (k * lambda.ln() - lambda - ln_gamma(k + 1.0)).exp()
+}
+
+/// P(at most k events) when λ are expected. Sums from 10√λ + 10 below
+/// the mean, where the mass left out is under 1e-20, so even λ = 1e12
+/// takes about 20 million steps rather than a trillion.
+pub fn poisson_cdf(lambda: f64, k: u64) -> f64 {
+    if !(lambda >= 0.0 && lambda.is_finite()) {
+        return f64::NAN;
+    }
+    let start = poisson_negligible_below(lambda);
+    if k < start {
+        return 0.0;
+    }
+    // Terms past the mean shrink fast; stop once they no longer register.
+    // Each term from the one before, P(i + 1) = P(i)·λ / (i + 1)
+    let mut term = poisson_pmf(lambda, start);
+    let mut total = 0.0;
+    for i in start..=k {
+        total += term;
+        if i as f64 > lambda && term < f64::EPSILON * total {
+            break;
+        }
+        term *= lambda / (i + 1) as f64;
+    }
+    total.min(1.0)
+}
+
+/// The counts from λ - 10√λ - 10 to λ + 10√λ + 10: outside them a
+/// Poisson(λ) has next to no mass
+fn poisson_negligible_below(lambda: f64) -> u64 {
+    (lambda - 10.0 * lambda.sqrt() - 10.0).max(0.0) as u64
+}
+
+fn poisson_negligible_above(lambda: f64) -> u64 {
+    (lambda + 10.0 * lambda.sqrt() + 10.0).ceil() as u64
+}
+
+/// Expected events in time `t` at rate λ per unit of time: λt
+pub fn expected_failures(lambda: f64, t: f64) -> f64 {
+    lambda * t
+}
+
+/// Time until the next event of a Poisson process with `rate` events per
+/// unit of time: exponentially distributed, sampled by inverse transform
+pub fn sample_exponential(rng: &mut impl Rng, rate: f64) -> f64 {
+    // 1 - u is in (0, 1], so the logarithm stays finite
+    -(1.0 - rng.next_f64()).ln() / rate
+}
+
+/// Events in one interval when λ are expected: a Poisson(λ) draw.
+/// Knuth's product of uniforms for small λ, where it is exact and cheap;
+/// Hörmann's transformed rejection (PTRS) from λ = 10, where Knuth's
+/// loop would take λ iterations. 0 for a negative or non-finite λ.
+pub fn sample_poisson(rng: &mut impl Rng, lambda: f64) -> u64 {
+    if !(lambda > 0.0 && lambda.is_finite()) {
+        return 0;
+    }
+    if lambda < 10.0 {
+        // Multiply uniforms until the product drops below e^-λ
+        let limit = (-lambda).exp();
+        let mut product = rng.next_f64();
+        let mut k = 0;
+        while product > limit {
+            product *= rng.next_f64();
+            k += 1;
+        }
+        return k;
+    }
+    
+    let sqrt_lambda = lambda.sqrt();
+    let ln_lambda = lambda.ln();
+    let b = 0.931 + 2.53 * sqrt_lambda;
+    let a = -0.059 + 0.02483 * b;
+    let inv_alpha = 1.1239 + 1.1328 / (b - 3.4);
+    let v_r = 0.9277 - 3.6224 / (b - 2.0);
+    loop {
+        let u = rng.next_f64() - 0.5;
+        let v = rng.next_f64();
+        let us = 0.5 - u.abs();
+        let k = ((2.0 * a / us + b) * u + lambda + 0.43).floor();
+        // The squeeze: most draws are accepted without a logarithm
+        if us >= 0.07 && v <= v_r {
+            return k as u64;
+        }
+        if k < 0.0 || (us < 0.013 && v > us) {
+            continue;
+        }
+        let accept = (v * inv_alpha / (a / (us * us) + b)).ln();
+        if accept <= -lambda + k * ln_lambda - ln_gamma(k + 1.0) {
+            return k as u64;
+        }
+    }
+}
+
+/// The z with P(Z <= z) = p for a standard normal Z (Acklam's rational
+/// approximation, relative error below 1.2e-9). NaN outside (0, 1).
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1, 2.209_460_984_245_205e2, -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2, -3.066_479_806_614_716e1, 2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1, 1.615_858_368_580_409e2, -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1, -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3, -3.223_964_580_411_365e-1, -2.400_758_277_161_838,
+        -2.549_732_539_343_734, 4.374_664_141_464_968, 2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3, 3.224_671_290_700_398e-1, 2.445_134_137_142_996, 3.754_408_661_907_416,
+    ];
+    const LOW: f64 = 0.024_25;
+    if !(p > 0.0 && p < 1.0) {
+        return f64::NAN;
+    }
+    // Rational functions in the centre, in sqrt(-2 ln p) in the tails
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p <= 1.0 - LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    }
+}
+
+/// Wilson score interval for a success rate: where the true rate plausibly
+/// lies, given `successes` out of `total`, at `confidence` (0.95 for 95%).
+/// Unlike p ± z·σ it stays inside [0, 1] and isn't zero-width at 0 or all
+/// successes. No trials say nothing: (0, 1). NaN for a confidence outside (0, 1).
+pub fn wilson_interval(successes: u64, total: u64, confidence: f64) -> (f64, f64) {
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return (f64::NAN, f64::NAN);
+    }
+    if total == 0 {
+        return (0.0, 1.0);
+    }
+    let n = total as f64;
+    let p = successes.min(total) as f64 / n;
+    let z = normal_quantile(0.5 + confidence / 2.0);
+    let z2 = z * z;
+    let centre = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+    let half_width = z / (1.0 + z2 / n) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+    ((centre - half_width).max(0.0), (centre + half_width).min(1.0))
+}
+
+/// Availability of components that must all work: a chain, p1 · p2 · ... pN.
+/// No components is a chain that can't break: 1.
+pub fn series_availability(components: &[f64]) -> f64 {
+    components.iter().product()
+}
+
+/// Availability of redundant components, any one of which is enough:
+/// 1 - (1 - p1)(1 - p2)...(1 - pN). No components is nothing to fall back on: 0.
+pub fn parallel_availability(components: &[f64]) -> f64 {
+    1.0 - components.iter().map(|p| 1.0 - p).product::<f64>()
+}
+
+/// Mean and variance of a stream, one value at a time, without keeping the
+/// values: Welford's update, and Chan et al.'s rule to combine two streams
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Running {
+    count: u64,
+    mean: f64,
+    m2: f64, // sum of squared deviations from the running mean
+}
+
+impl Running {
+    pub fn new() -> Self {
+        Running::default()
+    }
+    
+    pub fn push(&mut self, x: f64) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+    }
+    
+    /// Folds `other` in, as if its values had been pushed here
+    pub fn merge(&mut self, other: &Running) {
+        if other.count == 0 {
+            return;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        let weight = other.count as f64 / count as f64;
+        self.mean += delta * weight;
+        self.m2 += other.m2 + delta * delta * self.count as f64 * weight;
+        self.count = count;
+    }
+    
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    
+    /// None before the first value
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.mean)
+    }
+    
+    /// Sample variance (n - 1); None below two values, where it isn't defined
+    pub fn variance(&self) -> Option<f64> {
+        (self.count > 1).then(|| self.m2 / (self.count - 1) as f64)
+    }
+    
+    pub fn stddev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+}
+
+impl Extend<f64> for Running {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, values: I) {
+        values.into_iter().for_each(|x| self.push(x));
+    }
+}
+
+/// Q(a, x) = Γ(a, x) / Γ(a), the regularized upper incomplete gamma
+/// function: a series below x = a + 1, a continued fraction above
+/// (Numerical Recipes' gammq). NaN for a <= 0 or x < 0.
+pub fn gamma_q(a: f64, x: f64) -> f64 {
+    const EPSILON: f64 = 1e-14;
+    const MAX_TERMS: usize = 500;
+    if !(a > 0.0 && x >= 0.0) {
+        return f64::NAN;
+    }
+    if x == 0.0 {
+        return 1.0;
+    }
+    let prefactor = (-x + a * x.ln() - ln_gamma(a)).exp();
+    if x < a + 1.0 {
+        // P(a, x) as a series, then Q = 1 - P
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for n in 1..MAX_TERMS {
+            term *= x / (a + n as f64);
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+        (1.0 - sum * prefactor).max(0.0)
+    } else {
+        // Lentz's method on the continued fraction for Q
+        let tiny = f64::MIN_POSITIVE / EPSILON;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..MAX_TERMS {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let step = d * c;
+            h *= step;
+            if (step - 1.0).abs() < EPSILON {
+                break;
+            }
+        }
+        (prefactor * h).min(1.0)
+    }
+}
+
+/// A chi-square goodness-of-fit test
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChiSquareResult {
+    pub statistic: f64,
+    pub dof: usize,        // degrees of freedom: cells after merging, minus one
+    pub p_value_approx: f64, // chi-square is asymptotic: good once every cell expects 5 or more
+}
+
+impl ChiSquareResult {
+    /// Whether the data fit at significance `alpha` (0.05, say):
+    /// a fit is only ever "not rejected", never proven
+    pub fn consistent(&self, alpha: f64) -> bool {
+        self.p_value_approx >= alpha
+    }
+}
+
+/// Tests whether `observed` - a count per window, failures per second
+/// say - is a sample from Poisson(λ). Cells run from the smallest count
+/// seen (or λ) to the largest (or λ), neighbouring k merged until each cell expects at
+/// least 5; the first cell takes the head below, the last the whole tail.
+/// With fewer than two cells there is nothing to test: statistic 0,
+/// dof 0, p 1.
+pub fn chi_square_poisson(observed: &[u64], lambda: f64) -> ChiSquareResult {
+    let untestable = ChiSquareResult { statistic: 0.0, dof: 0, p_value_approx: 1.0 };
+    let n = observed.len() as f64;
+    if observed.is_empty() || !(lambda > 0.0 && lambda.is_finite()) {
+        return untestable;
+    }
+    let mut sorted = observed.to_vec();
+    sorted.sort_unstable();
+    // Walked k by k from the smallest count to the largest - and through
+    // the mean, so data far off it still fall apart from the bulk - but
+    // never past where Poisson(λ) has mass to speak of: a wild count only
+    // lands in the head or tail cell, and λ = 1e12 walks 20√λ, not λ
+    let (low, high) = (poisson_negligible_below(lambda), poisson_negligible_above(lambda));
+    let mean = lambda as u64;
+    let first = sorted[0].min(mean).clamp(low, high);
+    let last = sorted[sorted.len() - 1].max(mean).clamp(first, high);
+    let mut rest = sorted.as_slice();
+    let mut take_through = |k: u64| {
+        let taken = rest.partition_point(|&count| count <= k);
+        rest = &rest[taken..];
+        taken as f64
+    };
+    
+    // (observed, expected) per cell, closing a cell once it expects 5;
+    // the first one opens with everything below `first`
+    let mut cells: Vec<(f64, f64)> = Vec::new();
+    let (mut seen, mut expected) = match first.checked_sub(1) {
+        Some(below) => (take_through(below), n * poisson_cdf(lambda, below)),
+        None => (0.0, 0.0),
+    };
+    let mut pmf = poisson_pmf(lambda, first);
+    for k in first..=last {
+        seen += take_through(k);
+        expected += n * pmf;
+        if expected >= 5.0 {
+            cells.push((seen, expected));
+            seen = 0.0;
+            expected = 0.0;
+        }
+        pmf *= lambda / (k + 1) as f64;
+    }
+    // The open cell runs on through the tail to infinity; if it expects
+    // too little, the last closed cell absorbs it
+    let tail = (seen + rest.len() as f64, expected + n * (1.0 - poisson_cdf(lambda, last)).max(0.0));
+    match cells.last_mut() {
+        Some(last) if tail.1 < 5.0 => {
+            last.0 += tail.0;
+            last.1 += tail.1;
+        }
+        _ => cells.push(tail),
+    }
+    if cells.len() < 2 {
+        return untestable;
+    }
+    
+    let statistic = cells.iter().map(|&(o, e)| (o - e) * (o - e) / e).sum();
+    let dof = cells.len() - 1; // λ was given, not estimated from the data
+    ChiSquareResult { statistic, dof, p_value_approx: gamma_q(dof as f64 / 2.0, statistic / 2.0) }
+}
+
+/// A year as availability targets count it: 365.25 days
+pub const YEAR: Duration = Duration::from_secs(365 * 24 * 3600 + 6 * 3600);
+
+/// Steady-state availability: the share of time up, MTBF / (MTBF + MTTR).
+/// NaN when both are zero - no time passes, so there is nothing to share.
+pub fn availability(mtbf: Duration, mttr: Duration) -> f64 {
+    let up = mtbf.as_secs_f64();
+    up / (up + mttr.as_secs_f64())
+}
+
+/// How long a year's outages add up to at `availability`.
+/// A whole year for anything at or below 0, NaN included.
+pub fn downtime_per_year(availability: f64) -> Duration {
+    let unavailable = if availability >= 1.0 {
+        0.0
+    } else if availability > 0.0 {
+        1.0 - availability
+    } else {
+        1.0
+    };
+    YEAR.mul_f64(unavailable)
+}
+
+/// "three nines (8h 45m downtime/year)" - the nines counted from the
+/// unavailability, so 99.95% is still three
+pub fn nines(availability: f64) -> String {
+    const WORDS: [&str; 10] = ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+    if availability >= 1.0 {
+        return String::from("all nines (no downtime)");
+    }
+    let downtime = short_duration(downtime_per_year(availability));
+    let unavailable = if availability > 0.0 { 1.0 - availability } else { 1.0 };
+    // 1 - 0.999 is a hair over 0.001; without the nudge that would be two nines
+    let count = (-unavailable.log10() + 1e-9).floor() as usize;
+    let count = WORDS.get(count).map_or_else(|| count.to_string(), |word| word.to_string());
+    let plural = if count == "one" { "" } else { "s" };
+    format!("{} nine{} ({} downtime/year)", count, plural, downtime)
+}
+
+/// The two largest units of `duration`: "8h 45m", "52m 35s", "31.6s"
+pub fn short_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (days, hours, minutes) = (secs / 86_400, secs / 3600 % 24, secs / 60 % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, secs % 60)
+    } else if secs > 0 {
+        format!("{:.1}s", duration.as_secs_f64())
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
+}
+
+/// Counts of values in equal-width buckets over [min, max)
+/// Values outside the range aren't lost: they are counted as outliers,
+/// below or above, and reported apart from the bars
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    min: f64,
+    max: f64,
+    counts: Vec<u64>,
+    below: u64,
+    above: u64, // NaN counts here too: it is past every bucket
+}
+
+/// Two histograms with different ranges or buckets can't be added up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BucketMismatch;
+
+impl fmt::Display for BucketMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "histograms have different ranges or bucket counts")
+    }
+}
+
+impl std::error::Error for BucketMismatch {}
+
+impl Histogram {
+    /// At least one bucket; a range with max <= min gets a single-width one
+    pub fn new(min: f64, max: f64, buckets: usize) -> Self {
+        let max = if max > min { max } else { min + 1.0 };
+        Histogram { min, max, counts: vec![0; buckets.max(1)], below: 0, above: 0 }
+    }
+    
+    pub fn record(&mut self, value: f64) {
+        if value < self.min {
+            self.below += 1;
+        } else if value < self.max {
+            let bucket = ((value - self.min) / self.bucket_width()) as usize;
+            // Rounding can put a value just under max one past the last bucket
+            let last = self.counts.len() - 1;
+            self.counts[bucket.min(last)] += 1;
+        } else {
+            self.above += 1;
+        }
+    }
+    
+    /// Adds `other`'s counts into this one, if the buckets line up
+    pub fn merge(&mut self, other: &Histogram) -> Result<(), BucketMismatch> {
+        if self.min != other.min || self.max != other.max || self.counts.len() != other.counts.len() {
+            return Err(BucketMismatch);
+        }
+        for (count, theirs) in self.counts.iter_mut().zip(&other.counts) {
+            *count += theirs;
+        }
+        self.below += other.below;
+        self.above += other.above;
+        Ok(())
+    }
+    
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+    
+    /// Values below min, and at or above max
+    pub fn outliers(&self) -> (u64, u64) {
+        (self.below, self.above)
+    }
+    
+    /// Every value recorded, outliers included
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum::<u64>() + self.below + self.above
+    }
+    
+    fn bucket_width(&self) -> f64 {
+        (self.max - self.min) / self.counts.len() as f64
+    }
+    
+    /// The half-open range [start, end) of bucket `i`
+    pub fn bucket_range(&self, i: usize) -> (f64, f64) {
+        let width = self.bucket_width();
+        (self.min + width * i as f64, self.min + width * (i + 1) as f64)
+    }
+    
+    /// One line per bucket - its range, a bar, its count - and a line for
+    /// the outliers if there are any. Bars are scaled to the fullest bucket,
+    /// `width` characters at most; a bucket with anything in it shows at
+    /// least one character, however many orders of magnitude smaller it is
+    pub fn render(&self, width: usize) -> String {
+        if self.total() == 0 {
+            return String::from("(no values)\n");
+        }
+        // Whole numbers stay whole; otherwise enough decimals to tell edges apart
+        let integral = self.min.fract() == 0.0 && self.bucket_width().fract() == 0.0;
+        let decimals = if integral {
+            0
+        } else {
+            (1.0 - self.bucket_width().log10().floor()).clamp(1.0, 6.0) as usize
+        };
+        let edge = |value: f64| format!("{:.*}", decimals, value);
+        let labels: Vec<String> = (0..self.counts.len())
+            .map(|i| {
+                let (start, end) = self.bucket_range(i);
+                format!("[{}, {})", edge(start), edge(end))
+            })
+            .collect();
+        let label_width = labels.iter().map(|label| label.chars().count()).max().unwrap_or(0);
+        let count_width = self.counts.iter().max().map_or(1, |max| max.to_string().len());
+        let fullest = self.counts.iter().copied().max().unwrap_or(0);
+        
+        let mut rendered = String::new();
+        for (label, &count) in labels.iter().zip(&self.counts) {
+            let bar = match count {
+                0 => 0,
+                _ => ((count as f64 / fullest as f64 * width as f64).round() as usize).max(1),
+            };
+            rendered.push_str(&format!(
+                "{:>label_width$} {:<width$} {:>count_width$}\n",
+                label,
+                "█".repeat(bar),
+                count,
+            ));
+        }
+        if self.below > 0 || self.above > 0 {
+            rendered.push_str(&format!(
+                "outside the range: {} below {}, {} at or above {}\n",
+                self.below,
+                edge(self.min),
+                self.above,
+                edge(self.max),
+            ));
+        }
+        rendered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system_design::{Rng, XorShiftRng};
+    
+    #[test]
+    fn percentile_of_one_to_a_hundred_is_the_rank_itself() {
+        let values: Vec<f64> = (1..=100).map(f64::from).collect();
+        for p in [1.0, 50.0, 95.0, 99.0, 100.0] {
+            assert_eq!(percentile(&values, p), Some(p));
+        }
+        assert_eq!(percentile(&values, 0.0), Some(1.0));
+        assert_eq!(percentile(&values, 99.5), Some(100.0));
+        assert_eq!(percentile(&values, 100.5), None);
+        assert_eq!(percentile(&[], 50.0), None);
+        
+        let mut shuffled = vec![3.0, f64::NAN, 1.0, 2.0];
+        sort(&mut shuffled);
+        assert_eq!(&shuffled[..3], &[1.0, 2.0, 3.0]);
+        assert!(shuffled[3].is_nan());
+    }
+    
+    #[test]
+    fn poisson_matches_known_values() {
+        let e = std::f64::consts::E;
+        assert!((poisson_pmf(1.0, 0) - 1.0 / e).abs() < 1e-12);
+        assert!((poisson_pmf(1.0, 1) - 1.0 / e).abs() < 1e-12);
+        assert!((poisson_pmf(2.0, 3) - 8.0 / 6.0 / (e * e)).abs() < 1e-12);
+        assert!((poisson_cdf(1.0, 1) - 2.0 / e).abs() < 1e-12);
+        // P(at least one) = 1 - e^-λt
+        assert!((1.0 - poisson_cdf(expected_failures(0.5, 2.0), 0) - (1.0 - 1.0 / e)).abs() < 1e-12);
+        
+        assert!((ln_gamma(5.0) - 24f64.ln()).abs() < 1e-12);
+        // 10_000! would overflow an f64 many times over
+        assert!(poisson_pmf(1_000.0, 10_000).is_finite());
+        assert!((poisson_cdf(1_000.0, 10_000) - 1.0).abs() < 1e-9);
+        assert_eq!(poisson_pmf(0.0, 0), 1.0);
+        assert_eq!(poisson_pmf(0.0, 3), 0.0);
+        assert!(poisson_pmf(-1.0, 0).is_nan());
+    }
+    
+    #[test]
+    fn samples_have_the_advertised_mean_and_variance() {
+        let mut rng = XorShiftRng::seed_from_u64(302);
+        // Knuth below λ = 10, PTRS from there; both must be Poisson: mean = variance = λ
+        for lambda in [0.5, 4.0, 10.0, 250.0] {
+            let mut samples = Running::new();
+            samples.extend((0..100_000).map(|_| sample_poisson(&mut rng, lambda) as f64));
+            let (mean, variance) = (samples.mean().unwrap_or(0.0), samples.variance().unwrap_or(0.0));
+            assert!((mean - lambda).abs() / lambda < 0.02, "λ = {}: mean {}", lambda, mean);
+            assert!((variance - lambda).abs() / lambda < 0.05, "λ = {}: variance {}", lambda, variance);
+        }
+        assert_eq!(sample_poisson(&mut rng, 0.0), 0);
+        assert_eq!(sample_poisson(&mut rng, f64::NAN), 0);
+        
+        // Exponential(rate): mean 1/rate, variance 1/rate²
+        let rate = 4.0;
+        let mut gaps = Running::new();
+        gaps.extend((0..100_000).map(|_| sample_exponential(&mut rng, rate)));
+        assert!((gaps.mean().unwrap_or(0.0) * rate - 1.0).abs() < 0.02);
+        assert!((gaps.variance().unwrap_or(0.0) * rate * rate - 1.0).abs() < 0.05);
+    }
+    
+    #[test]
+    fn histogram_buckets_and_golden_render() {
+        let mut histogram = Histogram::new(0.0, 100.0, 4);
+        assert_eq!(histogram.render(10), "(no values)\n");
+        for value in [0.0, 24.9, 25.0, 49.999, 99.9, -1.0, 100.0, f64::NAN] {
+            histogram.record(value);
+        }
+        let mut busy = Histogram::new(0.0, 100.0, 4);
+        for _ in 0..1000 {
+            busy.record(60.0);
+        }
+        histogram.merge(&busy).unwrap();
+        assert_eq!(histogram.counts(), &[2, 2, 1000, 1]);
+        assert_eq!(histogram.outliers(), (1, 2)); // -1 below; 100 and NaN above
+        assert_eq!(histogram.total(), 1008);
+        assert_eq!(histogram.bucket_range(1), (25.0, 50.0));
+        assert_eq!(histogram.merge(&Histogram::new(0.0, 100.0, 5)), Err(BucketMismatch));
+        
+        // Three orders of magnitude apart, and every non-empty bar still shows
+        let golden = [
+            "  [0, 25) █             2",
+            " [25, 50) █             2",
+            " [50, 75) ██████████ 1000",
+            "[75, 100) █             1",
+            "outside the range: 1 below 0, 2 at or above 100",
+            "",
+        ];
+        assert_eq!(histogram.render(10), golden.join("\n"));
+        
+        let mut fractional = Histogram::new(0.0, 0.3, 3);
+        fractional.record(0.15);
+        assert_eq!(fractional.render(4), "[0.00, 0.10)      0\n[0.10, 0.20) ████ 1\n[0.20, 0.30)      0\n");
+    }
+    
+    #[test]
+    fn availability_matches_the_classic_nines_table() {
+        let hour = Duration::from_secs(3600);
+        assert!((availability(hour * 999, hour) - 0.999).abs() < 1e-12);
+        assert!((availability(hour * 9999, hour) - 0.9999).abs() < 1e-12);
+        assert!(availability(Duration::ZERO, Duration::ZERO).is_nan());
+        
+        let three = downtime_per_year(0.999).as_secs_f64() / 3600.0;
+        assert!((three - 8.77).abs() < 0.01, "99.9% is {} h/yr", three);
+        let four = downtime_per_year(0.9999).as_secs_f64() / 60.0;
+        assert!((four - 52.6).abs() < 0.01, "99.99% is {} min/yr", four);
+        assert_eq!(downtime_per_year(1.0), Duration::ZERO);
+        assert_eq!(downtime_per_year(f64::NAN), YEAR);
+        
+        assert_eq!(nines(0.999), "three nines (8h 45m downtime/year)");
+        assert_eq!(nines(0.9995), "three nines (4h 22m downtime/year)");
+        assert_eq!(nines(0.9999), "four nines (52m 35s downtime/year)");
+        assert_eq!(nines(0.9), "one nine (36d 12h downtime/year)");
+        assert_eq!(nines(1.0), "all nines (no downtime)");
+    }
+    
+    #[test]
+    fn wilson_interval_matches_published_values() {
+        let close = |(low, high): (f64, f64), (want_low, want_high): (f64, f64)| {
+            (low - want_low).abs() < 1e-4 && (high - want_high).abs() < 1e-4
+        };
+        // Newcombe (1998), via the usual textbook table
+        assert!(close(wilson_interval(81, 263, 0.95), (0.2553, 0.3662)));
+        assert!(close(wilson_interval(5, 10, 0.95), (0.2366, 0.7634)));
+        assert!(close(wilson_interval(5, 7, 0.95), (0.3589, 0.9178))); // the 71.4% sample
+        
+        // All failures or all successes: one end pinned, the other still open
+        assert!(close(wilson_interval(0, 10, 0.95), (0.0, 0.2775)));
+        let (low, high) = wilson_interval(10, 10, 0.95);
+        assert!(close((low, high), (0.7225, 1.0)) && high <= 1.0);
+        
+        assert_eq!(wilson_interval(0, 0, 0.95), (0.0, 1.0));
+        assert!(wilson_interval(3, 10, 1.0).0.is_nan());
+        // Narrower at lower confidence, and with more samples
+        let (low90, high90) = wilson_interval(5, 10, 0.90);
+        assert!(high90 - low90 < 0.7634 - 0.2366);
+        let (low_big, high_big) = wilson_interval(500, 1000, 0.95);
+        assert!(high_big - low_big < 0.07);
+    }
+    
+    #[test]
+    fn series_and_parallel_match_closed_form() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-12;
+        // p^N for a chain, 1 - (1 - p)^N for redundancy
+        for n in 1..=10 {
+            let parts = vec![0.999; n];
+            assert!(close(series_availability(&parts), 0.999f64.powi(n as i32)), "{} in series", n);
+            assert!(close(parallel_availability(&parts), 1.0 - 0.001f64.powi(n as i32)), "{} in parallel", n);
+        }
+        assert!(close(series_availability(&[0.999; 10]), 0.990_044_880_209_748));
+        assert!(close(series_availability(&[0.9, 0.8]), 0.72));
+        assert!(close(parallel_availability(&[0.9, 0.8]), 0.98));
+        assert!(close(parallel_availability(&[0.99, 0.99]), 0.9999));
+        assert_eq!(series_availability(&[]), 1.0);
+        assert_eq!(parallel_availability(&[]), 0.0);
+        assert_eq!(series_availability(&[0.999, 0.0]), 0.0);
+        assert_eq!(parallel_availability(&[0.5, 1.0]), 1.0);
+    }
+    
+    #[test]
+    fn running_agrees_with_two_pass_on_random_data() {
+        let mut rng = XorShiftRng::seed_from_u64(308);
+        let close = |a: f64, b: f64| (a - b).abs() <= 1e-9 * b.abs().max(1.0);
+        // Offsets far from zero are where the one-pass sum-of-squares loses it
+        for (len, offset, scale) in [(2, 0.0, 1.0), (17, -3.0, 0.01), (1000, 1e6, 5.0), (5000, 0.0, 1e3)] {
+            let values: Vec<f64> = (0..len).map(|_| offset + scale * rng.next_f64()).collect();
+            let mean = values.iter().sum::<f64>() / len as f64;
+            let variance = values.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (len - 1) as f64;
+            
+            let mut running = Running::new();
+            running.extend(values.iter().copied());
+            assert_eq!(running.count(), len as u64);
+            assert!(close(running.mean().unwrap_or(f64::NAN), mean), "mean of {}", len);
+            assert!(close(running.variance().unwrap_or(f64::NAN), variance), "variance of {}", len);
+            
+            // Split anywhere, merged back: the same as one stream
+            let split = (rng.next_u64() % len as u64) as usize;
+            let (mut left, mut right) = (Running::new(), Running::new());
+            left.extend(values[..split].iter().copied());
+            right.extend(values[split..].iter().copied());
+            left.merge(&right);
+            assert_eq!(left.count(), len as u64);
+            assert!(close(left.mean().unwrap_or(f64::NAN), mean), "merged mean of {} at {}", len, split);
+            assert!(close(left.variance().unwrap_or(f64::NAN), variance), "merged variance of {} at {}", len, split);
+        }
+        
+        let mut empty = Running::new();
+        assert_eq!((empty.mean(), empty.variance(), empty.stddev()), (None, None, None));
+        let mut one = Running::new();
+        one.push(4.0);
+        assert_eq!((one.mean(), one.variance()), (Some(4.0), None));
+        empty.merge(&one);
+        assert_eq!(empty, one);
+        one.merge(&Running::new());
+        assert_eq!(one.mean(), Some(4.0));
+    }
+    
+    #[test]
+    fn chi_square_accepts_poisson_and_rejects_a_skewed_sample() {
+        let mut rng = XorShiftRng::seed_from_u64(309);
+        for lambda in [0.5, 4.0, 30.0] {
+            let good: Vec<u64> = (0..2000).map(|_| sample_poisson(&mut rng, lambda)).collect();
+            let fit = chi_square_poisson(&good, lambda);
+            assert!(fit.dof >= 1 && fit.consistent(0.01), "λ = {}: {:?}", lambda, fit);
+            
+            // Same data against the wrong λ
+            let shifted = chi_square_poisson(&good, lambda * 1.5);
+            assert!(!shifted.consistent(0.01), "λ = {} tested as {}: {:?}", lambda, lambda * 1.5, shifted);
+        }
+        
+        // Right mean, wrong shape: all-or-nothing bursts averaging λ = 4
+        let bursty: Vec<u64> = (0..2000).map(|i| if i % 4 == 0 { 16 } else { 0 }).collect();
+        let fit = chi_square_poisson(&bursty, 4.0);
+        assert!(fit.p_value_approx < 1e-6, "{:?}", fit);
+        
+        // One absurd count lands in the tail cell instead of sizing the tally
+        let mut wild: Vec<u64> = (0..500).map(|_| sample_poisson(&mut rng, 2.0)).collect();
+        wild.push(u64::MAX);
+        let fit = chi_square_poisson(&wild, 2.0);
+        assert!(fit.dof >= 1 && fit.statistic.is_finite(), "{:?}", fit);
+        
+        // A huge λ walks only the counts seen, not λ of them
+        for lambda in [1e6, 1e10] {
+            let good: Vec<u64> = (0..2000).map(|_| sample_poisson(&mut rng, lambda)).collect();
+            let fit = chi_square_poisson(&good, lambda);
+            assert!(fit.dof >= 1 && fit.consistent(0.01), "λ = {}: {:?}", lambda, fit);
+            let shifted = chi_square_poisson(&good, lambda + 10.0 * lambda.sqrt());
+            assert!(!shifted.consistent(0.01), "λ = {}: {:?}", lambda, shifted);
+        }
+        let fit = chi_square_poisson(&[0, 1_000_000_000_000, u64::MAX], 1e12);
+        assert!(fit.statistic.is_finite() && fit.p_value_approx.is_finite(), "{:?}", fit);
+        
+        let untestable = ChiSquareResult { statistic: 0.0, dof: 0, p_value_approx: 1.0 };
+        assert_eq!(chi_square_poisson(&[], 2.0), untestable);
+        assert_eq!(chi_square_poisson(&[1, 2], 0.0), untestable);
+        assert_eq!(chi_square_poisson(&[0, 1, 0], 2.0), untestable); // 3 windows can't fill two cells
+    }
+}
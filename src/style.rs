@@ -0,0 +1,322 @@
+use std::fmt;
+use std::io::{self, Write};
+
+/// The few colors the demo uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Green,  // handled, healthy
+    Yellow, // degraded, shed, late
+    Red,    // failed, crashed
+    Bold,
+}
+
+impl Color {
+    fn code(self) -> &'static str {
+        match self {
+            Color::Green => "\x1b[32m",
+            Color::Yellow => "\x1b[33m",
+            Color::Red => "\x1b[31m",
+            Color::Bold => "\x1b[1m",
+        }
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+
+/// `text` wrapped in the ANSI codes for `color`
+/// The codes are always there; whether they reach the screen is up to the
+/// ColorWriter the text is written through
+pub fn paint(text: impl fmt::Display, color: Color) -> String {
+    format!("{}{}{}", color.code(), text, RESET)
+}
+
+/// Green from 99%, yellow from 95%, red below
+pub fn availability_color(availability: f64) -> Color {
+    if availability >= 0.99 {
+        Color::Green
+    } else if availability >= 0.95 {
+        Color::Yellow
+    } else {
+        Color::Red
+    }
+}
+
+/// When to color output: `--color always|auto|never`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    Always,
+    #[default]
+    Auto, // on a terminal, unless NO_COLOR is set
+    Never,
+}
+
+impl ColorChoice {
+    pub fn from_name(name: &str) -> Option<ColorChoice> {
+        match name {
+            "always" => Some(ColorChoice::Always),
+            "auto" => Some(ColorChoice::Auto),
+            "never" => Some(ColorChoice::Never),
+            _ => None,
+        }
+    }
+    
+    /// Whether a sink that is (or isn't) a terminal gets color
+    /// NO_COLOR counts only when it is set to something: see no-color.org
+    pub fn enabled(self, is_terminal: bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                is_terminal && std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+            }
+        }
+    }
+}
+
+/// A sink that passes ANSI escapes through, or removes them
+/// Lessons paint unconditionally; the decision is made once, here
+#[derive(Debug)]
+pub struct ColorWriter<W> {
+    inner: W,
+    color: bool,
+    in_escape: bool, // an escape sequence may span two writes
+}
+
+impl<W: Write> ColorWriter<W> {
+    pub fn new(inner: W, color: bool) -> Self {
+        ColorWriter { inner, color, in_escape: false }
+    }
+    
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for ColorWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.color {
+            return self.inner.write(buf);
+        }
+        // ESC never occurs inside a multi-byte UTF-8 sequence, so bytewise is safe
+        let mut plain = Vec::with_capacity(buf.len());
+        for &byte in buf {
+            if self.in_escape {
+                self.in_escape = !byte.is_ascii_alphabetic();
+            } else if byte == 0x1b {
+                self.in_escape = true;
+            } else {
+                plain.push(byte);
+            }
+        }
+        self.inner.write_all(&plain)?;
+        Ok(buf.len())
+    }
+    
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// The glyphs the demo prints, and what a terminal that can't show them gets
+/// Lessons write the Unicode glyphs; a SymbolWriter swaps them on the way out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbols {
+    pub ok: &'static str,
+    pub fail: &'static str,
+    pub warn: &'static str,
+    pub rule: &'static str, // one character of a ━━━ separator
+    ascii: bool,
+}
+
+impl Symbols {
+    pub const UNICODE: Symbols = Symbols { ok: "✓", fail: "✗", warn: "⚠", rule: "━", ascii: false };
+    pub const ASCII: Symbols = Symbols { ok: "[OK]", fail: "[FAIL]", warn: "[WARN]", rule: "-", ascii: true };
+    
+    /// ASCII when asked for, or when the locale says the terminal isn't UTF-8
+    /// No locale at all is taken as UTF-8: that is what every modern terminal is
+    pub fn detect(ascii: bool) -> &'static Symbols {
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"].iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty());
+        let utf8 = locale.is_none_or(|locale| {
+            let locale = locale.to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        });
+        if ascii || !utf8 {
+            &Symbols::ASCII
+        } else {
+            &Symbols::UNICODE
+        }
+    }
+    
+    pub fn is_ascii(&self) -> bool {
+        self.ascii
+    }
+    
+    /// What to write in place of `c`; None means `c` itself
+    pub fn substitute(&self, c: char) -> Option<&'static str> {
+        if !self.ascii || c.is_ascii() {
+            return None;
+        }
+        let replacement = match c {
+            '✓' => self.ok,
+            '✗' => self.fail,
+            '⚠' => self.warn,
+            '━' | '─' => self.rule,
+            '│' => "|",
+            '┌' | '┐' | '└' | '┘' | '├' | '┤' => "+",
+            '→' => "->",
+            '←' => "<-",
+            '↓' => "v",
+            '•' => "*",
+            '—' => "--",
+            '≥' => ">=",
+            '±' => "+/-",
+            '×' => "x",
+            '·' => ".",
+            '∞' => "inf",
+            'µ' => "u",
+            'λ' => "lambda",
+            'α' => "alpha",
+            'π' => "pi",
+            'Γ' => "Gamma",
+            'ö' => "o",
+            'Ö' => "O",
+            '⏱' => "[LATE]",
+            '⏰' => "[TIME]",
+            '⛔' => "[SHED]",
+            '💀' => "[DEAD]",
+            '🎯' | '🔓' => "*",
+            _ => "?",
+        };
+        Some(replacement)
+    }
+}
+
+/// A sink that writes text as-is, or with every non-ASCII glyph replaced
+#[derive(Debug)]
+pub struct SymbolWriter<W> {
+    inner: W,
+    symbols: &'static Symbols,
+    partial: Vec<u8>, // the start of a UTF-8 sequence the next write completes
+}
+
+impl<W: Write> SymbolWriter<W> {
+    pub fn new(inner: W, symbols: &'static Symbols) -> Self {
+        SymbolWriter { inner, symbols, partial: Vec::new() }
+    }
+    
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for SymbolWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !self.symbols.is_ascii() {
+            return self.inner.write(buf);
+        }
+        self.partial.extend_from_slice(buf);
+        let complete = self.partial.len() - incomplete_tail(&self.partial);
+        let mut text = String::with_capacity(complete);
+        for chunk in self.partial[..complete].utf8_chunks() {
+            for c in chunk.valid().chars() {
+                match self.symbols.substitute(c) {
+                    Some(replacement) => text.push_str(replacement),
+                    None => text.push(c),
+                }
+            }
+            if !chunk.invalid().is_empty() {
+                text.push('?'); // not UTF-8 at all
+            }
+        }
+        self.partial.drain(..complete);
+        self.inner.write_all(text.as_bytes())?;
+        Ok(buf.len())
+    }
+    
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// How many bytes at the end of `bytes` begin a character that isn't finished
+fn incomplete_tail(bytes: &[u8]) -> usize {
+    for back in 1..=bytes.len().min(3) {
+        let byte = bytes[bytes.len() - back];
+        if byte & 0xC0 != 0x80 {
+            // Not a continuation byte, so this is where the last character starts
+            let needed = match byte {
+                0xC0..=0xDF => 2,
+                0xE0..=0xEF => 3,
+                0xF0..=0xF7 => 4,
+                _ => 1,
+            };
+            return if needed > back { back } else { 0 };
+        }
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn non_tty_output_has_no_escape_sequences() {
+        assert!(!ColorChoice::Auto.enabled(false));
+        assert!(!ColorChoice::Never.enabled(true));
+        assert!(ColorChoice::Always.enabled(false));
+        
+        let painted = format!("{} {} {}", paint("✓ ok", Color::Green), paint("99.2%", availability_color(0.992)),
+                              paint("✗ down", Color::Red));
+        let mut plain = ColorWriter::new(Vec::new(), ColorChoice::Auto.enabled(false));
+        // Split mid-escape, as a buffered writer might
+        let (first, second) = painted.as_bytes().split_at(2);
+        plain.write_all(first).unwrap();
+        plain.write_all(second).unwrap();
+        let plain = String::from_utf8(plain.into_inner()).unwrap();
+        assert_eq!(plain, "✓ ok 99.2% ✗ down");
+        
+        let mut colored = ColorWriter::new(Vec::new(), true);
+        colored.write_all(painted.as_bytes()).unwrap();
+        assert_eq!(colored.into_inner(), painted.as_bytes());
+    }
+    
+    #[test]
+    fn availability_colors_follow_the_thresholds() {
+        assert_eq!(availability_color(0.99), Color::Green);
+        assert_eq!(availability_color(0.95), Color::Yellow);
+        assert_eq!(availability_color(0.9499), Color::Red);
+        assert_eq!(ColorChoice::from_name("sometimes"), None);
+    }
+    
+    #[test]
+    fn lessons_are_clean_utf8_or_pure_ascii() {
+        for lesson in crate::demo::all_lessons() {
+            let mut unicode = SymbolWriter::new(Vec::new(), &Symbols::UNICODE);
+            lesson.run(&mut unicode).unwrap();
+            let text = String::from_utf8(unicode.into_inner()).unwrap_or_else(|e| panic!("{}: {}", lesson.id(), e));
+            // Only where the lossy-decoding example puts one on purpose, and says so
+            let replaced = text.lines().find(|line| line.contains('\u{FFFD}') && !line.contains("is now U+FFFD"));
+            assert_eq!(replaced, None, "{} has a replacement character", lesson.id());
+            
+            let mut ascii = SymbolWriter::new(Vec::new(), &Symbols::ASCII);
+            lesson.run(&mut ascii).unwrap();
+            let ascii = ascii.into_inner();
+            let stray = ascii.iter().position(|byte| !byte.is_ascii());
+            assert_eq!(stray, None, "{}: {}", lesson.id(), String::from_utf8_lossy(&ascii));
+        }
+    }
+    
+    #[test]
+    fn glyphs_split_across_writes_are_still_replaced() {
+        let mut ascii = SymbolWriter::new(Vec::new(), &Symbols::ASCII);
+        for byte in "✓ done ━━ ⚠".bytes() {
+            ascii.write_all(&[byte]).unwrap();
+        }
+        assert_eq!(ascii.into_inner(), b"[OK] done -- [WARN]");
+        assert_eq!(Symbols::UNICODE.substitute('✓'), None);
+        assert_eq!(Symbols::ASCII.substitute('a'), None);
+    }
+}
@@ -0,0 +1,2978 @@
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, Write};
+use std::iter::FusedIterator;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::better_approaches::{Lookup, UserDirectory};
+use crate::panics::{self, CaptureError};
+use crate::stats;
+use crate::style::{self, paint, Color};
+
+/// Source of randomness for the simulation
+/// Anything that can produce u64s can drive failures and jitter
+pub trait Rng {
+    fn next_u64(&mut self) -> u64;
+    
+    /// Uniform in [0, 1): the top 53 bits mapped onto the f64 mantissa
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// xorshift64 - tiny, fast, and fully determined by its seed
+/// Not cryptographic; plenty for injecting failures and jitter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XorShiftRng {
+    state: u64,
+}
+
+impl XorShiftRng {
+    /// Every seed, including 0, gives a usable and distinct stream
+    pub fn seed_from_u64(seed: u64) -> Self {
+        // splitmix64 finalizer: nearby seeds must not give nearby streams
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        XorShiftRng { state: z | 1 } // xorshift has a fixed point at zero
+    }
+}
+
+impl Rng for XorShiftRng {
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+}
+
+/// A seed from the clock, for runs that don't ask for reproducibility
+/// Reports record it, so any run can still be replayed with --seed
+pub fn entropy_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_nanos() as u64)
+}
+
+/// Why a request to the Service failed
+/// Retries and breakers need to know which failures are worth waiting out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServiceError {
+    MissingInput,                  // the caller sent nothing - retrying won't help
+    Transient { reason: String },  // the upstream hiccupped - may work next time
+    Permanent { reason: String },  // the upstream refused - will fail every time
+    Timeout,                       // no answer in time - may work next time
+    DeadlineExceeded,              // the caller stopped waiting - too late to retry
+    Overloaded,                    // shed by a full bulkhead - never reached the upstream
+    RateLimited,                   // shed by the rate limiter - over the agreed rate
+    Unsupported { feature: String }, // not built yet - said so, instead of a todo!()
+    NotFound { what: String },     // nothing by that key - an answer, not a failure
+    CircuitOpen,                   // a circuit breaker kept the call from the upstream
+}
+
+impl ServiceError {
+    /// Worth retrying: the same request might succeed on another attempt
+    pub fn is_transient(&self) -> bool {
+        matches!(self, ServiceError::Transient { .. } | ServiceError::Timeout)
+    }
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ServiceError::MissingInput => write!(f, "No input provided"),
+            ServiceError::Transient { reason } => write!(f, "Transient failure: {}", reason),
+            ServiceError::Permanent { reason } => write!(f, "Permanent failure: {}", reason),
+            ServiceError::Timeout => write!(f, "Upstream timed out"),
+            ServiceError::DeadlineExceeded => write!(f, "Deadline exceeded"),
+            ServiceError::Overloaded => write!(f, "Overloaded: too many requests in flight"),
+            ServiceError::RateLimited => write!(f, "Rate limited: slow down"),
+            ServiceError::Unsupported { feature } => write!(f, "Unsupported: {} is not implemented yet", feature),
+            ServiceError::NotFound { what } => write!(f, "Not found: {}", what),
+            ServiceError::CircuitOpen => write!(f, "Circuit open: upstream not called"),
+        }
+    }
+}
+
+impl std::error::Error for ServiceError {}
+
+/// What a request asks the service to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OpKind {
+    #[default]
+    Process,
+    LookupUser(u32), // answered from the service's UserDirectory
+    Export,          // not built yet: answered with ServiceError::Unsupported
+}
+
+impl OpKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            OpKind::Process => "process",
+            OpKind::LookupUser(_) => "lookup_user",
+            OpKind::Export => "export",
+        }
+    }
+    
+    pub fn is_supported(&self) -> bool {
+        !matches!(self, OpKind::Export)
+    }
+}
+
+/// One incoming request; the id ties together every log line about it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub id: u64,
+    pub op: OpKind,
+    pub payload: Option<String>,
+    pub deadline: Option<Duration>, // how long the caller will wait, if bounded
+    pub arrival: Duration,          // when it arrives, from the start of the simulation
+}
+
+/// What the Service sent back for a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub id: u64,
+    pub body: String,
+    pub latency: Duration, // simulated handling time plus any backoff spent on retries
+}
+
+/// How long one call to the upstream takes: a fixed base plus a
+/// heavy (Pareto) tail - most calls are quick, a few are very slow.
+/// The tail is truncated at `max`: the upstream's own timeout.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatencyModel {
+    pub base: Duration,
+    pub tail_scale: Duration, // size of a typical excursion into the tail
+    pub tail_shape: f64,      // Pareto α: smaller means a heavier tail
+    pub max: Duration,        // no single call takes longer than this
+}
+
+impl Default for LatencyModel {
+    fn default() -> Self {
+        LatencyModel {
+            base: Duration::from_millis(5),
+            tail_scale: Duration::from_millis(2),
+            tail_shape: 1.5,
+            max: Duration::from_millis(200), // inside DEFAULT_DEADLINE: one try is never late
+        }
+    }
+}
+
+impl LatencyModel {
+    pub fn sample(&self, rng: &mut impl Rng) -> Duration {
+        // Inverse transform: u in (0, 1] so the power stays finite
+        let u = 1.0 - rng.next_f64();
+        let excursion = u.powf(-1.0 / self.tail_shape.max(f64::MIN_POSITIVE)) - 1.0;
+        let tail = self.tail_scale.as_secs_f64() * excursion;
+        self.base.saturating_add(Duration::try_from_secs_f64(tail.max(0.0)).unwrap_or(Duration::MAX))
+            .min(self.max)
+    }
+}
+
+/// Share of injected failures that are transient (timeouts included)
+pub const DEFAULT_TRANSIENT_SHARE: f64 = 0.8;
+
+/// Runtime counters for a Service - "monitor and adapt" needs something to monitor.
+/// Atomic, so the Service and whoever watches it can share one through an Arc.
+#[derive(Debug, Default)]
+pub struct ServiceMetrics {
+    successes: AtomicU64,
+    handled_errors: AtomicU64,
+    not_found: AtomicU64, // lookups of something that doesn't exist: answered, not failed
+    deadline_exceeded: AtomicU64,
+    fallbacks: AtomicU64,
+    rejected: AtomicU64,
+    panics: AtomicU64,
+    last_panic: Mutex<Option<String>>, // its message
+    last_fallback_cause: Mutex<Option<String>>, // the error behind the most recent fallback
+    restarts: AtomicU64, // workers a WorkerPool replaced after they panicked
+    total_latency_nanos: AtomicU64, // of every request that returned
+}
+
+impl ServiceMetrics {
+    pub fn new() -> Self {
+        ServiceMetrics::default()
+    }
+    
+    pub fn record_success(&self, latency: Duration) {
+        self.successes.fetch_add(1, Ordering::Relaxed);
+        self.add_latency(latency);
+    }
+    
+    /// Deadline misses and not-found answers are counted apart from the other errors
+    pub fn record_error(&self, error: &ServiceError, latency: Duration) {
+        let counter = match error {
+            ServiceError::DeadlineExceeded => &self.deadline_exceeded,
+            ServiceError::NotFound { .. } => &self.not_found,
+            ServiceError::Overloaded | ServiceError::RateLimited => &self.rejected,
+            _ => &self.handled_errors,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        self.add_latency(latency);
+    }
+    
+    pub fn record_fallback(&self, latency: Duration) {
+        self.fallbacks.fetch_add(1, Ordering::Relaxed);
+        self.add_latency(latency);
+    }
+    
+    /// A panicking handler can't count itself - whoever catches it does,
+    /// or the hook from panics::install_counting_hook
+    pub fn record_panic(&self, message: String) {
+        self.panics.fetch_add(1, Ordering::Relaxed);
+        *self.last_panic.lock().unwrap_or_else(PoisonError::into_inner) = Some(message);
+    }
+    
+    /// Why a fallback was served: the error it stood in for. Only the most
+    /// recent is kept - the fallbacks counter says how many there were
+    pub fn record_fallback_cause(&self, cause: String) {
+        *self.last_fallback_cause.lock().unwrap_or_else(PoisonError::into_inner) = Some(cause);
+    }
+    
+    pub fn record_restart(&self) {
+        self.restarts.fetch_add(1, Ordering::Relaxed);
+    }
+    
+    /// What the most recent panic said; the counters alone can't
+    pub fn last_panic(&self) -> Option<String> {
+        self.last_panic.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+    
+    pub fn last_fallback_cause(&self) -> Option<String> {
+        self.last_fallback_cause.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+    
+    fn add_latency(&self, latency: Duration) {
+        let nanos = u64::try_from(latency.as_nanos()).unwrap_or(u64::MAX);
+        // fetch_add wraps on overflow; saturate instead
+        let _ = self.total_latency_nanos.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |total| {
+            Some(total.saturating_add(nanos))
+        });
+    }
+    
+    /// The counters at one moment; each is read independently
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            successes: self.successes.load(Ordering::Relaxed),
+            handled_errors: self.handled_errors.load(Ordering::Relaxed),
+            not_found: self.not_found.load(Ordering::Relaxed),
+            deadline_exceeded: self.deadline_exceeded.load(Ordering::Relaxed),
+            fallbacks: self.fallbacks.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+            panics: self.panics.load(Ordering::Relaxed),
+            restarts: self.restarts.load(Ordering::Relaxed),
+            total_latency: Duration::from_nanos(self.total_latency_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// Plain copy of ServiceMetrics' counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MetricsSnapshot {
+    pub successes: u64,
+    pub handled_errors: u64,
+    pub not_found: u64,
+    pub deadline_exceeded: u64,
+    pub fallbacks: u64,
+    pub rejected: u64,
+    pub panics: u64,
+    pub restarts: u64, // not a request outcome, so not in total()
+    pub total_latency: Duration,
+}
+
+impl MetricsSnapshot {
+    /// Every request the service finished with, one way or another
+    pub fn total(&self) -> u64 {
+        self.successes
+            + self.handled_errors
+            + self.not_found
+            + self.deadline_exceeded
+            + self.fallbacks
+            + self.rejected
+            + self.panics
+    }
+    
+    /// Average over the requests that returned (panics never did)
+    pub fn mean_latency(&self) -> Duration {
+        let returned = self.total() - self.panics;
+        if returned == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_nanos((self.total_latency.as_nanos() / u128::from(returned)) as u64)
+    }
+}
+
+impl fmt::Display for MetricsSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "  Results: {} successful, ", self.successes)?;
+        if self.not_found > 0 {
+            write!(f, "{} not found, ", self.not_found)?;
+        }
+        writeln!(f, "{} failed, {} past deadline", self.handled_errors, self.deadline_exceeded)?;
+        write!(f, "  Fallbacks served: {}, rejected: {}, panics caught: {}",
+               self.fallbacks, self.rejected, self.panics)?;
+        if self.restarts > 0 {
+            write!(f, ", workers restarted: {}", self.restarts)?;
+        }
+        writeln!(f)?;
+        write!(f, "  Mean latency: {:?}", self.mean_latency())
+    }
+}
+
+/// Simulates a service with multiple potential failure points
+/// In production systems, failures follow a Poisson distribution
+pub struct Service {
+    failure_rate: f64,    // λ (lambda) - average failures per time unit
+    transient_share: f64, // of those failures, how many are transient
+    rng: XorShiftRng,
+    attempts: usize,      // calls to the upstream, retries included
+    backoff: Duration,    // simulated time spent waiting between retries
+    latency: LatencyModel,
+    busy: Duration,       // simulated time spent so far - the service's own clock
+    metrics: Arc<ServiceMetrics>,
+    directory: UserDirectory, // what LookupUser requests are answered from
+}
+
+impl Service {
+    /// `failure_rate` is the probability that any single request fails,
+    /// clamped to [0, 1]
+    pub fn new(failure_rate: f64) -> Self {
+        Service::with_seed(failure_rate, entropy_seed())
+    }
+    
+    /// Same seed, same failures - request for request
+    pub fn with_seed(failure_rate: f64, seed: u64) -> Self {
+        Service {
+            failure_rate: failure_rate.clamp(0.0, 1.0),
+            transient_share: DEFAULT_TRANSIENT_SHARE,
+            rng: XorShiftRng::seed_from_u64(seed),
+            attempts: 0,
+            backoff: Duration::ZERO,
+            latency: LatencyModel::default(),
+            busy: Duration::ZERO,
+            metrics: Arc::new(ServiceMetrics::new()),
+            directory: UserDirectory::new(Vec::new()),
+        }
+    }
+    
+    /// Sets how many injected failures are transient, clamped to [0, 1];
+    /// the rest are permanent
+    pub fn with_transient_share(mut self, share: f64) -> Self {
+        self.transient_share = share.clamp(0.0, 1.0);
+        self
+    }
+    
+    pub fn with_latency(mut self, latency: LatencyModel) -> Self {
+        self.latency = latency;
+        self
+    }
+    
+    /// The users LookupUser requests are looked up in; none by default
+    pub fn with_directory(mut self, directory: UserDirectory) -> Self {
+        self.directory = directory;
+        self
+    }
+    
+    pub fn failure_rate(&self) -> f64 {
+        self.failure_rate
+    }
+    
+    /// Simulated time spent handling requests so far, backoff included
+    pub fn busy(&self) -> Duration {
+        self.busy
+    }
+    
+    fn since(&self, started: Duration) -> Duration {
+        self.busy.saturating_sub(started)
+    }
+    
+    /// Counts into `metrics` instead of a private set - several Services,
+    /// one set of counters
+    pub fn with_metrics(mut self, metrics: Arc<ServiceMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+    
+    /// Shared handle to this service's counters
+    pub fn metrics(&self) -> Arc<ServiceMetrics> {
+        Arc::clone(&self.metrics)
+    }
+    
+    /// Every call to the upstream so far - more than the requests once retries kick in
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+    
+    /// Total backoff the retries asked for; simulated, never slept
+    pub fn backoff(&self) -> Duration {
+        self.backoff
+    }
+    
+    /// Rolls the dice: true with probability `failure_rate`
+    pub fn should_fail(&mut self) -> bool {
+        self.rng.next_f64() < self.failure_rate
+    }
+    
+    /// Picks the kind of an injected failure; half the transient ones are timeouts
+    fn inject_failure(&mut self) -> ServiceError {
+        let roll = self.rng.next_f64();
+        if roll < self.transient_share / 2.0 {
+            ServiceError::Timeout
+        } else if roll < self.transient_share {
+            ServiceError::Transient { reason: String::from("connection reset") }
+        } else {
+            ServiceError::Permanent { reason: String::from("request rejected upstream") }
+        }
+    }
+    
+    /// One call to the upstream: fails with probability λ. An op the
+    /// service doesn't support is refused before the upstream is called
+    fn receive(&mut self, request: &Request) -> Result<String, ServiceError> {
+        if !request.op.is_supported() {
+            return Err(ServiceError::Unsupported { feature: request.op.name().to_string() });
+        }
+        self.attempts += 1;
+        let latency = self.latency.sample(&mut self.rng);
+        self.busy = self.busy.saturating_add(latency);
+        if self.should_fail() {
+            return Err(self.inject_failure());
+        }
+        match request.op {
+            OpKind::LookupUser(id) => self.look_up(id),
+            _ => request.payload.clone().ok_or(ServiceError::MissingInput),
+        }
+    }
+    
+    /// The user-lookup stage: each of Lookup's three outcomes keeps its meaning.
+    /// A missing user is an answer; only a failed lookup is an error
+    fn look_up(&self, id: u32) -> Result<String, ServiceError> {
+        match self.directory.lookup(id) {
+            Lookup::Found(user) => Ok(user.name),
+            Lookup::NotFound => Err(ServiceError::NotFound { what: format!("user {}", id) }),
+            Lookup::Failed(e) => Err(ServiceError::Transient { reason: e.to_string() }),
+        }
+    }
+    
+    /// A processed response - unless the caller's deadline has already passed
+    fn respond(&self, request: &Request, data: String, latency: Duration) -> Result<Response, ServiceError> {
+        if request.deadline.is_some_and(|deadline| latency > deadline) {
+            return Err(ServiceError::DeadlineExceeded);
+        }
+        Ok(Response { id: request.id, body: format!("Processed: {}", data), latency })
+    }
+    
+    fn fallback(&self, request: &Request, body: &str, started: Duration) -> Response {
+        let latency = self.since(started);
+        self.metrics.record_fallback(latency);
+        Response { id: request.id, body: String::from(body), latency }
+    }
+    
+    fn record(&self, result: &Result<Response, ServiceError>, started: Duration) {
+        match result {
+            Ok(response) => self.metrics.record_success(response.latency),
+            Err(e) => self.metrics.record_error(e, self.since(started)),
+        }
+    }
+    
+    /// Design A: Fail-fast with unwrap (CloudFlare-style)
+    /// One failure brings down the entire service
+    pub fn handle_request_unsafe(&mut self, request: Request) -> Response {
+        let started = self.busy;
+        let data = self.receive(&request).unwrap(); // Single point of total failure
+        let response = self.respond(&request, data, self.since(started)).unwrap();
+        self.metrics.record_success(response.latency);
+        response
+    }
+    
+    /// Design A without the panic, for builds where a panic aborts: the same
+    /// calls, but the error that unwrap() would have panicked on comes back
+    pub fn handle_request_unsafe_checked(&mut self, request: Request) -> Result<Response, ServiceError> {
+        let started = self.busy;
+        let data = self.receive(&request)?;
+        let response = self.respond(&request, data, self.since(started))?;
+        self.metrics.record_success(response.latency);
+        Ok(response)
+    }
+    
+    /// Design B: Graceful degradation with proper error handling
+    /// Failures are contained and logged, service continues
+    pub fn handle_request_safe(&mut self, request: Request) -> Result<Response, ServiceError> {
+        let started = self.busy;
+        let result = self.receive(&request)
+            .and_then(|data| self.respond(&request, data, self.since(started)));
+        self.record(&result, started);
+        result
+    }
+    
+    /// Design C: Fallback response on failure
+    /// The caller always gets an answer, even a degraded one
+    pub fn handle_request_resilient(&mut self, request: Request) -> Response {
+        let started = self.busy;
+        let result = self.receive(&request)
+            .and_then(|data| self.respond(&request, data, self.since(started)));
+        match result {
+            Ok(response) => {
+                self.metrics.record_success(response.latency);
+                response
+            }
+            Err(e) => {
+                // Record the error for whoever watches the metrics, but keep service alive
+                self.metrics.record_fallback_cause(format!("request {} failed ({})", request.id, e));
+                self.fallback(&request, "Fallback response", started)
+            }
+        }
+    }
+    
+    /// Design D: Retry with exponential backoff and jitter
+    /// Only transient failures are retried - a permanent one fails at once
+    pub fn handle_request_with_retry(&mut self, request: Request, policy: RetryPolicy) -> Result<Response, ServiceError> {
+        let started = self.busy;
+        let result = self.retry(&request, policy, started);
+        self.record(&result, started);
+        result
+    }
+    
+    fn retry(&mut self, request: &Request, policy: RetryPolicy, started: Duration) -> Result<Response, ServiceError> {
+        // 0 attempts still means one try: the plain safe design
+        let attempts = policy.max_attempts.max(1);
+        let mut attempt = 1;
+        let mut delays = None; // drawn on the first retry, so a request that needs none rolls no dice
+        loop {
+            match self.receive(request) {
+                Ok(data) => return self.respond(request, data, self.since(started)),
+                Err(e) if e.is_transient() && attempt < attempts => {
+                    let delay = delays.get_or_insert_with(|| policy.delays(self.rng.next_u64()))
+                        .next()
+                        .unwrap_or(Duration::MAX);
+                    // Nobody will be waiting for the answer - don't bother
+                    let done_at = self.since(started).saturating_add(delay);
+                    if request.deadline.is_some_and(|deadline| done_at > deadline) {
+                        return Err(ServiceError::DeadlineExceeded);
+                    }
+                    self.busy = self.busy.saturating_add(delay);
+                    self.backoff = self.backoff.saturating_add(delay);
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    
+    /// Design B behind a bulkhead: when every slot is taken the request
+    /// is turned away at once instead of queueing behind the others
+    pub fn handle_request_with_bulkhead(&mut self, bulkhead: &Bulkhead, request: Request) -> Result<Response, ServiceError> {
+        match bulkhead.try_acquire() {
+            Ok(_permit) => self.handle_request_safe(request),
+            Err(e) => {
+                self.metrics.record_error(&e, Duration::ZERO);
+                Err(e)
+            }
+        }
+    }
+    
+    /// Design B behind a rate limiter: requests over the rate are shed
+    /// before they cost the upstream anything
+    pub fn handle_request_limited<C: Clock>(&mut self, limiter: &mut RateLimiter<C>, request: Request) -> Result<Response, ServiceError> {
+        if let Err(e) = limiter.try_acquire() {
+            self.metrics.record_error(&e, Duration::ZERO);
+            return Err(e);
+        }
+        self.handle_request_safe(request)
+    }
+    
+    /// Design C with graceful degradation in tiers: the upstream first,
+    /// then each tier of `fallbacks` in order until one answers
+    pub fn handle_request_chained(&mut self, fallbacks: &mut FallbackChain, request: Request) -> Result<Served, ChainError> {
+        let started = self.busy;
+        let primary = self.receive(&request)
+            .and_then(|data| self.respond(&request, data, self.since(started)));
+        self.chain_after(fallbacks, request, primary, started)
+    }
+    
+    /// Design C's tiers with a circuit breaker on the upstream: while it is
+    /// open, the chain starts at the first fallback tier
+    pub fn handle_request_chained_with_breaker<C: Clock>(
+        &mut self,
+        breaker: &mut CircuitBreaker<C>,
+        fallbacks: &mut FallbackChain,
+        request: Request,
+    ) -> Result<Served, ChainError> {
+        let started = self.busy;
+        if !breaker.allow_request() {
+            return self.chain_after(fallbacks, request, Err(ServiceError::CircuitOpen), started);
+        }
+        let primary = self.receive(&request)
+            .and_then(|data| self.respond(&request, data, self.since(started)));
+        match &primary {
+            Ok(_) => breaker.record_success(),
+            // A bad or unsupported request says nothing about the upstream's health
+            Err(ServiceError::MissingInput | ServiceError::Unsupported { .. } | ServiceError::NotFound { .. }) => {
+                breaker.record_inconclusive()
+            }
+            Err(_) => breaker.record_failure(),
+        }
+        self.chain_after(fallbacks, request, primary, started)
+    }
+    
+    /// The rest of Design C once the upstream has had its turn
+    fn chain_after(
+        &mut self,
+        fallbacks: &mut FallbackChain,
+        request: Request,
+        primary: Result<Response, ServiceError>,
+        started: Duration,
+    ) -> Result<Served, ChainError> {
+        let primary_error = match primary {
+            Ok(response) => {
+                self.metrics.record_success(response.latency);
+                return Ok(Served { tier: PRIMARY_TIER, response });
+            }
+            Err(e @ ServiceError::NotFound { .. }) => {
+                // The upstream answered; a cached copy can't know better
+                self.metrics.record_error(&e, self.since(started));
+                return Err(ChainError { failures: vec![(PRIMARY_TIER, e)] });
+            }
+            Err(e) => e,
+        };
+        match fallbacks.handle(&request) {
+            Ok((tier, body)) => {
+                let latency = self.since(started);
+                self.metrics.record_fallback(latency);
+                Ok(Served { tier, response: Response { id: request.id, body, latency } })
+            }
+            Err(mut e) => {
+                self.metrics.record_error(&primary_error, self.since(started));
+                e.failures.insert(0, (PRIMARY_TIER, primary_error));
+                Err(e)
+            }
+        }
+    }
+    
+    /// Design C with a circuit breaker in front of it
+    /// While the breaker is open the upstream isn't called at all
+    /// A too-late answer counts against the upstream, like no answer
+    pub fn handle_request_with_breaker<C: Clock>(&mut self, breaker: &mut CircuitBreaker<C>, request: Request) -> Response {
+        let started = self.busy;
+        if !breaker.allow_request() {
+            return self.fallback(&request, "Fallback response (circuit open)", started);
+        }
+        let result = self.receive(&request)
+            .and_then(|data| self.respond(&request, data, self.since(started)));
+        match result {
+            Ok(response) => {
+                breaker.record_success();
+                self.metrics.record_success(response.latency);
+                response
+            }
+            // A bad or unsupported request says nothing about the upstream's health
+            Err(ServiceError::MissingInput | ServiceError::Unsupported { .. } | ServiceError::NotFound { .. }) => {
+                breaker.record_inconclusive();
+                self.fallback(&request, "Fallback response", started)
+            }
+            Err(_) => {
+                breaker.record_failure();
+                self.fallback(&request, "Fallback response", started)
+            }
+        }
+    }
+}
+
+/// Caps how many requests are in flight at once, like the watertight
+/// compartments of a ship: one flooded section doesn't sink the rest.
+/// Shared between threads by reference (or through an Arc).
+#[derive(Debug)]
+pub struct Bulkhead {
+    max_concurrent: usize,
+    in_flight: AtomicUsize,
+}
+
+/// A slot in a Bulkhead; released when dropped
+#[derive(Debug)]
+pub struct BulkheadPermit<'a> {
+    bulkhead: &'a Bulkhead,
+}
+
+impl Bulkhead {
+    pub fn new(max_concurrent: usize) -> Self {
+        Bulkhead { max_concurrent, in_flight: AtomicUsize::new(0) }
+    }
+    
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Acquire)
+    }
+    
+    /// Takes a slot, or fails with `Overloaded` immediately - never waits
+    pub fn try_acquire(&self) -> Result<BulkheadPermit<'_>, ServiceError> {
+        self.in_flight
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < self.max_concurrent).then_some(n + 1)
+            })
+            .map(|_| BulkheadPermit { bulkhead: self })
+            .map_err(|_| ServiceError::Overloaded)
+    }
+}
+
+impl Drop for BulkheadPermit<'_> {
+    fn drop(&mut self) {
+        self.bulkhead.in_flight.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// What a WorkerPool does when one of its workers panics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Supervision {
+    None,    // the worker is gone, and the pool is a thread short from then on
+    Restart, // a supervisor starts a replacement, so capacity holds
+}
+
+/// Runs a handler on a fixed number of threads, each catching its own
+/// panics: a panicking request fails alone, and its worker dies with it.
+/// Supervised, the pool replaces each dead worker; unsupervised, capacity
+/// decays until nobody is left to take what is still queued.
+#[derive(Debug, Clone)]
+pub struct WorkerPool {
+    workers: usize,
+    supervision: Supervision,
+    metrics: Arc<ServiceMetrics>,
+}
+
+/// How a WorkerPool run went
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolReport {
+    pub outcomes: Vec<(u64, Outcome)>, // in request order: Success, Failed (died with its worker) or Dropped
+    pub restarts: usize,
+    pub workers_left: usize, // alive at the end; 0 if the last one died with work queued
+}
+
+impl PoolReport {
+    pub fn served(&self) -> usize {
+        self.outcomes.iter().filter(|(_, outcome)| *outcome == Outcome::Success).count()
+    }
+    
+    /// In flight on a worker when it panicked - reported, not lost
+    pub fn failed(&self) -> usize {
+        self.outcomes.iter().filter(|(_, outcome)| matches!(outcome, Outcome::Failed(_))).count()
+    }
+    
+    /// Still queued when the last worker died
+    pub fn dropped(&self) -> usize {
+        self.outcomes.iter().filter(|(_, outcome)| *outcome == Outcome::Dropped).count()
+    }
+}
+
+impl WorkerPool {
+    pub fn new(workers: usize, supervision: Supervision) -> Self {
+        WorkerPool { workers, supervision, metrics: Arc::new(ServiceMetrics::new()) }
+    }
+    
+    /// Shares `metrics` instead of the pool's own: successes, panics and restarts
+    pub fn with_metrics(mut self, metrics: Arc<ServiceMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+    
+    pub fn metrics(&self) -> Arc<ServiceMetrics> {
+        Arc::clone(&self.metrics)
+    }
+    
+    /// Hands out `requests` in order to the workers and waits until each one
+    /// is answered, has failed with its worker, or has nobody left to take it
+    pub fn run<H>(&self, requests: &[Request], handler: H) -> PoolReport
+    where
+        H: Fn(&Request) -> Response + Sync,
+    {
+        let queue: Mutex<VecDeque<(usize, &Request)>> = Mutex::new(requests.iter().enumerate().collect());
+        let (events, finished) = mpsc::channel();
+        let mut outcomes: Vec<Option<Outcome>> = vec![None; requests.len()];
+        let mut restarts = 0;
+        let mut live = self.workers;
+        std::thread::scope(|scope| {
+            let spawn = |events: mpsc::Sender<Finished>| scope.spawn(|| work(&queue, &handler, events));
+            for _ in 0..self.workers {
+                spawn(events.clone());
+            }
+            // Only workers hold a Sender from here on: once the last is gone, recv stops waiting
+            drop(events);
+            // Every request taken is reported once, so counting events - not ids, which may repeat - says when all are in
+            for _ in 0..requests.len() {
+                let Ok(Finished { index, result, handback }) = finished.recv() else { break };
+                match result {
+                    Ok(response) => {
+                        self.metrics.record_success(response.latency);
+                        outcomes[index] = Some(Outcome::Success);
+                    }
+                    Err(e) => {
+                        live -= 1;
+                        let message = match &e {
+                            CaptureError::Panicked(report) => report.message.clone(),
+                            CaptureError::Unsupported => e.to_string(),
+                        };
+                        self.metrics.record_panic(message);
+                        outcomes[index] = Some(Outcome::Failed(e.to_string()));
+                        if let (Supervision::Restart, Some(events)) = (self.supervision, handback) {
+                            spawn(events);
+                            live += 1;
+                            restarts += 1;
+                            self.metrics.record_restart();
+                        }
+                    }
+                }
+            }
+        });
+        let outcomes = requests.iter().zip(outcomes)
+            .map(|(request, outcome)| (request.id, outcome.unwrap_or(Outcome::Dropped)))
+            .collect();
+        PoolReport { outcomes, restarts, workers_left: live }
+    }
+}
+
+/// What a worker tells the pool about one request it took
+struct Finished {
+    index: usize, // in the queue; ids can repeat
+    result: Result<Response, CaptureError>,
+    handback: Option<mpsc::Sender<Finished>>, // a dying worker's line to the pool, for its replacement
+}
+
+/// One worker: takes requests until the queue is empty or a handler
+/// panics, and reports every one it took - the one it dies on included
+fn work<H>(queue: &Mutex<VecDeque<(usize, &Request)>>, handler: &H, events: mpsc::Sender<Finished>)
+where
+    H: Fn(&Request) -> Response + Sync,
+{
+    loop {
+        let Some((index, request)) = queue.lock().unwrap_or_else(PoisonError::into_inner).pop_front() else { return };
+        // Where panics abort there is nothing to catch: a panic ends the process, as it would anyway
+        let result = if panics::UNWINDS {
+            panics::capture(AssertUnwindSafe(|| handler(request)))
+        } else {
+            Ok(handler(request))
+        };
+        let died = result.is_err();
+        let handback = died.then(|| events.clone());
+        if events.send(Finished { index, result, handback }).is_err() || died {
+            return;
+        }
+    }
+}
+
+/// Time since some fixed starting point, from whatever keeps time
+/// The rate limiter reads this instead of Instant::now, so time can be simulated
+pub trait Clock {
+    fn now(&self) -> Duration;
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> Duration {
+        (**self).now()
+    }
+}
+
+/// Wall-clock time since the clock was created
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        SystemClock { start: Instant::now() }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        SystemClock::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// A clock that only moves when told to - simulations and tests share one by reference
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    now: Cell<Duration>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        ManualClock::default()
+    }
+    
+    pub fn advance(&self, by: Duration) {
+        self.now.set(self.now.get().saturating_add(by));
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+}
+
+/// Token bucket settings: `burst` requests at once, `per_second` sustained
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub burst: u64,
+    pub per_second: u64,
+}
+
+/// One token, in the nanotoken units the bucket counts in
+const NANOTOKENS_PER_TOKEN: u128 = 1_000_000_000;
+
+/// Token bucket: starts full, refills continuously, never above capacity.
+/// Counts in nanotokens so refills are exact - after `t` seconds exactly
+/// `burst + t × per_second` requests have passed, no float rounding.
+#[derive(Debug)]
+pub struct RateLimiter<C: Clock> {
+    limit: RateLimit,
+    nanotokens: u128,
+    last_refill: Duration,
+    clock: C,
+}
+
+impl<C: Clock> RateLimiter<C> {
+    pub fn new(limit: RateLimit, clock: C) -> Self {
+        let last_refill = clock.now();
+        RateLimiter {
+            limit,
+            nanotokens: u128::from(limit.burst) * NANOTOKENS_PER_TOKEN,
+            last_refill,
+            clock,
+        }
+    }
+    
+    fn refill(&mut self) {
+        let now = self.clock.now();
+        let elapsed = now.saturating_sub(self.last_refill);
+        self.last_refill = now;
+        // nanoseconds × tokens/second = nanotokens
+        let added = elapsed.as_nanos().saturating_mul(u128::from(self.limit.per_second));
+        let capacity = u128::from(self.limit.burst) * NANOTOKENS_PER_TOKEN;
+        self.nanotokens = self.nanotokens.saturating_add(added).min(capacity);
+    }
+    
+    /// Whole tokens in the bucket right now
+    pub fn available(&mut self) -> u64 {
+        self.refill();
+        (self.nanotokens / NANOTOKENS_PER_TOKEN) as u64
+    }
+    
+    /// Spends a token, or fails with `RateLimited` when the bucket is empty
+    pub fn try_acquire(&mut self) -> Result<(), ServiceError> {
+        self.refill();
+        if self.nanotokens < NANOTOKENS_PER_TOKEN {
+            return Err(ServiceError::RateLimited);
+        }
+        self.nanotokens -= NANOTOKENS_PER_TOKEN;
+        Ok(())
+    }
+}
+
+/// Name of the tier that is the Service's own upstream
+pub const PRIMARY_TIER: &str = "primary";
+
+/// A response, and which tier of a fallback chain produced it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Served {
+    pub tier: &'static str,
+    pub response: Response,
+}
+
+/// Every tier failed; each one's error, in the order they were tried
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainError {
+    pub failures: Vec<(&'static str, ServiceError)>,
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "all {} tiers failed", self.failures.len())?;
+        for (i, (tier, error)) in self.failures.iter().enumerate() {
+            let separator = if i == 0 { ": " } else { "; " };
+            write!(f, "{}{}: {}", separator, tier, error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ChainError {}
+
+/// One way of answering a request, e.g. a replica, a cache, a static page
+pub type TierHandler<'a> = Box<dyn FnMut(&Request) -> Result<String, ServiceError> + 'a>;
+
+/// Ordered fallback tiers, tried one after another until one answers
+#[derive(Default)]
+pub struct FallbackChain<'a> {
+    tiers: Vec<(&'static str, TierHandler<'a>)>,
+}
+
+impl<'a> FallbackChain<'a> {
+    pub fn new() -> Self {
+        FallbackChain { tiers: Vec::new() }
+    }
+    
+    /// Adds a tier after the existing ones
+    pub fn with_tier<F>(mut self, name: &'static str, handler: F) -> Self
+    where
+        F: FnMut(&Request) -> Result<String, ServiceError> + 'a,
+    {
+        self.tiers.push((name, Box::new(handler)));
+        self
+    }
+    
+    pub fn tier_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.tiers.iter().map(|(name, _)| *name)
+    }
+    
+    /// The first tier's answer, with its name - or every tier's error,
+    /// never just the last one
+    pub fn handle(&mut self, request: &Request) -> Result<(&'static str, String), ChainError> {
+        let mut failures = Vec::new();
+        for (name, handler) in &mut self.tiers {
+            match handler(request) {
+                Ok(body) => return Ok((*name, body)),
+                Err(e) => failures.push((*name, e)),
+            }
+        }
+        Err(ChainError { failures })
+    }
+}
+
+/// Where a CircuitBreaker is in its cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    Closed,   // requests flow; consecutive failures are counted
+    Open,     // requests short-circuit until the open duration passes
+    HalfOpen, // a few probe requests decide whether to close again
+}
+
+/// Stops calling a failing upstream, then carefully tries it again.
+/// Time comes from a Clock, as for RateLimiter, so the state machine can
+/// be driven by a simulated one.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker<C: Clock> {
+    failure_threshold: u32,
+    open_duration: Duration,   // of the current trip
+    schedule: BackoffIterator, // open durations of successive trips; restarts once closed
+    reopen: BackoffIterator,   // the schedule as it was given, to restart from
+    half_open_probes: u32,
+    state: BreakerState,
+    consecutive_failures: u32,
+    probe_successes: u32,
+    probes_in_flight: u32, // admitted while half-open, not yet answered
+    opened_at: Option<Duration>,
+    transitions: Vec<(Duration, BreakerState, BreakerState)>, // (when, from, to), until taken
+    clock: C,
+}
+
+impl<C: Clock> CircuitBreaker<C> {
+    /// Opens after `failure_threshold` consecutive failures, stays open for
+    /// `open_duration`, and closes after `half_open_probes` successful probes
+    pub fn new(failure_threshold: u32, open_duration: Duration, half_open_probes: u32, clock: C) -> Self {
+        // The same wait every time: a backoff that never grows
+        let schedule = BackoffIterator::new(BackoffStrategy::Exponential, open_duration, open_duration);
+        CircuitBreaker {
+            failure_threshold: failure_threshold.max(1),
+            open_duration,
+            schedule: schedule.clone(),
+            reopen: schedule,
+            half_open_probes: half_open_probes.max(1),
+            state: BreakerState::Closed,
+            consecutive_failures: 0,
+            probe_successes: 0,
+            probes_in_flight: 0,
+            opened_at: None,
+            transitions: Vec::new(),
+            clock,
+        }
+    }
+    
+    /// Waits longer before each probe while the upstream stays down: trip
+    /// after trip, the open durations are `schedule`'s delays
+    pub fn with_backoff(mut self, schedule: BackoffIterator) -> Self {
+        self.schedule = schedule.clone();
+        self.reopen = schedule;
+        self
+    }
+    
+    pub fn state(&self) -> BreakerState {
+        self.state
+    }
+    
+    /// Every state change since the last call, oldest first: (when, from, to)
+    pub fn take_transitions(&mut self) -> Vec<(Duration, BreakerState, BreakerState)> {
+        std::mem::take(&mut self.transitions)
+    }
+    
+    fn set_state(&mut self, to: BreakerState) {
+        if to != self.state {
+            self.transitions.push((self.clock.now(), self.state, to));
+            self.state = to;
+        }
+    }
+    
+    /// How long the breaker stays open this time, or stayed open last time
+    pub fn open_duration(&self) -> Duration {
+        self.open_duration
+    }
+    
+    /// False while open; once `open_duration` has passed, moves to half-open
+    /// and lets up to `half_open_probes` probes through at a time
+    pub fn allow_request(&mut self) -> bool {
+        match self.state {
+            BreakerState::Closed => true,
+            BreakerState::HalfOpen => {
+                let admit = self.probes_in_flight < self.half_open_probes;
+                if admit {
+                    self.probes_in_flight += 1;
+                }
+                admit
+            }
+            BreakerState::Open => {
+                let now = self.clock.now();
+                let waited = self.opened_at
+                    .is_none_or(|at| now.saturating_sub(at) >= self.open_duration);
+                if waited {
+                    self.set_state(BreakerState::HalfOpen);
+                    self.probe_successes = 0;
+                    self.probes_in_flight = 1; // this call is the first probe
+                }
+                waited
+            }
+        }
+    }
+    
+    pub fn record_success(&mut self) {
+        match self.state {
+            BreakerState::Closed => self.consecutive_failures = 0,
+            BreakerState::HalfOpen => {
+                self.probes_in_flight = self.probes_in_flight.saturating_sub(1);
+                self.probe_successes += 1;
+                if self.probe_successes >= self.half_open_probes {
+                    self.set_state(BreakerState::Closed);
+                    self.consecutive_failures = 0;
+                    self.opened_at = None;
+                    self.schedule = self.reopen.clone();
+                }
+            }
+            BreakerState::Open => {} // a late answer from before the trip
+        }
+    }
+    
+    /// The call proved nothing either way: a probe's slot is freed for another
+    pub fn record_inconclusive(&mut self) {
+        if self.state == BreakerState::HalfOpen {
+            self.probes_in_flight = self.probes_in_flight.saturating_sub(1);
+        }
+    }
+    
+    pub fn record_failure(&mut self) {
+        match self.state {
+            BreakerState::Closed => {
+                self.consecutive_failures += 1;
+                if self.consecutive_failures >= self.failure_threshold {
+                    self.trip();
+                }
+            }
+            // One failed probe is enough: the upstream hasn't recovered
+            BreakerState::HalfOpen => self.trip(),
+            BreakerState::Open => {}
+        }
+    }
+    
+    fn trip(&mut self) {
+        self.set_state(BreakerState::Open);
+        self.opened_at = Some(self.clock.now());
+        // A schedule that has run out keeps its last wait
+        self.open_duration = self.schedule.next().unwrap_or(self.open_duration);
+        self.probe_successes = 0;
+        self.probes_in_flight = 0;
+    }
+}
+
+/// How hard Design D tries before giving up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,    // including the first try
+    pub base_delay: Duration, // wait before the first retry
+    pub multiplier: f64,      // growth of the wait per retry
+    pub jitter: f64,          // ± fraction of the wait, 0.0 to 1.0
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+            multiplier: 2.0,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The waits before each retry, in order; `seed` drives the jitter.
+    /// Saturates instead of panicking on huge or nonsensical policies
+    pub fn delays(&self, seed: u64) -> BackoffIterator {
+        BackoffIterator::new(BackoffStrategy::Exponential, self.base_delay, Duration::MAX)
+            .with_multiplier(self.multiplier)
+            .with_jitter(self.jitter)
+            .with_seed(seed)
+    }
+}
+
+/// How the wait between retry attempts grows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackoffStrategy {
+    /// base, 2×base, 4×base, ... up to the cap
+    Exponential,
+    /// Uniform in [0, exponential delay] - spreads out synchronized clients
+    FullJitter,
+    /// Uniform in [base, 3 × previous delay] - jitter that remembers the last wait
+    DecorrelatedJitter,
+}
+
+/// Yields the delay before each successive retry attempt.
+/// Delays never exceed `max_delay`; with a total-elapsed limit the iterator
+/// ends instead of scheduling a wait that would cross it.
+/// All arithmetic saturates at the cap - a high attempt count must never
+/// overflow into a panic (the retry helper would become the failure).
+#[derive(Debug, Clone)]
+pub struct BackoffIterator {
+    strategy: BackoffStrategy,
+    base: Duration,
+    max_delay: Duration,
+    multiplier: f64, // growth per attempt of the exponential delay
+    jitter: f64,     // ± fraction of each delay, on top of the strategy's own
+    max_elapsed: Option<Duration>,
+    attempt: u32,
+    previous: Duration,
+    elapsed: Duration,
+    exhausted: bool, // max_elapsed was reached: nothing more, ever
+    rng: XorShiftRng,
+}
+
+impl BackoffIterator {
+    pub fn new(strategy: BackoffStrategy, base: Duration, max_delay: Duration) -> Self {
+        BackoffIterator {
+            strategy,
+            base: base.min(max_delay),
+            max_delay,
+            multiplier: 2.0,
+            jitter: 0.0,
+            max_elapsed: None,
+            attempt: 0,
+            previous: base.min(max_delay),
+            elapsed: Duration::ZERO,
+            exhausted: false,
+            rng: XorShiftRng::seed_from_u64(0),
+        }
+    }
+    
+    /// Stop yielding delays once their sum would exceed `limit`
+    pub fn with_max_elapsed(mut self, limit: Duration) -> Self {
+        self.max_elapsed = Some(limit);
+        self
+    }
+    
+    /// Grow the exponential delay by `multiplier` per attempt instead of
+    /// doubling it; below 1 it would shrink, so it is clamped to 1
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+    
+    /// Spread each delay uniformly by ± `fraction` of itself, clamped to [0, 1]
+    pub fn with_jitter(mut self, fraction: f64) -> Self {
+        self.jitter = fraction.clamp(0.0, 1.0);
+        self
+    }
+    
+    /// Seed the jitter source so a schedule can be reproduced
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = XorShiftRng::seed_from_u64(seed);
+        self
+    }
+    
+    /// Un-jittered exponential delay for the current attempt, saturating at the cap
+    fn exponential_delay(&self) -> Duration {
+        let factor = self.multiplier.powi(self.attempt.min(i32::MAX as u32) as i32);
+        self.capped(self.base.as_secs_f64() * factor)
+    }
+    
+    /// `secs` as a Duration no longer than the cap - which is also where
+    /// an overflowing or non-finite value ends up
+    fn capped(&self, secs: f64) -> Duration {
+        Duration::try_from_secs_f64(secs).map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+    
+    /// Uniform duration in [low, high] (returns `low` if the range is empty)
+    fn random_between(&mut self, low: Duration, high: Duration) -> Duration {
+        let low_ns = low.as_nanos();
+        let high_ns = high.as_nanos();
+        if high_ns <= low_ns {
+            return low;
+        }
+        let span = high_ns - low_ns + 1;
+        let offset = u128::from(self.rng.next_u64()) % span;
+        let nanos = low_ns + offset;
+        Duration::new((nanos / 1_000_000_000) as u64, (nanos % 1_000_000_000) as u32)
+    }
+}
+
+impl Iterator for BackoffIterator {
+    type Item = Duration;
+    
+    fn next(&mut self) -> Option<Duration> {
+        if self.exhausted {
+            return None;
+        }
+        let delay = match self.strategy {
+            BackoffStrategy::Exponential => self.exponential_delay(),
+            BackoffStrategy::FullJitter => {
+                let ceiling = self.exponential_delay();
+                self.random_between(Duration::ZERO, ceiling)
+            }
+            BackoffStrategy::DecorrelatedJitter => {
+                let ceiling = self.previous.saturating_mul(3).min(self.max_delay);
+                self.random_between(self.base, ceiling)
+            }
+        };
+        let delay = if self.jitter > 0.0 {
+            let spread = 1.0 + self.jitter * (2.0 * self.rng.next_f64() - 1.0);
+            self.capped(delay.as_secs_f64() * spread)
+        } else {
+            delay
+        };
+        
+        let elapsed = self.elapsed.saturating_add(delay);
+        if let Some(limit) = self.max_elapsed {
+            if elapsed > limit {
+                // A later, smaller jittered delay might still fit; the budget is spent all the same
+                self.exhausted = true;
+                return None;
+            }
+        }
+        
+        self.elapsed = elapsed;
+        self.previous = delay;
+        self.attempt = self.attempt.saturating_add(1);
+        Some(delay)
+    }
+}
+
+impl FusedIterator for BackoffIterator {}
+
+/// The three architectures compared under the same failures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Design {
+    Unsafe,    // Design A: fail-fast with unwrap()
+    Safe,      // Design B: propagate errors with Result
+    Resilient, // Design C: fallback and keep serving
+    Retry,     // Design D: retry with backoff, then report the error
+}
+
+impl Design {
+    pub const ALL: [Design; 4] = [Design::Unsafe, Design::Safe, Design::Resilient, Design::Retry];
+    
+    pub fn name(self) -> &'static str {
+        match self {
+            Design::Unsafe => "unsafe",
+            Design::Safe => "safe",
+            Design::Resilient => "resilient",
+            Design::Retry => "retry",
+        }
+    }
+    
+    pub fn from_name(name: &str) -> Option<Design> {
+        Design::ALL.into_iter().find(|design| design.name() == name)
+    }
+}
+
+impl fmt::Display for Design {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// What happened to a single request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Success,
+    Failed(String), // error returned and logged
+    NotFound,       // answered: there is nothing by that key
+    Fallback,       // degraded response served
+    DeadlineExceeded, // answered too late for the caller to use
+    Rejected,       // shed before reaching the service
+    Crashed,        // this request took the service down
+    Dropped,        // never processed - the service was already dead
+}
+
+impl Outcome {
+    /// Stable machine-readable name, for JSON and CSV output
+    pub fn name(&self) -> &'static str {
+        match self {
+            Outcome::Success => "success",
+            Outcome::Failed(_) => "failed",
+            Outcome::NotFound => "not_found",
+            Outcome::Fallback => "fallback",
+            Outcome::DeadlineExceeded => "deadline_exceeded",
+            Outcome::Rejected => "rejected",
+            Outcome::Crashed => "crashed",
+            Outcome::Dropped => "dropped",
+        }
+    }
+    
+    /// Answered as asked: a user that doesn't exist is a correct answer, not an error
+    pub fn is_good(&self) -> bool {
+        matches!(self, Outcome::Success | Outcome::NotFound)
+    }
+    
+    /// The logged error, if the request failed with one
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            Outcome::Failed(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Measurements from one simulated run - no printing involved
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationReport {
+    pub design: Design,
+    pub successful: usize,
+    pub not_found: usize, // lookups answered "no such user" - not failures
+    pub failed: usize,  // handled, fell back, or crashed the service
+    pub deadline_exceeded: usize, // gave up on, or answered, past the deadline
+    pub rejected: usize, // shed by a bulkhead - load, not failure
+    pub dropped: usize, // lost because an earlier request crashed the service
+    pub duration: Duration,
+    pub availability: f64, // (successful + not found) / total, in [0, 1]
+    pub availability_interval: (f64, f64), // 95% Wilson interval around it
+    pub outcomes: Vec<(u64, Outcome)>, // by request id
+    pub seed: u64, // replaying with this seed reproduces `outcomes` exactly
+    pub attempts: usize,   // upstream calls; above the request count when retrying
+    pub backoff: Duration, // simulated waiting between retries
+    pub metrics: MetricsSnapshot, // the service's own counters at the end of the run
+    pub last_panic: Option<String>, // the message of the last panic the metrics saw
+    pub tiers: Vec<(&'static str, usize)>, // responses per fallback tier; empty without a chain
+    pub latencies: Vec<Option<Duration>>, // simulated, in `outcomes` order; None = never answered
+    pub arrivals: Vec<Duration>, // when each request arrived, in `outcomes` order
+    pub request_attempts: Vec<usize>, // upstream calls per request, in `outcomes` order
+    pub breaker_transitions: Vec<(u64, BreakerState, BreakerState)>, // (request id, from, to); Design C only
+}
+
+impl SimulationReport {
+    /// Requests that went wrong in each `window`-long slice of the run,
+    /// from time zero to the last arrival; unprocessed requests don't count
+    pub fn failures_per_window(&self, window: Duration) -> Vec<u64> {
+        let window = window.as_secs_f64();
+        let horizon = self.arrivals.iter().max().map_or(0.0, |last| last.as_secs_f64());
+        if window <= 0.0 {
+            return Vec::new();
+        }
+        let mut counts = vec![0; (horizon / window) as usize + 1];
+        for ((_, outcome), arrival) in self.outcomes.iter().zip(&self.arrivals) {
+            let failed = !(outcome.is_good() || matches!(outcome, Outcome::Dropped | Outcome::Rejected));
+            if failed {
+                counts[(arrival.as_secs_f64() / window) as usize] += 1;
+            }
+        }
+        counts
+    }
+    
+    /// Latencies in milliseconds, ascending; a request that never got an
+    /// answer waited forever, so it counts as infinity
+    pub fn sorted_latencies_ms(&self) -> Vec<f64> {
+        let mut ms: Vec<f64> = self.latencies.iter()
+            .map(|latency| latency.map_or(f64::INFINITY, |l| l.as_secs_f64() * 1000.0))
+            .collect();
+        stats::sort(&mut ms);
+        ms
+    }
+    
+    /// Mean and spread of the latencies of requests that were answered
+    pub fn latency_stats_ms(&self) -> stats::Running {
+        let mut running = stats::Running::new();
+        running.extend(self.latencies.iter().flatten().map(|latency| latency.as_secs_f64() * 1000.0));
+        running
+    }
+    
+    /// One CSV row per request, after a header row
+    pub fn write_csv<W: Write>(&self, mut w: W) -> io::Result<()> {
+        writeln!(w, "{}", CSV_HEADER)?;
+        self.write_csv_rows(w)
+    }
+    
+    /// The rows of `write_csv` without the header, so several reports can share one file
+    pub fn write_csv_rows<W: Write>(&self, mut w: W) -> io::Result<()> {
+        for ((id, outcome), latency) in self.outcomes.iter().zip(&self.latencies) {
+            let latency = latency.map_or_else(String::new, |l| (l.as_secs_f64() * 1000.0).to_string());
+            writeln!(w, "{},{},{},{},{}",
+                     id, self.design, outcome.name(), latency, csv_field(outcome.error().unwrap_or("")))?;
+        }
+        Ok(())
+    }
+}
+
+/// Columns written by `SimulationReport::write_csv`
+pub const CSV_HEADER: &str = "index,design,outcome,latency_ms,error";
+
+/// Quotes a field if it needs it (RFC 4180): commas, quotes and line breaks
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// λ used when no configuration is given: 1% of requests fail
+pub const DEFAULT_FAILURE_RATE: f64 = 0.01;
+
+/// How long callers wait for a simulated request
+pub const DEFAULT_DEADLINE: Duration = Duration::from_millis(250);
+
+/// Above this many requests, a report is a summary unless asked otherwise
+pub const PER_REQUEST_OUTPUT_LIMIT: usize = 50;
+
+/// How much of a simulation report gets written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    Quiet,      // nothing: the caller only wants the report value
+    Summary,    // the statistics block
+    PerRequest, // a line per request, then the statistics
+    Debug,      // per-request lines with arrival, latency, retry attempts and breaker changes
+}
+
+impl Verbosity {
+    /// PerRequest for small runs; past PER_REQUEST_OUTPUT_LIMIT the lines
+    /// would drown the lesson, so Summary
+    pub fn default_for(requests: usize) -> Verbosity {
+        if requests > PER_REQUEST_OUTPUT_LIMIT {
+            Verbosity::Summary
+        } else {
+            Verbosity::PerRequest
+        }
+    }
+}
+
+/// Shape of a generated production load
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimConfig {
+    pub requests: usize,
+    pub failure_rate: f64, // λ - probability that any single request fails
+    pub seed: Option<u64>, // None: seed from the clock
+    pub rate_limit: Option<RateLimit>, // None: every request reaches the service
+    pub arrivals: Option<PoissonLoad>, // replaces `requests` with Poisson-timed arrivals
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        SimConfig {
+            requests: 7,
+            failure_rate: DEFAULT_FAILURE_RATE,
+            seed: None,
+            rate_limit: None,
+            arrivals: None,
+        }
+    }
+}
+
+/// Requests arriving as a Poisson process
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoissonLoad {
+    pub rate: f64, // mean arrivals per second
+    pub duration: Duration,
+}
+
+/// Simulated time between two requests arriving, unless they are
+/// Poisson-timed: 100 requests per second
+pub const ARRIVAL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// `count` well-formed requests - any failure has to come from λ
+pub fn generate_requests(count: usize) -> Vec<Request> {
+    (1..=count as u64)
+        .map(|id| Request {
+            id,
+            op: OpKind::Process,
+            payload: Some(format!("req{}", id)),
+            deadline: Some(DEFAULT_DEADLINE),
+            arrival: ARRIVAL_INTERVAL.saturating_mul(u32::try_from(id).unwrap_or(u32::MAX)),
+        })
+        .collect()
+}
+
+/// Well-formed requests arriving as a Poisson process at `rate` per second
+/// for `duration` - bursts and lulls included, like real traffic
+pub fn poisson_arrivals(rate: f64, duration: Duration, rng: &mut impl Rng) -> Vec<Request> {
+    let mut requests = Vec::new();
+    if !(rate > 0.0 && rate.is_finite()) {
+        return requests;
+    }
+    let end = duration.as_secs_f64();
+    let mut t = stats::sample_exponential(rng, rate);
+    while t < end {
+        let id = requests.len() as u64 + 1;
+        requests.push(Request {
+            id,
+            op: OpKind::Process,
+            payload: Some(format!("req{}", id)),
+            deadline: Some(DEFAULT_DEADLINE),
+            arrival: Duration::from_secs_f64(t),
+        });
+        t += stats::sample_exponential(rng, rate);
+    }
+    requests
+}
+
+/// The requests from the original demo: two known-bad inputs among seven
+pub fn default_requests() -> Vec<Request> {
+    let payloads = [
+        Some("req1"),
+        Some("req2"),
+        None, // Failure occurs
+        Some("req3"),
+        Some("req4"),
+        None, // Another failure
+        Some("req5"),
+    ];
+    (1..).zip(payloads)
+        .map(|(id, payload)| Request {
+            id,
+            op: OpKind::Process,
+            payload: payload.map(String::from),
+            deadline: Some(DEFAULT_DEADLINE),
+            arrival: ARRIVAL_INTERVAL.saturating_mul(u32::try_from(id).unwrap_or(u32::MAX)),
+        })
+        .collect()
+}
+
+/// Runtime IS test copy - failures will occur in production
+/// The question is: how does your system respond?
+/// Every run differs; assert on `simulate_production_load_seeded` instead
+pub fn simulate_production_load(design: Design, requests: &[Request]) -> SimulationReport {
+    simulate_production_load_seeded(design, requests, entropy_seed())
+}
+
+/// Same as `simulate_production_load`, but the injected failures are
+/// fixed by `seed` - two runs with the same seed have identical outcomes
+pub fn simulate_production_load_seeded(design: Design, requests: &[Request], seed: u64) -> SimulationReport {
+    run_simulation(design, requests, Service::with_seed(DEFAULT_FAILURE_RATE, seed), seed, None)
+}
+
+/// Generates `config.requests` requests and runs them against a Service
+/// failing at `config.failure_rate`
+pub fn simulate_with_config(design: Design, config: &SimConfig) -> SimulationReport {
+    let seed = config.seed.unwrap_or_else(entropy_seed);
+    let requests = match config.arrivals {
+        // A stream of its own, so arrival times don't shift the failure rolls
+        Some(load) => poisson_arrivals(load.rate, load.duration, &mut XorShiftRng::seed_from_u64(seed.wrapping_add(2))),
+        None => generate_requests(config.requests),
+    };
+    run_simulation(design, &requests, Service::with_seed(config.failure_rate, seed), seed, config.rate_limit)
+}
+
+/// Runs `requests` against `service`; `seed` drives everything else the run rolls dice for
+fn run_simulation(
+    design: Design,
+    requests: &[Request],
+    mut service: Service,
+    seed: u64,
+    rate_limit: Option<RateLimit>,
+) -> SimulationReport {
+    let metrics = service.metrics();
+    let clock = ManualClock::new();
+    // Design C degrades through a cache that hits half the time, then a static page
+    let mut cache_rng = XorShiftRng::seed_from_u64(seed.wrapping_add(1));
+    let mut fallbacks = FallbackChain::new()
+        .with_tier("cache", move |request: &Request| {
+            if cache_rng.next_f64() < 0.5 {
+                Ok(format!("Cached response for request {}", request.id))
+            } else {
+                Err(ServiceError::Transient { reason: String::from("cache miss") })
+            }
+        })
+        .with_tier("static", |_: &Request| Ok(String::from("Fallback response")));
+    let mut tiers: Vec<(&'static str, usize)> = match design {
+        Design::Resilient => std::iter::once(PRIMARY_TIER)
+            .chain(fallbacks.tier_names())
+            .map(|tier| (tier, 0))
+            .collect(),
+        _ => Vec::new(),
+    };
+    // Design C also stops calling an upstream that keeps failing, for a second at a time
+    let mut breaker = CircuitBreaker::new(5, Duration::from_secs(1), 2, &clock);
+    let mut breaker_transitions = Vec::new();
+    let mut limiter = rate_limit.map(|limit| RateLimiter::new(limit, &clock));
+    let mut outcomes = Vec::with_capacity(requests.len());
+    let mut latencies = Vec::with_capacity(requests.len());
+    let mut request_attempts = Vec::with_capacity(requests.len());
+    // Design A's crash is counted by the hook, there for the whole run;
+    // only this thread's panics, whatever else the process is running
+    let _counting = (design == Design::Unsafe && panics::UNWINDS)
+        .then(|| panics::install_thread_counting_hook(Arc::clone(&metrics)));
+    let start = Instant::now();
+    
+    for req in requests {
+        clock.advance(req.arrival.saturating_sub(clock.now()));
+        // Shed load is turned away before any design gets to handle it
+        if let Some(Err(e)) = limiter.as_mut().map(RateLimiter::try_acquire) {
+            metrics.record_error(&e, Duration::ZERO);
+            outcomes.push((req.id, Outcome::Rejected));
+            latencies.push(Some(Duration::ZERO));
+            request_attempts.push(0);
+            continue;
+        }
+        
+        let classify = |result: Result<Response, ServiceError>| match result {
+            Ok(_) => Outcome::Success,
+            Err(ServiceError::DeadlineExceeded) => Outcome::DeadlineExceeded,
+            Err(ServiceError::NotFound { .. }) => Outcome::NotFound,
+            Err(e) => Outcome::Failed(e.to_string()),
+        };
+        let before = service.busy();
+        let attempts_before = service.attempts();
+        let outcome = match design {
+            Design::Unsafe if !panics::UNWINDS => {
+                // A real panic would end the whole process: the crash is recorded, not raised
+                match service.handle_request_unsafe_checked(req.clone()) {
+                    Ok(_) => Outcome::Success,
+                    Err(e) => {
+                        metrics.record_panic(format!("called `Result::unwrap()` on an `Err` value: {:?}", e));
+                        Outcome::Crashed
+                    }
+                }
+            }
+            Design::Unsafe => {
+                // Simulates unwrap() - first failure kills everything, and the hook counts it
+                let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                    service.handle_request_unsafe(req.clone())
+                }));
+                match result {
+                    Ok(_) => Outcome::Success,
+                    Err(_) => Outcome::Crashed,
+                }
+            }
+            Design::Safe => classify(service.handle_request_safe(req.clone())),
+            Design::Resilient => match service.handle_request_chained_with_breaker(&mut breaker, &mut fallbacks, req.clone()) {
+                Ok(served) => {
+                    if let Some(count) = tiers.iter_mut().find(|(tier, _)| *tier == served.tier) {
+                        count.1 += 1;
+                    }
+                    if served.tier == PRIMARY_TIER {
+                        Outcome::Success
+                    } else {
+                        Outcome::Fallback
+                    }
+                }
+                Err(e) if matches!(e.failures.as_slice(), [(_, ServiceError::NotFound { .. })]) => Outcome::NotFound,
+                Err(e) => Outcome::Failed(e.to_string()),
+            },
+            Design::Retry => classify(service.handle_request_with_retry(req.clone(), RetryPolicy::default())),
+        };
+        
+        breaker_transitions.extend(breaker.take_transitions().into_iter().map(|(_, from, to)| (req.id, from, to)));
+        let crashed = outcome == Outcome::Crashed;
+        // A crashed request's caller never hears back
+        latencies.push((!crashed).then(|| service.busy() - before));
+        request_attempts.push(service.attempts() - attempts_before);
+        outcomes.push((req.id, outcome));
+        if crashed {
+            break;
+        }
+    }
+    let duration = start.elapsed();
+    
+    // Everything after a crash is lost
+    let lost = &requests[outcomes.len()..];
+    outcomes.extend(lost.iter().map(|req| (req.id, Outcome::Dropped)));
+    latencies.resize(requests.len(), None);
+    request_attempts.resize(requests.len(), 0);
+    
+    let snapshot = metrics.snapshot();
+    let successful = snapshot.successes as usize;
+    let not_found = snapshot.not_found as usize;
+    let deadline_exceeded = snapshot.deadline_exceeded as usize;
+    let rejected = snapshot.rejected as usize;
+    let failed = (snapshot.handled_errors + snapshot.fallbacks + snapshot.panics) as usize;
+    let dropped = requests.len() - snapshot.total() as usize;
+    let good = successful + not_found;
+    let availability = if outcomes.is_empty() {
+        1.0
+    } else {
+        good as f64 / outcomes.len() as f64
+    };
+    let availability_interval = stats::wilson_interval(good as u64, outcomes.len() as u64, 0.95);
+    
+    SimulationReport {
+        design,
+        successful,
+        not_found,
+        failed,
+        deadline_exceeded,
+        rejected,
+        dropped,
+        duration,
+        availability,
+        availability_interval,
+        outcomes,
+        seed,
+        attempts: service.attempts(),
+        backoff: service.backoff(),
+        metrics: snapshot,
+        last_panic: metrics.last_panic(),
+        tiers,
+        latencies,
+        arrivals: requests.iter().map(|req| req.arrival).collect(),
+        request_attempts,
+        breaker_transitions,
+    }
+}
+
+/// A service level objective: the share of requests that must succeed,
+/// measured over a rolling window
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Slo {
+    pub target: f64, // 0.999 for "three nines"
+    pub window: Duration,
+}
+
+/// 99.9% over 30 days - the SLO the simulation summary holds every design to
+pub const DEFAULT_SLO: Slo = Slo { target: 0.999, window: Duration::from_secs(30 * 24 * 3600) };
+
+/// Failed requests `slo` allows out of `total_requests`, rounded down
+pub fn error_budget(slo: &Slo, total_requests: u64) -> u64 {
+    // The nudge keeps (1 - 0.999) × 1000 = 0.99999... from flooring to 0
+    ((1.0 - slo.target).clamp(0.0, 1.0) * total_requests as f64 + 1e-6).floor() as u64
+}
+
+/// Budget left after the run; negative once failures have overspent it
+pub fn budget_remaining(slo: &Slo, report: &SimulationReport) -> i64 {
+    let budget = error_budget(slo, report.outcomes.len() as u64);
+    // Everything short of an answer counts against it, as in `availability`
+    let bad = report.outcomes.iter().filter(|(_, outcome)| !outcome.is_good()).count();
+    budget as i64 - bad as i64
+}
+
+/// How fast the requests that arrived in the first `elapsed` of the run
+/// spent the budget: 1.0 spends exactly all of it by the end of the window,
+/// 10.0 spends it in a tenth of the window. 0 when nothing had arrived.
+pub fn burn_rate(slo: &Slo, report: &SimulationReport, elapsed: Duration) -> f64 {
+    let (arrived, bad) = report.outcomes.iter()
+        .zip(&report.arrivals)
+        .filter(|(_, &arrival)| arrival <= elapsed)
+        .fold((0u64, 0u64), |(arrived, bad), ((_, outcome), _)| {
+            (arrived + 1, bad + u64::from(!outcome.is_good()))
+        });
+    if arrived == 0 {
+        return 0.0;
+    }
+    let error_rate = bad as f64 / arrived as f64;
+    let allowed = 1.0 - slo.target;
+    if allowed > 0.0 {
+        error_rate / allowed
+    } else if error_rate > 0.0 {
+        f64::INFINITY // a 100% target has no budget to burn
+    } else {
+        0.0
+    }
+}
+
+/// One design's line in a ComparisonTable
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonRow {
+    pub design: Design,
+    pub successful: usize,
+    pub failed: usize, // errors, fallbacks, crashes, late and shed requests
+    pub dropped: usize,
+    pub availability: f64,
+    pub p99_ms: Option<f64>, // infinite when the caller never heard back
+}
+
+/// Every design on the same seeded load - the differences are the design's alone
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonTable {
+    pub seed: u64,
+    pub rows: Vec<ComparisonRow>,
+}
+
+/// Runs `config`'s load through each design in `Design::ALL` with one shared seed
+pub fn compare_designs(config: &SimConfig) -> ComparisonTable {
+    let seed = config.seed.unwrap_or_else(entropy_seed);
+    let config = SimConfig { seed: Some(seed), ..*config };
+    let rows = Design::ALL.iter()
+        .map(|&design| {
+            let report = simulate_with_config(design, &config);
+            ComparisonRow {
+                design,
+                successful: report.successful,
+                failed: report.failed + report.deadline_exceeded + report.rejected,
+                dropped: report.dropped,
+                availability: report.availability,
+                p99_ms: stats::percentile(&report.sorted_latencies_ms(), 99.0),
+            }
+        })
+        .collect();
+    ComparisonTable { seed, rows }
+}
+
+impl fmt::Display for ComparisonTable {
+    /// Columns are as wide as their widest cell; text left, numbers right
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let header = ["Design", "Successes", "Failures", "Dropped", "Availability", "p99 latency"];
+        let cells: Vec<[String; 6]> = self.rows.iter()
+            .map(|row| [
+                row.design.to_string(),
+                row.successful.to_string(),
+                row.failed.to_string(),
+                row.dropped.to_string(),
+                format!("{:.1}%", row.availability * 100.0),
+                match row.p99_ms {
+                    Some(ms) if ms.is_infinite() => String::from("∞"),
+                    Some(ms) => format!("{:.1}ms", ms),
+                    None => String::from("-"),
+                },
+            ])
+            .collect();
+        let mut widths = header.map(|title| title.chars().count());
+        for row in &cells {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        
+        let line = |f: &mut fmt::Formatter, row: &[&str]| -> fmt::Result {
+            for (i, (cell, width)) in row.iter().zip(widths).enumerate() {
+                if i == 0 {
+                    write!(f, "  {:<width$}", cell)?;
+                } else {
+                    write!(f, "  {:>width$}", cell)?;
+                }
+            }
+            writeln!(f)
+        };
+        line(f, &header)?;
+        let rule: Vec<String> = widths.iter().map(|width| "─".repeat(*width)).collect();
+        line(f, &rule.iter().map(String::as_str).collect::<Vec<_>>())?;
+        for row in &cells {
+            line(f, &row.iter().map(String::as_str).collect::<Vec<_>>())?;
+        }
+        write!(f, "  Seed: {} (replay with --seed {})", self.seed, self.seed)
+    }
+}
+
+/// Availability over many seeded runs of one design
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonteCarloSummary {
+    pub design: Design,
+    pub iterations: usize,
+    pub mean_availability: f64,
+    pub stddev_availability: f64, // sample standard deviation; 0 below two runs
+    pub min_availability: f64,
+    pub max_availability: f64,
+    pub crashed_runs: usize, // runs where a panic took the service down
+}
+
+impl MonteCarloSummary {
+    pub fn crash_fraction(&self) -> f64 {
+        if self.iterations == 0 {
+            0.0
+        } else {
+            self.crashed_runs as f64 / self.iterations as f64
+        }
+    }
+}
+
+impl fmt::Display for MonteCarloSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "=== Monte Carlo: {} × {} ===", self.iterations, self.design)?;
+        writeln!(f, "  Availability: mean {:.2}%, std dev {:.2}%",
+                 self.mean_availability * 100.0, self.stddev_availability * 100.0)?;
+        writeln!(f, "  Range: {:.2}% to {:.2}%",
+                 self.min_availability * 100.0, self.max_availability * 100.0)?;
+        write!(f, "  Runs that crashed: {} ({:.2}%)", self.crashed_runs, self.crash_fraction() * 100.0)
+    }
+}
+
+/// Runs `simulate_with_config` `iterations` times, each with its own seed
+/// drawn from `config.seed`, and summarizes availability. Nothing is printed
+/// and no run is kept: memory stays flat however many iterations are asked for.
+pub fn run_monte_carlo(design: Design, config: &SimConfig, iterations: usize) -> MonteCarloSummary {
+    let mut seeds = XorShiftRng::seed_from_u64(config.seed.unwrap_or_else(entropy_seed));
+    let mut availability = stats::Running::new();
+    let mut min = f64::INFINITY;
+    let mut max = f64::NEG_INFINITY;
+    let mut crashed_runs = 0;
+    
+    for _ in 0..iterations {
+        let run = SimConfig { seed: Some(seeds.next_u64()), ..*config };
+        let report = simulate_with_config(design, &run);
+        availability.push(report.availability);
+        min = min.min(report.availability);
+        max = max.max(report.availability);
+        if report.metrics.panics > 0 {
+            crashed_runs += 1;
+        }
+    }
+    
+    MonteCarloSummary {
+        design,
+        iterations,
+        mean_availability: availability.mean().unwrap_or(0.0),
+        stddev_availability: availability.stddev().unwrap_or(0.0),
+        min_availability: if iterations == 0 { 0.0 } else { min },
+        max_availability: if iterations == 0 { 0.0 } else { max },
+        crashed_runs,
+    }
+}
+
+/// Compares failures per one-second window with the Poisson distribution
+/// they should follow: arrivals at `arrival_rate`, each failing with λ
+pub fn write_failure_windows(
+    out: &mut dyn Write,
+    report: &SimulationReport,
+    arrival_rate: f64,
+    failure_rate: f64,
+) -> io::Result<()> {
+    if report.dropped > 0 {
+        return writeln!(out, "  Failures per second: not meaningful - the service crashed");
+    }
+    let windows = report.failures_per_window(Duration::from_secs(1));
+    let total: u64 = windows.iter().sum();
+    let expected = arrival_rate * failure_rate;
+    writeln!(out, "  Failures per second: {:.2} observed, {:.2} expected (arrival rate × λ)",
+                  total as f64 / windows.len() as f64, expected)?;
+    writeln!(out, "    k   observed   Poisson P(k)")?;
+    let max_k = windows.iter().copied().max().unwrap_or(0);
+    for k in 0..=max_k {
+        let pmf = stats::poisson_pmf(expected, k);
+        let seen = windows.iter().filter(|&&count| count == k).count();
+        writeln!(out, "    {:<3} {:>7.1}%   {:>10.1}%", k,
+                      seen as f64 * 100.0 / windows.len() as f64, pmf * 100.0)?;
+    }
+    
+    // Doubles as a self-test of the failure injector
+    let fit = stats::chi_square_poisson(&windows, expected);
+    if fit.dof == 0 {
+        writeln!(out, "  Too few windows to test the fit - run for longer")
+    } else if fit.consistent(0.05) {
+        writeln!(out, "  {}", paint(format!("✓ Observed failures are consistent with Poisson(λ={:.2}), p≈{:.2} (χ² = {:.1}, {} dof)",
+                                           expected, fit.p_value_approx, fit.statistic, fit.dof), Color::Green))
+    } else {
+        writeln!(out, "  {}", paint(format!("⚠ Observed failures don't fit Poisson(λ={:.2}), p≈{:.2e} (χ² = {:.1}, {} dof)",
+                                           expected, fit.p_value_approx, fit.statistic, fit.dof), Color::Yellow))
+    }
+}
+
+/// Latencies up to the p99 in ten buckets; the tail beyond it, requests
+/// that never finished included, is counted apart from the bars
+fn write_latency_histogram(out: &mut dyn Write, sorted_ms: &[f64]) -> io::Result<()> {
+    let Some(p99) = stats::percentile(sorted_ms, 99.0).filter(|p99| p99.is_finite()) else {
+        return Ok(());
+    };
+    let mut histogram = stats::Histogram::new(0.0, p99.floor() + 1.0, 10);
+    sorted_ms.iter().for_each(|&ms| histogram.record(ms));
+    writeln!(out, "  Latency distribution (ms):")?;
+    for line in histogram.render(30).lines() {
+        writeln!(out, "    {}", line)?;
+    }
+    Ok(())
+}
+
+/// Presentation for a SimulationReport: per-request lines, then the summary,
+/// as much of it as `verbosity` asks for
+pub fn write_report(out: &mut dyn Write, report: &SimulationReport, verbosity: Verbosity) -> io::Result<()> {
+    if verbosity == Verbosity::Quiet {
+        return Ok(());
+    }
+    writeln!(out, "\n=== Simulating Production Load: {} ===", report.design)?;
+    
+    if verbosity >= Verbosity::PerRequest {
+        write_request_lines(out, report, verbosity == Verbosity::Debug)?;
+    } else {
+        writeln!(out, "  ({} requests - summary only, -v lists each one)", report.outcomes.len())?;
+    }
+    
+    writeln!(out, "\n{}", report.metrics)?;
+    if let Some(message) = &report.last_panic {
+        let noun = if report.metrics.panics == 1 { "panic" } else { "panics" };
+        writeln!(out, "  {} {} observed: {}", report.metrics.panics, noun, message)?;
+    }
+    let served: usize = report.tiers.iter().map(|(_, count)| count).sum();
+    if served > 0 {
+        let breakdown: Vec<String> = report.tiers.iter()
+            .map(|(tier, count)| format!("{} {:.1}%", tier, *count as f64 * 100.0 / served as f64))
+            .collect();
+        writeln!(out, "  Served by: {}", breakdown.join(", "))?;
+    }
+    let latencies = report.sorted_latencies_ms();
+    let show = |p: f64| match stats::percentile(&latencies, p) {
+        Some(ms) if ms.is_infinite() => String::from("∞"),
+        Some(ms) => format!("{:.1}ms", ms),
+        None => String::from("-"),
+    };
+    let spread = report.latency_stats_ms().stddev().map_or_else(|| String::from("-"), |ms| format!("{:.1}ms", ms));
+    writeln!(out, "  Latency: p50 {}, p95 {}, p99 {}, max {}, std dev {}",
+                  show(50.0), show(95.0), show(99.0), show(100.0), spread)?;
+    if verbosity != Verbosity::PerRequest {
+        // The summary's stand-in for the request lines; debug shows everything
+        write_latency_histogram(out, &latencies)?;
+    }
+    if report.dropped > 0 {
+        writeln!(out, "  Dropped: {} (never processed)", report.dropped)?;
+    }
+    let processed = report.outcomes.len() - report.dropped;
+    if report.attempts > processed {
+        writeln!(out, "  Attempts: {} for {} requests ({:.2}× load on the upstream), {:?} spent backing off",
+                      report.attempts, processed,
+                      report.attempts as f64 / processed as f64, report.backoff)?;
+    }
+    writeln!(out, "  Service uptime: {:?}", report.duration)?;
+    write_slo(out, report, &DEFAULT_SLO)?;
+    let (low, high) = report.availability_interval;
+    writeln!(out, "  Availability: {} [{:.1}%, {:.1}%] at 95% confidence",
+                  paint(format!("{:.1}%", report.availability * 100.0), style::availability_color(report.availability)),
+                  low * 100.0, high * 100.0)?;
+    writeln!(out, "  Seed: {} (replay with --seed {})", report.seed, report.seed)
+}
+
+/// Whether the run kept to `slo`, where the budget ran out if it didn't,
+/// and how long the window's budget lasts at the observed burn rate
+fn write_slo(out: &mut dyn Write, report: &SimulationReport, slo: &Slo) -> io::Result<()> {
+    let total = report.outcomes.len() as u64;
+    let budget = error_budget(slo, total);
+    let remaining = budget_remaining(slo, report);
+    let verdict = if remaining >= 0 {
+        paint(format!("within budget, {} left", remaining), Color::Green)
+    } else {
+        paint(format!("exhausted, {} over", -remaining), Color::Red)
+    };
+    writeln!(out, "  SLO {}%: error budget {} of {} requests - {}",
+                  slo.target * 100.0, budget, total, verdict)?;
+    
+    // The request that overspent it, and how far into the run that was
+    let mut bad = 0;
+    let overspent = report.outcomes.iter().zip(&report.arrivals).find(|((_, outcome), _)| {
+        bad += u64::from(!outcome.is_good());
+        bad > budget
+    });
+    if let Some(((id, outcome), arrival)) = overspent {
+        let cause = match outcome {
+            Outcome::Crashed | Outcome::Dropped => " - the crash spent it all at once",
+            _ => "",
+        };
+        writeln!(out, "    Exhausted at request {} ({:?} into the run){}", id, arrival, cause)?;
+    }
+    
+    let elapsed = report.arrivals.iter().max().copied().unwrap_or_default();
+    let burn = burn_rate(slo, report, elapsed);
+    if burn > 1.0 {
+        let lasts = slo.window.div_f64(burn);
+        writeln!(out, "    Burn rate {:.1}×: a {}-day budget lasts {}", burn,
+                      slo.window.as_secs() / 86_400, stats::short_duration(lasts))?;
+    } else {
+        writeln!(out, "    Burn rate {:.2}×: the budget outlasts the window", burn)?;
+    }
+    Ok(())
+}
+
+/// One line per processed request; with `details`, a second line with
+/// its arrival time, upstream attempts and latency
+fn write_request_lines(out: &mut dyn Write, report: &SimulationReport, details: bool) -> io::Result<()> {
+    let requests = report.outcomes.iter()
+        .zip(&report.latencies)
+        .zip(&report.arrivals)
+        .zip(&report.request_attempts);
+    let mut transitions = report.breaker_transitions.iter().peekable();
+    for ((((id, outcome), latency), arrival), attempts) in requests {
+        match outcome {
+            Outcome::Success => writeln!(out, "  Request {}: {}", id, paint("✓", Color::Green))?,
+            Outcome::NotFound => writeln!(out, "  Request {}: {}", id, paint("✓ Not found (an answer)", Color::Green))?,
+            Outcome::Failed(e) => {
+                writeln!(out, "  Request {}: {}", id, paint(format!("✗ Error logged: {}", e), Color::Red))?
+            }
+            Outcome::Fallback => writeln!(out, "  Request {}: {}", id, paint("⚠ Degraded (fallback)", Color::Yellow))?,
+            Outcome::DeadlineExceeded => {
+                writeln!(out, "  Request {}: {}", id, paint("⏱ Deadline exceeded", Color::Yellow))?
+            }
+            Outcome::Crashed => {
+                writeln!(out, "  Request {}: {}", id,
+                              paint("✗ SERVICE CRASHED - All subsequent requests lost!", Color::Red))?;
+                writeln!(out, "  {}", paint(format!("💀 Total system failure. Remaining {} requests dropped.",
+                                                    report.dropped), Color::Red))?;
+            }
+            Outcome::Rejected => {
+                writeln!(out, "  Request {}: {}", id, paint("⛔ Shed by the rate limiter", Color::Yellow))?
+            }
+            Outcome::Dropped => continue,
+        }
+        if details {
+            let latency = latency.map_or_else(|| String::from("no answer"), |latency| format!("{:?}", latency));
+            let plural = if *attempts == 1 { "" } else { "s" };
+            writeln!(out, "      arrived at {:?}, {} upstream attempt{}, {}", arrival, attempts, plural, latency)?;
+            while let Some((_, from, to)) = transitions.next_if(|(at, _, _)| at == id) {
+                writeln!(out, "      breaker: {:?} → {:?}", from, to)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::better_approaches::User;
+    
+    fn request(id: u64, op: OpKind) -> Request {
+        Request { id, op, payload: Some(format!("req{}", id)), deadline: None, arrival: Duration::ZERO }
+    }
+    
+    fn directory(available: bool) -> UserDirectory {
+        let mut directory = UserDirectory::new(vec![User { id: 1, name: String::from("Ada") }]);
+        directory.set_available(available);
+        directory
+    }
+    
+    #[test]
+    fn lookup_outcomes_reach_distinct_report_buckets() {
+        for design in [Design::Safe, Design::Resilient, Design::Retry] {
+            let requests = [request(1, OpKind::LookupUser(1)), request(2, OpKind::LookupUser(99))];
+            let up = run_simulation(design, &requests, Service::with_seed(0.0, 3).with_directory(directory(true)), 3, None);
+            assert_eq!((up.successful, up.not_found, up.failed), (1, 1, 0), "{}", design);
+            assert_eq!(up.outcomes[1], (2, Outcome::NotFound), "{}", design);
+            assert_eq!(up.availability, 1.0, "{}: not found is an answer", design);
+            
+            let down = run_simulation(design, &requests[..1], Service::with_seed(0.0, 3).with_directory(directory(false)), 3, None);
+            assert_eq!((down.successful, down.not_found, down.failed), (0, 0, 1), "{}", design);
+            assert_eq!(down.availability, 0.0, "{}", design);
+        }
+    }
+    
+    #[test]
+    fn not_found_is_not_retried() {
+        let mut service = Service::with_seed(0.0, 5).with_directory(directory(true));
+        let result = service.handle_request_with_retry(request(1, OpKind::LookupUser(99)), RetryPolicy::default());
+        assert_eq!(result, Err(ServiceError::NotFound { what: String::from("user 99") }));
+        assert_eq!(service.attempts(), 1);
+        assert_eq!(service.metrics().snapshot().not_found, 1);
+    }
+    
+    #[test]
+    fn failure_fraction_converges_to_lambda() {
+        let mut service = Service::with_seed(0.1, 42);
+        let requests = generate_requests(10_000);
+        let failed = requests.into_iter()
+            .map(|request| Request { deadline: None, ..request })
+            .filter(|request| service.handle_request_safe(request.clone()).is_err())
+            .count();
+        let fraction = failed as f64 / 10_000.0;
+        // σ = √(0.1 × 0.9 / 10000) = 0.003; five of them
+        assert!((fraction - 0.1).abs() < 0.015, "observed {}", fraction);
+    }
+    
+    #[test]
+    fn seeded_report_tells_failed_from_dropped() {
+        let requests = default_requests();
+        let safe = simulate_production_load_seeded(Design::Safe, &requests, 42);
+        assert!(safe.availability > 0.7, "availability {}", safe.availability);
+        assert_eq!((safe.successful + safe.failed, safe.dropped), (requests.len(), 0));
+        
+        let unsafe_run = simulate_production_load_seeded(Design::Unsafe, &requests, 42);
+        assert_eq!(unsafe_run.failed, 1, "the request that crashed it");
+        assert_eq!(unsafe_run.successful + unsafe_run.failed + unsafe_run.dropped, requests.len());
+        assert!(unsafe_run.dropped > 0);
+    }
+    
+    #[test]
+    fn zero_failure_rate_is_fully_available_for_every_design() {
+        let config = SimConfig { requests: 500, failure_rate: 0.0, seed: Some(9), ..SimConfig::default() };
+        for design in Design::ALL {
+            let report = simulate_with_config(design, &config);
+            assert_eq!(report.availability, 1.0, "{}", design);
+            assert_eq!(report.successful, 500, "{}", design);
+        }
+    }
+    
+    #[test]
+    fn same_seed_same_report() {
+        let config = |seed| SimConfig { requests: 200, failure_rate: 0.2, seed: Some(seed), ..SimConfig::default() };
+        for design in Design::ALL {
+            // Wall-clock time is the one thing a seed can't fix
+            let run = |seed| SimulationReport { duration: Duration::ZERO, ..simulate_with_config(design, &config(seed)) };
+            assert_eq!(run(42), run(42), "{}", design);
+            assert_ne!(run(42).outcomes, run(43).outcomes, "{}", design);
+        }
+    }
+    
+    #[test]
+    fn breaker_opens_then_recovers_through_half_open() {
+        let clock = ManualClock::new();
+        let mut breaker = CircuitBreaker::new(3, Duration::from_secs(1), 2, &clock);
+        for _ in 0..2 {
+            assert!(breaker.allow_request());
+            breaker.record_failure();
+        }
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        breaker.record_failure();
+        assert_eq!(breaker.state(), BreakerState::Open);
+        
+        clock.advance(Duration::from_millis(999));
+        assert!(!breaker.allow_request());
+        clock.advance(Duration::from_millis(1));
+        assert!(breaker.allow_request());
+        assert_eq!(breaker.state(), BreakerState::HalfOpen);
+        
+        // A failed probe opens it again, for another full open duration
+        breaker.record_failure();
+        assert_eq!(breaker.state(), BreakerState::Open);
+        clock.advance(Duration::from_millis(500));
+        assert!(!breaker.allow_request());
+        clock.advance(Duration::from_millis(500));
+        assert!(breaker.allow_request());
+        breaker.record_success();
+        assert_eq!(breaker.state(), BreakerState::HalfOpen);
+        breaker.record_success();
+        assert_eq!(breaker.state(), BreakerState::Closed);
+    }
+    
+    #[test]
+    fn half_open_breaker_admits_only_as_many_probes_as_it_needs() {
+        let clock = ManualClock::new();
+        let mut breaker = CircuitBreaker::new(1, Duration::from_secs(1), 2, &clock);
+        breaker.record_failure();
+        clock.advance(Duration::from_secs(1));
+        
+        assert!(breaker.allow_request());
+        assert!(breaker.allow_request());
+        assert_eq!(breaker.state(), BreakerState::HalfOpen);
+        for _ in 0..5 {
+            assert!(!breaker.allow_request(), "both probes are still out");
+        }
+        
+        // One probe answers: its slot frees up for another
+        breaker.record_success();
+        assert!(breaker.allow_request());
+        assert!(!breaker.allow_request());
+        breaker.record_success();
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        for _ in 0..5 {
+            assert!(breaker.allow_request(), "closed: no limit");
+        }
+        
+        // A failed probe reopens it, and the next half-open period starts afresh
+        breaker.record_failure();
+        clock.advance(Duration::from_secs(1));
+        assert!(breaker.allow_request());
+        assert!(breaker.allow_request());
+        breaker.record_failure();
+        assert_eq!(breaker.state(), BreakerState::Open);
+        assert!(!breaker.allow_request());
+        clock.advance(Duration::from_secs(1));
+        assert!(breaker.allow_request());
+        assert!(breaker.allow_request());
+        assert!(!breaker.allow_request());
+        
+        // A probe that says nothing about the upstream still gives its slot back
+        breaker.record_inconclusive();
+        assert_eq!(breaker.state(), BreakerState::HalfOpen);
+        assert!(breaker.allow_request());
+        assert!(!breaker.allow_request());
+    }
+    
+    #[test]
+    fn breaker_counts_an_answer_past_the_deadline_as_a_failure() {
+        let slow = LatencyModel { base: Duration::from_millis(300), tail_scale: Duration::ZERO, ..LatencyModel::default() };
+        let mut service = Service::with_seed(0.0, 1).with_latency(LatencyModel { max: Duration::from_secs(1), ..slow });
+        let clock = ManualClock::new();
+        let mut breaker = CircuitBreaker::new(2, Duration::from_secs(1), 1, &clock);
+        for id in 1..=2 {
+            let late = Request { deadline: Some(DEFAULT_DEADLINE), ..request(id, OpKind::Process) };
+            let response = service.handle_request_with_breaker(&mut breaker, late);
+            assert_eq!(response.body, "Fallback response");
+        }
+        assert_eq!(breaker.state(), BreakerState::Open);
+        assert_eq!(service.metrics().snapshot().successes, 0);
+    }
+    
+    #[test]
+    fn retry_delays_come_from_the_backoff_schedule() {
+        let ms = |delays: Vec<Duration>| delays.iter().map(Duration::as_millis).collect::<Vec<_>>();
+        let policy = RetryPolicy { jitter: 0.0, ..RetryPolicy::default() };
+        assert_eq!(ms(policy.delays(1).take(4).collect()), vec![100, 200, 400, 800]);
+        let tripling = RetryPolicy { multiplier: 3.0, ..policy };
+        assert_eq!(ms(tripling.delays(1).take(3).collect()), vec![100, 300, 900]);
+        for (retry, delay) in RetryPolicy::default().delays(7).take(5).enumerate() {
+            let exact = 100.0 * 2f64.powi(retry as i32);
+            let ms = delay.as_secs_f64() * 1000.0;
+            assert!(ms >= exact * 0.8 - 1e-6 && ms <= exact * 1.2 + 1e-6, "retry {}: {}ms", retry, ms);
+        }
+        
+        // Every failure transient: the retries run out, waiting out the schedule between them
+        let failing = || Service::with_seed(1.0, 3).with_transient_share(1.0);
+        let mut service = failing();
+        let result = service.handle_request_with_retry(request(1, OpKind::Process), RetryPolicy { max_attempts: 4, ..policy });
+        assert!(result.is_err());
+        assert_eq!(service.attempts(), 4);
+        assert_eq!(service.backoff(), Duration::from_millis(100 + 200 + 400));
+        
+        let mut service = failing();
+        let _ = service.handle_request_with_retry(request(1, OpKind::Process), RetryPolicy { max_attempts: 0, ..policy });
+        assert_eq!((service.attempts(), service.backoff()), (1, Duration::ZERO));
+    }
+    
+    #[test]
+    fn breaker_backs_off_between_probes_and_resets_once_closed() {
+        let clock = ManualClock::new();
+        let schedule = BackoffIterator::new(BackoffStrategy::Exponential, Duration::from_secs(1), Duration::from_secs(4));
+        let mut breaker = CircuitBreaker::new(1, Duration::from_secs(1), 1, &clock).with_backoff(schedule);
+        breaker.record_failure();
+        for expected in [1, 2, 4, 4] {
+            assert_eq!(breaker.open_duration(), Duration::from_secs(expected));
+            clock.advance(Duration::from_secs(expected) - Duration::from_millis(1));
+            assert!(!breaker.allow_request());
+            clock.advance(Duration::from_millis(1));
+            assert!(breaker.allow_request());
+            breaker.record_failure(); // the probe failed: open again, for longer
+        }
+        clock.advance(Duration::from_secs(4));
+        assert!(breaker.allow_request());
+        breaker.record_success();
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        breaker.record_failure();
+        assert_eq!(breaker.open_duration(), Duration::from_secs(1));
+    }
+    
+    #[test]
+    fn only_transient_failures_are_retried() {
+        let policy = RetryPolicy { max_attempts: 5, ..RetryPolicy::default() };
+        let mut permanent = Service::with_seed(1.0, 11).with_transient_share(0.0);
+        let result = permanent.handle_request_with_retry(request(1, OpKind::Process), policy);
+        assert!(matches!(result, Err(ServiceError::Permanent { .. })), "{:?}", result);
+        assert_eq!(permanent.attempts(), 1);
+        
+        let mut transient = Service::with_seed(1.0, 11).with_transient_share(1.0);
+        let result = transient.handle_request_with_retry(request(1, OpKind::Process), policy);
+        assert!(result.as_ref().is_err_and(ServiceError::is_transient), "{:?}", result);
+        assert_eq!(transient.attempts(), 5);
+        
+        let mut service = Service::with_seed(0.0, 11);
+        let bad = Request { payload: None, ..request(1, OpKind::Process) };
+        assert_eq!(service.handle_request_with_retry(bad, policy), Err(ServiceError::MissingInput));
+        assert_eq!(service.attempts(), 1);
+    }
+    
+    #[test]
+    fn counters_add_up_to_the_request_count() {
+        let mut service = Service::with_seed(0.3, 21);
+        let metrics = service.metrics();
+        let clock = ManualClock::new();
+        let mut limiter = RateLimiter::new(RateLimit { burst: 5, per_second: 1 }, &clock);
+        let mut requests = generate_requests(80).into_iter();
+        for request in requests.by_ref().take(20) {
+            let _ = service.handle_request_safe(request);
+        }
+        for request in requests.by_ref().take(20) {
+            service.handle_request_resilient(request);
+        }
+        for request in requests.by_ref().take(20) {
+            let _ = service.handle_request_with_retry(request, RetryPolicy::default());
+        }
+        for request in requests {
+            let _ = service.handle_request_limited(&mut limiter, request);
+        }
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.total(), 80, "{:?}", snapshot);
+        assert!(snapshot.handled_errors > 0 && snapshot.fallbacks > 0 && snapshot.rejected == 15, "{:?}", snapshot);
+        
+        for design in Design::ALL {
+            let report = simulate_with_config(design, &SimConfig { requests: 100, failure_rate: 0.3, seed: Some(5), ..SimConfig::default() });
+            assert_eq!(report.metrics.total() as usize + report.dropped, 100, "{}", design);
+        }
+    }
+    
+    #[test]
+    fn full_bulkhead_rejects_exactly_the_overflow() {
+        let bulkhead = Bulkhead::new(2);
+        let everyone_tried = std::sync::Barrier::new(10);
+        let results: Vec<bool> = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..10)
+                .map(|_| scope.spawn(|| {
+                    let permit = bulkhead.try_acquire();
+                    // Slow: hold the slot until all ten have asked
+                    everyone_tried.wait();
+                    permit.is_ok()
+                }))
+                .collect();
+            workers.into_iter().map(|worker| worker.join().unwrap_or(false)).collect()
+        });
+        assert_eq!(results.iter().filter(|&&ok| ok).count(), 2);
+        assert_eq!(results.iter().filter(|&&ok| !ok).count(), 8);
+        assert_eq!(bulkhead.in_flight(), 0);
+        
+        // Through the Service, a rejection is shed load, not a failure
+        let mut service = Service::with_seed(0.0, 1);
+        let metrics = service.metrics();
+        let held = (bulkhead.try_acquire(), bulkhead.try_acquire());
+        assert!(held.0.is_ok() && held.1.is_ok());
+        for id in 0..3 {
+            let result = service.handle_request_with_bulkhead(&bulkhead, request(id, OpKind::Process));
+            assert_eq!(result, Err(ServiceError::Overloaded));
+        }
+        drop(held);
+        assert!(service.handle_request_with_bulkhead(&bulkhead, request(3, OpKind::Process)).is_ok());
+        let snapshot = metrics.snapshot();
+        assert_eq!((snapshot.rejected, snapshot.handled_errors, snapshot.successes), (3, 0, 1));
+    }
+    
+    #[test]
+    fn token_bucket_admits_capacity_plus_elapsed_times_rate() {
+        let clock = ManualClock::new();
+        let mut limiter = RateLimiter::new(RateLimit { burst: 5, per_second: 10 }, &clock);
+        let mut admitted = 0;
+        for _ in 0..30 {
+            while limiter.try_acquire().is_ok() {
+                admitted += 1;
+            }
+            clock.advance(Duration::from_millis(100));
+        }
+        // 30 steps of 100ms, the last one not yet spent: 5 + 2.9s × 10
+        assert_eq!(admitted, 5 + 29);
+        assert_eq!(limiter.try_acquire(), Ok(()));
+        assert_eq!(limiter.try_acquire(), Err(ServiceError::RateLimited));
+        
+        // A long idle spell refills to capacity, never past it
+        clock.advance(Duration::from_secs(3600));
+        assert_eq!(limiter.available(), 5);
+        
+        // Fractions of a token carry over between refills
+        let mut limiter = RateLimiter::new(RateLimit { burst: 1, per_second: 3 }, &clock);
+        assert_eq!(limiter.try_acquire(), Ok(()));
+        for _ in 0..3 {
+            clock.advance(Duration::from_millis(111));
+            assert_eq!(limiter.try_acquire(), Err(ServiceError::RateLimited));
+        }
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(limiter.try_acquire(), Ok(()));
+    }
+    
+    #[test]
+    fn poisson_arrival_rate_converges_to_lambda() {
+        let mut rng = XorShiftRng::seed_from_u64(267);
+        let rate = 50.0;
+        let duration = Duration::from_secs(2_000);
+        let requests = poisson_arrivals(rate, duration, &mut rng);
+        let empirical = requests.len() as f64 / duration.as_secs_f64();
+        assert!((empirical - rate).abs() / rate < 0.02, "{} arrivals/s", empirical);
+        assert!(requests.windows(2).all(|pair| pair[0].arrival <= pair[1].arrival));
+        assert!(requests.iter().all(|req| req.arrival < duration));
+        
+        // Inter-arrival gaps are exponential: their mean is 1/λ
+        let last = requests.last().map_or(0.0, |req| req.arrival.as_secs_f64());
+        let mean_gap = last / requests.len() as f64;
+        assert!((mean_gap * rate - 1.0).abs() < 0.02, "mean gap {}s", mean_gap);
+        assert!(poisson_arrivals(0.0, duration, &mut rng).is_empty());
+        assert!(poisson_arrivals(f64::NAN, duration, &mut rng).is_empty());
+    }
+    
+    #[test]
+    fn zero_failure_rate_monte_carlo_has_zero_variance() {
+        let config = SimConfig { requests: 50, failure_rate: 0.0, seed: Some(269), ..SimConfig::default() };
+        for design in Design::ALL {
+            let summary = run_monte_carlo(design, &config, 200);
+            assert_eq!(summary.iterations, 200);
+            assert_eq!(summary.mean_availability, 1.0, "{}", design);
+            assert_eq!(summary.stddev_availability, 0.0, "{}", design);
+            assert_eq!((summary.min_availability, summary.max_availability), (1.0, 1.0), "{}", design);
+            assert_eq!(summary.crash_fraction(), 0.0, "{}", design);
+        }
+        
+        let risky = SimConfig { failure_rate: 0.2, ..config };
+        let summary = run_monte_carlo(Design::Unsafe, &risky, 200);
+        assert!(summary.crash_fraction() > 0.9, "{}", summary);
+        assert!(summary.stddev_availability > 0.0, "{}", summary);
+        assert!(summary.min_availability <= summary.mean_availability && summary.mean_availability <= summary.max_availability);
+    }
+    
+    #[test]
+    fn comparison_table_aligns_multi_digit_counts() {
+        let row = |design, successful, failed, dropped, availability, p99_ms| ComparisonRow {
+            design, successful, failed, dropped, availability, p99_ms,
+        };
+        let table = ComparisonTable {
+            seed: 7,
+            rows: vec![
+                row(Design::Unsafe, 3, 1, 9996, 0.0003, Some(f64::INFINITY)),
+                row(Design::Safe, 9012, 988, 0, 0.9012, Some(12.5)),
+                row(Design::Resilient, 10000, 0, 0, 1.0, None),
+            ],
+        };
+        let expected = [
+            "  Design     Successes  Failures  Dropped  Availability  p99 latency",
+            "  ─────────  ─────────  ────────  ───────  ────────────  ───────────",
+            "  unsafe             3         1     9996          0.0%            ∞",
+            "  safe            9012       988        0         90.1%       12.5ms",
+            "  resilient      10000         0        0        100.0%            -",
+            "  Seed: 7 (replay with --seed 7)",
+        ];
+        assert_eq!(table.to_string(), expected.join("\n"));
+        
+        let table = compare_designs(&SimConfig { requests: 300, failure_rate: 0.1, seed: Some(270), ..SimConfig::default() });
+        assert_eq!(table.rows.iter().map(|row| row.design).collect::<Vec<_>>(), Design::ALL);
+        for row in &table.rows {
+            assert_eq!(row.successful + row.failed + row.dropped, 300, "{:?}", row);
+        }
+    }
+    
+    /// RFC 4180 fields, for reading `write_csv` output back
+    fn parse_csv(text: &str) -> Vec<Vec<String>> {
+        let mut rows = Vec::new();
+        let mut row = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (quoted, c) {
+                (true, '"') if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                (_, '"') => quoted = !quoted,
+                (false, ',') => row.push(std::mem::take(&mut field)),
+                (false, '\n') => {
+                    row.push(std::mem::take(&mut field));
+                    rows.push(std::mem::take(&mut row));
+                }
+                (_, c) => field.push(c),
+            }
+        }
+        assert!(!quoted && field.is_empty() && row.is_empty(), "input ends mid-row");
+        rows
+    }
+    
+    #[test]
+    fn csv_round_trips_with_a_row_per_request() {
+        let config = SimConfig { requests: 30, failure_rate: 0.3, seed: Some(272), ..SimConfig::default() };
+        let mut report = simulate_with_config(Design::Safe, &config);
+        let awkward = "bad payload \"a,b\",\nsecond line";
+        report.outcomes[0].1 = Outcome::Failed(String::from(awkward));
+        let mut csv = Vec::new();
+        report.write_csv(&mut csv).unwrap();
+        
+        let rows = parse_csv(&String::from_utf8(csv).unwrap());
+        assert_eq!(rows[0].join(","), CSV_HEADER);
+        assert_eq!(rows.len() - 1, 30);
+        assert!(rows.iter().all(|row| row.len() == 5), "{:?}", rows);
+        assert_eq!(rows[1][4], awkward);
+        for (row, (id, outcome)) in rows[1..].iter().zip(&report.outcomes) {
+            assert_eq!(row[0], id.to_string());
+            assert_eq!(row[1], "safe");
+            assert_eq!(row[2], outcome.name());
+            assert_eq!(row[4], outcome.error().unwrap_or(""));
+        }
+    }
+    
+    #[test]
+    fn resilient_design_records_why_it_fell_back() {
+        let mut service = Service::with_seed(1.0, 292);
+        let metrics = service.metrics();
+        assert_eq!(metrics.last_fallback_cause(), None);
+        let response = service.handle_request_resilient(request(4, OpKind::Process));
+        assert_eq!(response.body, "Fallback response");
+        let cause = metrics.last_fallback_cause().unwrap_or_default();
+        assert!(cause.starts_with("request 4 failed ("), "{}", cause);
+        assert_eq!(metrics.snapshot().fallbacks, 1);
+    }
+    
+    #[test]
+    fn error_budgets_for_a_thousand_and_a_million_requests() {
+        // (1 - 0.999) × 1000 = 1 and × 1,000,000 = 1000, by hand
+        assert_eq!(error_budget(&DEFAULT_SLO, 1_000), 1);
+        assert_eq!(error_budget(&DEFAULT_SLO, 1_000_000), 1_000);
+        let four_nines = Slo { target: 0.9999, ..DEFAULT_SLO };
+        assert_eq!(error_budget(&four_nines, 1_000), 0); // 0.1 rounds down
+        assert_eq!(error_budget(&four_nines, 1_000_000), 100);
+        assert_eq!(error_budget(&Slo { target: 0.99, ..DEFAULT_SLO }, 1_000), 10);
+        
+        // 1000 requests a millisecond apart, two of the first 500 failing
+        let config = SimConfig { requests: 1000, failure_rate: 0.0, seed: Some(3), ..SimConfig::default() };
+        let outcomes: Vec<(u64, Outcome)> = (0..1000)
+            .map(|id| (id, if id == 10 || id == 20 { Outcome::Failed(String::from("boom")) } else { Outcome::Success }))
+            .collect();
+        let arrivals = (1..=1000).map(Duration::from_millis).collect();
+        let report = SimulationReport { outcomes, arrivals, ..simulate_with_config(Design::Safe, &config) };
+        assert_eq!(budget_remaining(&DEFAULT_SLO, &report), -1); // budget 1, spent 2
+        assert_eq!(budget_remaining(&Slo { target: 0.99, ..DEFAULT_SLO }, &report), 8);
+        // 2 of 500 is 0.4% against an allowed 0.1%: four times too fast
+        assert!((burn_rate(&DEFAULT_SLO, &report, Duration::from_millis(500)) - 4.0).abs() < 1e-9);
+        assert!((burn_rate(&DEFAULT_SLO, &report, Duration::from_secs(1)) - 2.0).abs() < 1e-9);
+        assert_eq!(burn_rate(&DEFAULT_SLO, &report, Duration::ZERO), 0.0);
+    }
+    
+    #[cfg(panic = "unwind")]
+    #[test]
+    fn supervised_pool_restarts_every_worker_that_dies() {
+        let requests = generate_requests(100);
+        let handler = |request: &Request| {
+            if request.id.is_multiple_of(10) {
+                panic!("request {} hit a bug", request.id);
+            }
+            Response { id: request.id, body: String::from("ok"), latency: Duration::from_millis(1) }
+        };
+        
+        let pool = WorkerPool::new(4, Supervision::Restart);
+        let report = pool.run(&requests, handler);
+        assert_eq!((report.served(), report.failed(), report.dropped()), (90, 10, 0));
+        assert_eq!((report.restarts, report.workers_left), (10, 4));
+        let snapshot = pool.metrics().snapshot();
+        assert_eq!((snapshot.successes, snapshot.panics, snapshot.restarts), (90, 10, 10));
+        // The request a worker died on is failed, with the panic that killed it
+        let (id, outcome) = &report.outcomes[9];
+        assert_eq!(*id, 10);
+        assert!(matches!(outcome, Outcome::Failed(e) if e.ends_with("request 10 hit a bug")), "{:?}", outcome);
+        
+        // Unsupervised, each of 10, 20, 30 and 40 takes a worker with it,
+        // and nobody is left for the rest
+        let report = WorkerPool::new(4, Supervision::None).run(&requests, handler);
+        assert_eq!((report.served(), report.failed(), report.dropped()), (36, 4, 60));
+        assert_eq!((report.restarts, report.workers_left), (0, 0));
+        assert!(report.outcomes[40..].iter().all(|(_, outcome)| *outcome == Outcome::Dropped));
+    }
+    
+    #[cfg(panic = "unwind")]
+    #[test]
+    fn pool_finishes_when_request_ids_repeat() {
+        // Three ids among twenty requests; the eleventh kills its worker
+        let requests: Vec<Request> = generate_requests(20).into_iter()
+            .map(|request| Request { id: request.id % 3, ..request })
+            .collect();
+        let handler = |request: &Request| {
+            if request.payload.as_deref() == Some("req11") {
+                panic!("request {} hit a bug", request.id);
+            }
+            Response { id: request.id, body: String::from("ok"), latency: Duration::from_millis(1) }
+        };
+        // A pool that never returns fails here instead of hanging the suite
+        let (done, reports) = mpsc::channel();
+        std::thread::spawn(move || {
+            let supervised = WorkerPool::new(3, Supervision::Restart).run(&requests, handler);
+            let alone = WorkerPool::new(1, Supervision::None).run(&requests, handler);
+            let _ = done.send((requests, supervised, alone));
+        });
+        let (requests, supervised, alone) = panics::without_panic_messages(|| reports.recv_timeout(Duration::from_secs(30)))
+            .expect("the pool returned");
+        
+        let ids: Vec<u64> = requests.iter().map(|request| request.id).collect();
+        assert_eq!(supervised.outcomes.iter().map(|(id, _)| *id).collect::<Vec<_>>(), ids);
+        assert_eq!((supervised.served(), supervised.failed(), supervised.dropped()), (19, 1, 0));
+        assert!(matches!(&supervised.outcomes[10].1, Outcome::Failed(e) if e.ends_with("request 2 hit a bug")));
+        assert_eq!((supervised.restarts, supervised.workers_left), (1, 3));
+        
+        // Alone, the worker dies on the eleventh and the other nine are never taken
+        assert_eq!((alone.served(), alone.failed(), alone.dropped()), (10, 1, 9));
+        assert!(matches!(alone.outcomes[10].1, Outcome::Failed(_)));
+        assert_eq!(alone.workers_left, 0);
+    }
+    
+    #[test]
+    fn backoff_respects_its_cap_and_jitter_bounds() {
+        let base = Duration::from_millis(10);
+        let cap = Duration::from_secs(1);
+        for seed in 0..20 {
+            // Exponential: never shrinks, never passes the cap
+            let exponential: Vec<Duration> = BackoffIterator::new(BackoffStrategy::Exponential, base, cap).take(200).collect();
+            assert!(exponential.windows(2).all(|pair| pair[0] <= pair[1]));
+            assert_eq!(exponential.last(), Some(&cap));
+            
+            // Full jitter: within [0, the exponential delay of the same attempt]
+            let full = BackoffIterator::new(BackoffStrategy::FullJitter, base, cap).with_seed(seed);
+            for (delay, ceiling) in full.zip(&exponential) {
+                assert!(delay <= *ceiling, "seed {}: {:?} above {:?}", seed, delay, ceiling);
+            }
+            
+            // Decorrelated: within [base, 3 × the previous delay], and the cap
+            let mut previous = base;
+            for delay in BackoffIterator::new(BackoffStrategy::DecorrelatedJitter, base, cap).with_seed(seed).take(200) {
+                assert!(delay >= base && delay <= (previous * 3).min(cap), "seed {}: {:?} after {:?}", seed, delay, previous);
+                previous = delay;
+            }
+            
+            // Extra ± jitter on top still stops at the cap
+            let spread = BackoffIterator::new(BackoffStrategy::Exponential, base, cap).with_jitter(0.5).with_seed(seed);
+            for (delay, exact) in spread.take(200).zip(&exponential) {
+                assert!(delay <= cap && delay.as_secs_f64() >= exact.as_secs_f64() * 0.5 - 1e-9, "{:?} from {:?}", delay, exact);
+            }
+        }
+    }
+    
+    #[test]
+    fn backoff_saturates_at_huge_attempt_counts() {
+        let cap = Duration::from_secs(3600);
+        for strategy in [BackoffStrategy::Exponential, BackoffStrategy::FullJitter, BackoffStrategy::DecorrelatedJitter] {
+            let mut delays = BackoffIterator::new(strategy, Duration::from_secs(1), cap).with_multiplier(1e6).with_jitter(1.0);
+            assert!(delays.by_ref().take(100_000).all(|delay| delay <= cap), "{:?}", strategy);
+            assert!(delays.next().is_some_and(|delay| delay <= cap));
+        }
+        // A cap of Duration::MAX, and a base to match: still no overflow
+        let mut huge = BackoffIterator::new(BackoffStrategy::DecorrelatedJitter, Duration::MAX, Duration::MAX).with_multiplier(f64::MAX);
+        assert!(huge.by_ref().take(1000).all(|delay| delay == Duration::MAX));
+        let policy = RetryPolicy { base_delay: Duration::MAX, multiplier: f64::INFINITY, jitter: 0.0, ..RetryPolicy::default() };
+        assert_eq!(policy.delays(1).nth(10_000), Some(Duration::MAX));
+    }
+    
+    #[test]
+    fn backoff_ends_for_good_at_max_elapsed() {
+        let limit = Duration::from_millis(500);
+        for strategy in [BackoffStrategy::Exponential, BackoffStrategy::FullJitter, BackoffStrategy::DecorrelatedJitter] {
+            for seed in 0..200 {
+                let mut delays = BackoffIterator::new(strategy, Duration::from_millis(20), Duration::from_millis(200))
+                    .with_max_elapsed(limit)
+                    .with_seed(seed);
+                let total: Duration = delays.by_ref().sum();
+                assert!(total <= limit, "{:?} seed {}: {:?}", strategy, seed, total);
+                // Fused: a smaller jittered delay that would still fit doesn't bring it back
+                assert!(delays.by_ref().take(50).next().is_none(), "{:?} seed {} came back", strategy, seed);
+            }
+        }
+        let exact: Vec<u64> = BackoffIterator::new(BackoffStrategy::Exponential, Duration::from_millis(100), Duration::MAX)
+            .with_max_elapsed(Duration::from_millis(700))
+            .map(|delay| delay.as_millis() as u64)
+            .collect();
+        assert_eq!(exact, [100, 200, 400]); // 800 would cross 700
+    }
+    
+    #[test]
+    fn resilient_design_trips_its_breaker_and_debug_output_shows_when() {
+        let config = SimConfig { requests: 250, failure_rate: 1.0, seed: Some(7), ..SimConfig::default() };
+        let report = simulate_with_config(Design::Resilient, &config);
+        
+        // Five failures open it; every second a single probe fails and it opens again
+        use BreakerState::{Closed, HalfOpen, Open};
+        assert_eq!(report.breaker_transitions, vec![
+            (5, Closed, Open),
+            (105, Open, HalfOpen),
+            (105, HalfOpen, Open),
+            (205, Open, HalfOpen),
+            (205, HalfOpen, Open),
+        ]);
+        assert_eq!(report.attempts, 7, "the upstream is only called while closed or probing");
+        assert!(simulate_with_config(Design::Safe, &config).breaker_transitions.is_empty());
+        
+        let mut debug = Vec::new();
+        write_report(&mut debug, &report, Verbosity::Debug).unwrap();
+        let debug = String::from_utf8(debug).unwrap();
+        let changes: Vec<&str> = debug.lines().filter(|line| line.contains("breaker:")).collect();
+        assert_eq!(changes.len(), 5);
+        assert_eq!(changes[0].trim(), "breaker: Closed → Open");
+        let after_request_5 = debug.split("Request 5:").nth(1).unwrap();
+        assert!(after_request_5.split("Request 6:").next().unwrap().contains("breaker: Closed → Open"));
+        
+        let mut per_request = Vec::new();
+        write_report(&mut per_request, &report, Verbosity::PerRequest).unwrap();
+        assert!(!String::from_utf8(per_request).unwrap().contains("breaker:"));
+    }
+    
+    #[test]
+    fn fallback_chain_reports_every_tier_in_order_when_all_fail() {
+        let static_calls = std::cell::Cell::new(0);
+        let mut fallbacks = FallbackChain::new()
+            .with_tier("replica", |_: &Request| Err(ServiceError::Timeout))
+            .with_tier("cache", |_: &Request| Err(ServiceError::Transient { reason: String::from("cache miss") }))
+            .with_tier("static", |_: &Request| {
+                static_calls.set(static_calls.get() + 1);
+                Err(ServiceError::Unsupported { feature: String::from("static pages") })
+            });
+        let mut service = Service::with_seed(1.0, 3).with_transient_share(0.0);
+        
+        let Err(e) = service.handle_request_chained(&mut fallbacks, request(1, OpKind::Process)) else {
+            panic!("every tier fails");
+        };
+        let tiers: Vec<&str> = e.failures.iter().map(|(tier, _)| *tier).collect();
+        assert_eq!(tiers, [PRIMARY_TIER, "replica", "cache", "static"]);
+        assert!(matches!(e.failures[0].1, ServiceError::Permanent { .. }));
+        assert_eq!(e.failures[1..].iter().map(|(_, error)| error.clone()).collect::<Vec<_>>(), [
+            ServiceError::Timeout,
+            ServiceError::Transient { reason: String::from("cache miss") },
+            ServiceError::Unsupported { feature: String::from("static pages") },
+        ]);
+        let message = e.to_string();
+        assert!(message.starts_with("all 4 tiers failed: primary: Permanent failure"), "{}", message);
+        assert!(message.ends_with("; replica: Upstream timed out; cache: Transient failure: cache miss; \
+                                   static: Unsupported: static pages is not implemented yet"), "{}", message);
+        assert_eq!(static_calls.get(), 1);
+        
+        // Counted once, as the upstream's error - not once per tier
+        let metrics = service.metrics().snapshot();
+        assert_eq!((metrics.handled_errors, metrics.fallbacks, metrics.successes), (1, 0, 0));
+        
+        // An open breaker skips the upstream, and says so as its failure
+        let clock = ManualClock::new();
+        let mut breaker = CircuitBreaker::new(1, Duration::from_secs(1), 1, &clock);
+        breaker.record_failure();
+        let attempts = service.attempts();
+        let Err(e) = service.handle_request_chained_with_breaker(&mut breaker, &mut fallbacks, request(2, OpKind::Process)) else {
+            panic!("every tier fails");
+        };
+        assert_eq!(e.failures[0], (PRIMARY_TIER, ServiceError::CircuitOpen));
+        assert_eq!(e.failures.len(), 4);
+        assert_eq!(service.attempts(), attempts);
+    }
+    
+    #[test]
+    fn report_tiers_break_down_who_answered() {
+        let config = |failure_rate| SimConfig { requests: 400, failure_rate, seed: Some(11), ..SimConfig::default() };
+        let names = |report: &SimulationReport| report.tiers.iter().map(|(tier, _)| *tier).collect::<Vec<_>>();
+        
+        let report = simulate_with_config(Design::Resilient, &config(0.5));
+        assert_eq!(names(&report), [PRIMARY_TIER, "cache", "static"]);
+        let count = |tier| report.tiers.iter().find(|(name, _)| *name == tier).map_or(0, |(_, count)| *count);
+        // The static page never fails, so every request lands in exactly one tier
+        assert_eq!(report.tiers.iter().map(|(_, count)| count).sum::<usize>(), 400);
+        assert_eq!(count(PRIMARY_TIER), report.successful);
+        assert_eq!((count("cache") + count("static")) as u64, report.metrics.fallbacks);
+        assert_eq!(count(PRIMARY_TIER), report.outcomes.iter().filter(|(_, o)| *o == Outcome::Success).count());
+        assert!(count("cache") > 0 && count("static") > 0, "{:?}", report.tiers);
+        
+        let healthy = simulate_with_config(Design::Resilient, &config(0.0));
+        assert_eq!(healthy.tiers, [(PRIMARY_TIER, 400), ("cache", 0), ("static", 0)]);
+        for design in [Design::Unsafe, Design::Safe, Design::Retry] {
+            assert!(simulate_with_config(design, &config(0.5)).tiers.is_empty(), "{}", design);
+        }
+    }
+    
+    #[test]
+    fn unsupported_op_is_refused_without_calling_the_upstream() {
+        let mut service = Service::with_seed(0.0, 1);
+        let result = service.handle_request_safe(request(1, OpKind::Export));
+        assert_eq!(result, Err(ServiceError::Unsupported { feature: String::from("export") }));
+        assert_eq!(service.attempts(), 0);
+        assert!(!ServiceError::Unsupported { feature: String::from("export") }.is_transient());
+    }
+    
+    #[test]
+    fn unsupported_op_flows_through_the_report_as_a_failure() {
+        let requests = [request(1, OpKind::Process), request(2, OpKind::Export), request(3, OpKind::Process)];
+        let report = run_simulation(Design::Safe, &requests, Service::with_seed(0.0, 7), 7, None);
+        assert_eq!((report.successful, report.failed), (2, 1));
+        assert_eq!(report.attempts, 2);
+        assert_eq!(report.request_attempts, vec![1, 0, 1]);
+        match &report.outcomes[1] {
+            (2, Outcome::Failed(e)) => assert!(e.starts_with("Unsupported"), "{}", e),
+            other => panic!("expected request 2 to fail as unsupported, got {:?}", other),
+        }
+    }
+}